@@ -0,0 +1,1724 @@
+mod output;
+
+use {
+    crate::output::{
+        CliBuyout, CliEscrow, CliMultisig, CliPlatformConfig, CliShareBid, CliTokenPool,
+    },
+    clap::{Parser, Subcommand},
+    serde::Serialize,
+    serde_json::json,
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        system_instruction,
+        transaction::Transaction,
+    },
+    std::{error::Error, path::PathBuf},
+    token_pool_client::{
+        instruction, max_members_for,
+        pda::{find_buyout_address, find_platform_config_address},
+        rpc::{
+            find_pools_by_target_mint, get_allowed_mints, get_buyout, get_escrow, get_inventory,
+            get_platform_config, get_share_bids, get_token_pool, get_token_pools,
+        },
+        state::{
+            allowed_mints_account_size, decode_escrow, decode_multisig, decode_share_bid,
+            decode_token_pool, escrow_account_size, inventory_account_size, multisig_account_size,
+            share_bid_account_size,
+        },
+        AdmissionPolicy, ListingKind, MaximumKind, PoolTargetKind, TokenPool, TokenPoolStage,
+        DELEGATE_CLAIM, DELEGATE_SELL, DELEGATE_VOTE,
+    },
+};
+
+type CommandResult = Result<(), Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(
+    name = "token-pool",
+    version,
+    about = "Operate token pools from the command line"
+)]
+struct Cli {
+    /// RPC url of the cluster, defaults to a local solana-test-validator
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+    /// Keypair file of the signer, which also pays for the transactions
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<PathBuf>,
+    /// Multisig managing the token pool, manager commands are signed by the multisig signers
+    #[arg(long, global = true)]
+    multisig: Option<Pubkey>,
+    /// Keypair file of a signer of the multisig, repeat it for every signer signing
+    #[arg(long = "multisig-signer", global = true)]
+    multisig_signers: Vec<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a token pool for buying the target token, the signer becomes its manager
+    Initialize {
+        /// Mint of the token which will be bought using pool money
+        #[arg(long)]
+        target_token: Pubkey,
+        /// Lamports needed to buy the target token
+        #[arg(long)]
+        target_amount: u64,
+        /// Minimum lamports a member has to contribute
+        #[arg(long)]
+        minimum_amount: u64,
+        #[arg(long, default_value = "")]
+        description: String,
+        /// Lamports given to the manager when the target token is bought
+        #[arg(long, default_value_t = 0)]
+        minimum_exemption_amount: u64,
+    },
+    /// Join a token pool by contributing lamports
+    AddMember { token_pool: Pubkey, amount: u64 },
+    /// Contribute more lamports to your share in a token pool
+    Upgrade { token_pool: Pubkey, amount: u64 },
+    /// Put your share in a token pool on sale for the given lamports
+    SellShare {
+        token_pool: Pubkey,
+        amount: u64,
+        /// Seconds after which the sale expires
+        #[arg(long, default_value_t = 0)]
+        expires_in: u32,
+        /// Only buyer allowed to buy the share, for a private sale
+        #[arg(long)]
+        buyer: Option<Pubkey>,
+        /// Member you act for as their delegate
+        #[arg(long)]
+        member: Option<Pubkey>,
+    },
+    /// Buy a share on sale, paying the price asked by the seller
+    BuyShare {
+        token_pool: Pubkey,
+        escrow: Pubkey,
+        /// Lamports to pay, defaults to the price in the escrow
+        #[arg(long)]
+        amount: Option<u64>,
+    },
+    /// Cancel your share sale or nft listing
+    Cancel {
+        escrow: Pubkey,
+        /// Token pool of the share on sale, needed for share sales
+        #[arg(long)]
+        token_pool: Option<Pubkey>,
+    },
+    /// Close an expired share sale or listing, giving the share or the listed account back to its seller
+    Expire {
+        escrow: Pubkey,
+        /// Token pool of the share on sale, needed for share sales
+        #[arg(long)]
+        token_pool: Option<Pubkey>,
+    },
+    /// Close a finished token pool and its treasury, their rent goes back to whoever paid it.
+    /// the manager closes a pool nobody has joined
+    ClosePool { token_pool: Pubkey },
+    /// Close the escrow of a sold nft, its rent goes back to whoever paid it
+    CloseEscrow { escrow: Pubkey },
+    /// Close the allowed mints of a finished or closed token pool, its rent goes back to the manager who set it up
+    CloseAllowedMints { allowed_mints: Pubkey },
+    /// Move what the treasury of a token pool holds over what it owes to you as the manager
+    Sweep { token_pool: Pubkey },
+    /// Move your share, or a part of it, to another wallet without selling it
+    Transfer {
+        token_pool: Pubkey,
+        recipient: Pubkey,
+        /// Share units to move, one unit is 0.01% of the pool, defaults to the whole share
+        #[arg(long, default_value_t = 0)]
+        units: u64,
+    },
+    /// List your nft for the given lamports, or for a dutch auction starting at them
+    ListNft {
+        #[arg(long)]
+        nft_mint: Pubkey,
+        /// Token account holding the nft
+        #[arg(long)]
+        nft_account: Pubkey,
+        amount: u64,
+        /// Unix timestamp at which a dutch auction reaches its floor price
+        #[arg(long, requires = "floor_price")]
+        end_time: Option<i64>,
+        /// Lowest price of a dutch auction
+        #[arg(long, requires = "end_time")]
+        floor_price: Option<u64>,
+        /// Seconds after which the listing expires
+        #[arg(long, default_value_t = 0)]
+        expires_in: u32,
+    },
+    /// List your nft on an english auction ending at the given unix timestamp
+    ListAuction {
+        #[arg(long)]
+        nft_mint: Pubkey,
+        /// Token account holding the nft
+        #[arg(long)]
+        nft_account: Pubkey,
+        /// Lowest first bid in lamports
+        reserve_price: u64,
+        /// Lamports each bid has to raise the highest bid by
+        min_bid_increment: u64,
+        end_time: i64,
+    },
+    /// Bid lamports on an english auction, with the treasury of a token pool you are a member of if given
+    Bid {
+        escrow: Pubkey,
+        amount: u64,
+        #[arg(long)]
+        token_pool: Option<Pubkey>,
+    },
+    /// Give the nft of an ended english auction to its highest bidder and the bid to its seller
+    SettleAuction { escrow: Pubkey },
+    /// Make a token pool buy an amount of an spl token instead of an nft
+    SetFungibleTarget {
+        token_pool: Pubkey,
+        mint: Pubkey,
+        token_amount: u64,
+    },
+    /// List all the tokens of a token account for the given lamports
+    ListTokens {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Token account holding the tokens
+        #[arg(long)]
+        token_account: Pubkey,
+        amount: u64,
+        /// Unix timestamp at which the listing expires
+        #[arg(long)]
+        expires_at: Option<i64>,
+    },
+    /// Redeem your share of a fungible pool for your part of the tokens it bought
+    Redeem {
+        token_pool: Pubkey,
+        /// Token account receiving the tokens
+        #[arg(long)]
+        token_account: Pubkey,
+        /// Token account of the pool holding the tokens, looked up by default
+        #[arg(long)]
+        pool_token_account: Option<Pubkey>,
+    },
+    /// Buy a listed nft or listed tokens using the treasury of a token pool
+    ExecuteBuy {
+        token_pool: Pubkey,
+        escrow: Pubkey,
+        /// Lamports to pay, defaults to the price in the escrow
+        #[arg(long)]
+        amount: Option<u64>,
+    },
+    /// Propose a new manager for a token pool, who takes over once they accept
+    ProposeManager {
+        token_pool: Pubkey,
+        new_manager: Pubkey,
+    },
+    /// Accept the management of a token pool proposed to you
+    AcceptManager { token_pool: Pubkey },
+    /// Withdraw the proposed handover of a token pool
+    CancelManagerTransfer { token_pool: Pubkey },
+    /// Pause the contributions, trading, purchases and buyouts of a token pool, refunds and cancellations still work
+    Pause {
+        token_pool: Pubkey,
+        /// Resume the token pool instead
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Take the authority of the nft owned by a token pool, needs 100% of the shares
+    ClaimNft {
+        token_pool: Pubkey,
+        /// Token account holding the nft
+        #[arg(long)]
+        nft_account: Pubkey,
+        /// Mint of the nft, needed to claim one nft of a basket pool
+        #[arg(long)]
+        nft_mint: Option<Pubkey>,
+    },
+    /// Post a standing bid for share units of a token pool, a unit is 0.01% of the pool
+    BidShare {
+        token_pool: Pubkey,
+        /// Lamports paid for each share unit
+        price_per_unit: u64,
+        /// Share units wanted
+        quantity: u64,
+    },
+    /// Cancel your share bid and get its lamports back
+    CancelShareBid { token_pool: Pubkey },
+    /// Sell share units of your share into a bid, the best priced bid by default
+    FillBid {
+        token_pool: Pubkey,
+        units: u64,
+        /// Bidder whose bid is filled
+        #[arg(long)]
+        bidder: Option<Pubkey>,
+        /// Lowest lamports per share unit you accept
+        #[arg(long, default_value_t = 0)]
+        min_price_per_unit: u64,
+    },
+    /// List the share bids of a token pool as json, best priced bid first
+    Bids { token_pool: Pubkey },
+    /// Let a token pool buy any nft of a collection by allowing its mints, the first
+    /// call creates the allowed mints account
+    AllowMints {
+        token_pool: Pubkey,
+        #[arg(required = true)]
+        mints: Vec<Pubkey>,
+        /// Mints the allowed mints account can hold when it is created
+        #[arg(long, default_value_t = 100)]
+        max_mints: usize,
+    },
+    /// Turn a collection pool into a basket pool which keeps buying allowed nfts
+    Basket {
+        token_pool: Pubkey,
+        /// Nfts the inventory account can hold
+        #[arg(long, default_value_t = 20)]
+        max_nfts: usize,
+    },
+    /// Set the reserve price for buying out a token pool and the time members get to object
+    BuyoutTerms {
+        token_pool: Pubkey,
+        /// Lowest lamports for the whole pool, zero disables buyouts
+        reserve_price: u64,
+        /// Seconds members get to object a buyout
+        #[arg(long, default_value_t = 3 * 24 * 60 * 60)]
+        objection_period: i64,
+    },
+    /// Offer to buy the nft of a token pool from all of its members, or outbid the current offer
+    StartBuyout {
+        token_pool: Pubkey,
+        /// Lamports for the whole pool, you only deposit for the shares you don't own
+        price: u64,
+    },
+    /// Object to the buyout of your token pool
+    ObjectBuyout {
+        token_pool: Pubkey,
+        /// Member you act for as their delegate
+        #[arg(long)]
+        member: Option<Pubkey>,
+    },
+    /// Complete a buyout once its objection window has closed
+    CompleteBuyout {
+        token_pool: Pubkey,
+        /// Token account holding the nft
+        #[arg(long)]
+        nft_account: Pubkey,
+    },
+    /// Claim the lamports for your share of a bought out token pool
+    ClaimPayout {
+        token_pool: Pubkey,
+        /// Member you act for as their delegate
+        #[arg(long)]
+        member: Option<Pubkey>,
+    },
+    /// Show the buyout offer for a token pool as json
+    ShowBuyout { token_pool: Pubkey },
+    /// Only let holders of a token of the gate mint join a token pool, leave out the mint to open it again
+    Gate {
+        token_pool: Pubkey,
+        #[arg(long)]
+        mint: Option<Pubkey>,
+    },
+    /// Cap what a single member can contribute to a token pool, zero removes the cap
+    MaxContribution {
+        token_pool: Pubkey,
+        maximum_amount: u64,
+        /// Read the maximum amount as a percent of the target amount
+        #[arg(long)]
+        percent: bool,
+    },
+    /// Set the share the manager gets when a token pool buys, before anyone contributes
+    ManagerShare {
+        token_pool: Pubkey,
+        /// Share units, a unit is one basis point of the pool
+        units: u64,
+    },
+    /// Set how much new members of a token pool have to contribute so it can still reach its target
+    Admission {
+        token_pool: Pubkey,
+        /// Every new member pays at least an even part of what is left over the free slots,
+        /// otherwise only the last free slot has to pay what is left
+        #[arg(long)]
+        even_split: bool,
+    },
+    /// Let contributions to a token pool go beyond its target till the subscription ends
+    Oversubscribe {
+        token_pool: Pubkey,
+        /// Unix timestamp at which the subscription ends, zero stops oversubscribing
+        subscription_end: i64,
+    },
+    /// Scale down the contributions of an oversubscribed token pool once its subscription has ended
+    CloseSubscription { token_pool: Pubkey },
+    /// Claim the lamports refunded to you after a token pool was oversubscribed
+    ClaimRefund {
+        token_pool: Pubkey,
+        /// Member you act for as their delegate
+        #[arg(long)]
+        member: Option<Pubkey>,
+    },
+    /// Let a delegate act on your share in a token pool, replacing the current delegate
+    ApproveDelegate {
+        token_pool: Pubkey,
+        delegate: Pubkey,
+        /// Delegate can object buyouts for you
+        #[arg(long)]
+        vote: bool,
+        /// Delegate can sell your share or cancel its sale, you are paid
+        #[arg(long)]
+        sell: bool,
+        /// Delegate can claim your payouts and refunds, they are paid to you
+        #[arg(long)]
+        claim: bool,
+    },
+    /// Remove the delegate of your share in a token pool
+    RevokeDelegate { token_pool: Pubkey },
+    /// Set the most members a new token pool can have, the upgrade authority of the program sets it up
+    Platform {
+        #[arg(long)]
+        max_members: u32,
+    },
+    /// Show the platform config as json
+    ShowPlatform,
+    /// Pause the contributions, trading, listings, purchases and buyouts of every token pool as the platform authority
+    PausePlatform {
+        /// Resume the platform instead
+        #[arg(long)]
+        resume: bool,
+    },
+    /// Create a multisig which needs m of the signers to act, it can be made the manager of token pools
+    Multisig {
+        m: u8,
+        #[arg(required = true)]
+        signers: Vec<Pubkey>,
+    },
+    /// Move the fees paid to the multisig given with --multisig to a destination
+    CollectFees {
+        /// Wallet receiving the fees, defaults to the signer
+        #[arg(long)]
+        destination: Option<Pubkey>,
+        /// Lamports to collect, defaults to everything over the rent exemption
+        #[arg(long, default_value_t = 0)]
+        amount: u64,
+    },
+    /// Move a token pool created by an older program version to the current layout, you pay the extra rent
+    MigratePool { token_pool: Pubkey },
+    /// Move an escrow created by an older program version to the current layout, you pay the extra rent
+    MigrateEscrow { escrow: Pubkey },
+    /// Show a token pool, an escrow or a share bid account as json
+    Show { address: Pubkey },
+    /// List token pools as json
+    List {
+        /// Only list the pools buying this mint
+        #[arg(long)]
+        target_mint: Option<Pubkey>,
+    },
+}
+
+struct Config {
+    rpc_client: RpcClient,
+    keypair_path: PathBuf,
+    multisig: Option<Pubkey>,
+    multisig_signer_paths: Vec<PathBuf>,
+}
+
+impl Config {
+    fn new(cli: &Cli) -> Result<Self, Box<dyn Error>> {
+        let keypair_path = match &cli.keypair {
+            Some(path) => path.clone(),
+            None => PathBuf::from(std::env::var("HOME")?).join(".config/solana/id.json"),
+        };
+        Ok(Self {
+            rpc_client: RpcClient::new_with_commitment(
+                cli.url.clone(),
+                CommitmentConfig::confirmed(),
+            ),
+            keypair_path,
+            multisig: cli.multisig,
+            multisig_signer_paths: cli.multisig_signers.clone(),
+        })
+    }
+
+    /// manager of the token pools the signer operates, the multisig when one is given
+    fn manager(&self, signer: &Keypair) -> Pubkey {
+        self.multisig.unwrap_or_else(|| signer.pubkey())
+    }
+
+    /// read the keypairs of the multisig signers
+    fn multisig_signers(&self) -> Result<Vec<Keypair>, Box<dyn Error>> {
+        self.multisig_signer_paths
+            .iter()
+            .map(|path| {
+                read_keypair_file(path)
+                    .map_err(|e| format!("failed to read keypair {}: {}", path.display(), e).into())
+            })
+            .collect()
+    }
+
+    /// read the signer keypair, only the commands sending transactions need it
+    fn signer(&self) -> Result<Keypair, Box<dyn Error>> {
+        read_keypair_file(&self.keypair_path).map_err(|e| {
+            format!(
+                "failed to read keypair {}: {}",
+                self.keypair_path.display(),
+                e
+            )
+            .into()
+        })
+    }
+
+    async fn send(
+        &self,
+        signer: &Keypair,
+        instructions: &[Instruction],
+        extra_signers: &[&Keypair],
+    ) -> Result<Signature, Box<dyn Error>> {
+        let mut signers = vec![signer];
+        signers.extend_from_slice(extra_signers);
+        let blockhash = self.rpc_client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &signers,
+            blockhash,
+        );
+        Ok(self
+            .rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .await?)
+    }
+
+    /// send instructions of the manager, with a multisig they are signed by the multisig signers
+    async fn send_as_manager(
+        &self,
+        signer: &Keypair,
+        instructions: &[Instruction],
+        extra_signers: &[&Keypair],
+    ) -> Result<Signature, Box<dyn Error>> {
+        let Some(multisig) = self.multisig else {
+            return self.send(signer, instructions, extra_signers).await;
+        };
+        let multisig_signers = self.multisig_signers()?;
+        let signer_keys: Vec<Pubkey> = multisig_signers.iter().map(|s| s.pubkey()).collect();
+        let instructions: Vec<Instruction> = instructions
+            .iter()
+            .cloned()
+            .map(|ix| instruction::multisig_signed(ix, &multisig, &signer_keys))
+            .collect();
+        let mut signers = extra_signers.to_vec();
+        signers.extend(multisig_signers.iter());
+        self.send(signer, &instructions, &signers).await
+    }
+
+    /// token account of the gate mint held by owner, only gated token pools need it
+    async fn gate_token_account(
+        &self,
+        token_pool: &TokenPool,
+        owner: &Pubkey,
+    ) -> Result<Option<Pubkey>, Box<dyn Error>> {
+        if token_pool.gate_mint == Pubkey::default() {
+            return Ok(None);
+        }
+        let accounts = self
+            .rpc_client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(token_pool.gate_mint))
+            .await?;
+        let account = accounts
+            .first()
+            .ok_or("the token pool is gated and you hold no token of its gate mint")?;
+        Ok(Some(account.pubkey.parse()?))
+    }
+
+    /// instruction to create a rent exempt account owned by the token pool program
+    async fn create_program_account(
+        &self,
+        payer: &Pubkey,
+        account: &Keypair,
+        space: usize,
+    ) -> Result<Instruction, Box<dyn Error>> {
+        let lamports = self
+            .rpc_client
+            .get_minimum_balance_for_rent_exemption(space)
+            .await?;
+        Ok(system_instruction::create_account(
+            payer,
+            &account.pubkey(),
+            lamports,
+            space as u64,
+            &token_pool::id(),
+        ))
+    }
+}
+
+/// Member the signer acts for and the delegate signing for them, when it is not the signer's own share
+fn member_and_delegate(signer: &Pubkey, member: Option<Pubkey>) -> (Pubkey, Option<Pubkey>) {
+    match member {
+        Some(member) => (member, Some(*signer)),
+        None => (*signer, None),
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> CommandResult {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli).await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> CommandResult {
+    let config = Config::new(&cli)?;
+    match cli.command {
+        Command::Initialize {
+            target_token,
+            target_amount,
+            minimum_amount,
+            description,
+            minimum_exemption_amount,
+        } => {
+            let signer = config.signer()?;
+            let token_pool = Keypair::new();
+            let treasury = Keypair::new();
+            let instructions = vec![
+                config
+                    .create_program_account(&signer.pubkey(), &treasury, 0)
+                    .await?,
+                instruction::initialize_pool(
+                    &signer.pubkey(),
+                    &token_pool.pubkey(),
+                    &treasury.pubkey(),
+                    &target_token,
+                    target_amount,
+                    minimum_amount,
+                    description,
+                    minimum_exemption_amount,
+                ),
+            ];
+            let signature = config
+                .send(&signer, &instructions, &[&token_pool, &treasury])
+                .await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "tokenPool": token_pool.pubkey().to_string(),
+                "treasury": treasury.pubkey().to_string(),
+                "maxMembers": max_members_for(target_amount, minimum_amount),
+            }))
+        }
+        Command::AddMember { token_pool, amount } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let gate_token_account = config.gate_token_account(&pool, &signer.pubkey()).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::add_member(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &pool.treasurey,
+                        gate_token_account.as_ref(),
+                        amount,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Upgrade { token_pool, amount } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::upgrade_share(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &pool.treasurey,
+                        amount,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::SellShare {
+            token_pool,
+            amount,
+            expires_in,
+            buyer,
+            member,
+        } => {
+            let signer = config.signer()?;
+            let (member, delegate) = member_and_delegate(&signer.pubkey(), member);
+            let escrow = Keypair::new();
+            let instructions = [
+                config
+                    .create_program_account(&signer.pubkey(), &escrow, escrow_account_size())
+                    .await?,
+                instruction::sell_share(
+                    &member,
+                    &token_pool,
+                    &escrow.pubkey(),
+                    buyer.as_ref(),
+                    delegate.as_ref(),
+                    amount,
+                    expires_in,
+                ),
+            ];
+            let signature = config.send(&signer, &instructions, &[&escrow]).await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "escrow": escrow.pubkey().to_string(),
+            }))
+        }
+        Command::BuyShare {
+            token_pool,
+            escrow,
+            amount,
+        } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let gate_token_account = config.gate_token_account(&pool, &signer.pubkey()).await?;
+            let escrow_state = get_escrow(&config.rpc_client, &escrow).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::buy_share(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &escrow,
+                        &escrow_state,
+                        gate_token_account.as_ref(),
+                        amount.unwrap_or(escrow_state.amount),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Cancel { escrow, token_pool } => {
+            let signer = config.signer()?;
+            let escrow_state = get_escrow(&config.rpc_client, &escrow).await?;
+            // only nft listings record the mint of the nft
+            let cancel_instruction = if escrow_state.nft_mint == Pubkey::default() {
+                let token_pool =
+                    token_pool.ok_or("--token-pool is needed to cancel a share sale")?;
+                // a delegate of the seller cancels for them
+                let delegate = Some(signer.pubkey()).filter(|key| *key != escrow_state.seller);
+                instruction::cancel_share_sale(
+                    &token_pool,
+                    &escrow,
+                    &escrow_state,
+                    delegate.as_ref(),
+                )
+            } else {
+                instruction::cancel_nft_listing(&escrow, &escrow_state)
+            };
+            let signature = config.send(&signer, &[cancel_instruction], &[]).await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Transfer {
+            token_pool,
+            recipient,
+            units,
+        } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            // a member already passed the gate of the token pool
+            let gate_token_account = if pool.pool_member_list.find_member(recipient) {
+                None
+            } else {
+                config.gate_token_account(&pool, &recipient).await?
+            };
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::transfer_share(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &recipient,
+                        gate_token_account.as_ref(),
+                        units,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Expire { escrow, token_pool } => {
+            let signer = config.signer()?;
+            let escrow_state = get_escrow(&config.rpc_client, &escrow).await?;
+            // only nft listings record the mint of the nft
+            if escrow_state.nft_mint == Pubkey::default() && token_pool.is_none() {
+                return Err("--token-pool is needed to expire a share sale".into());
+            }
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::expire_escrow(
+                        &escrow,
+                        &escrow_state,
+                        token_pool.as_ref(),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ClosePool { token_pool } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            // only a pool nobody has joined needs its manager to close it
+            let manager =
+                (pool.stage == TokenPoolStage::Initialized).then(|| config.manager(&signer));
+            let mut instructions = vec![];
+            if config.rpc_client.get_balance(&pool.treasurey).await? > 0 {
+                instructions.push(instruction::close_treasury(
+                    &token_pool,
+                    &pool,
+                    manager.as_ref(),
+                ));
+            }
+            instructions.push(instruction::close_pool(
+                &token_pool,
+                &pool,
+                manager.as_ref(),
+            ));
+            // the allowed mints of a collection or basket pool are closed along with it
+            if matches!(
+                pool.target_kind,
+                PoolTargetKind::Collection | PoolTargetKind::Basket
+            ) {
+                let allowed_mints =
+                    get_allowed_mints(&config.rpc_client, &pool.target_token).await?;
+                instructions.push(instruction::close_allowed_mints(
+                    &pool.target_token,
+                    &allowed_mints,
+                ));
+            }
+            let signature = config.send_as_manager(&signer, &instructions, &[]).await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::CloseEscrow { escrow } => {
+            let signer = config.signer()?;
+            let escrow_state = get_escrow(&config.rpc_client, &escrow).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::close_escrow(&escrow, &escrow_state)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::CloseAllowedMints { allowed_mints } => {
+            let signer = config.signer()?;
+            let allowed_mints_state = get_allowed_mints(&config.rpc_client, &allowed_mints).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::close_allowed_mints(
+                        &allowed_mints,
+                        &allowed_mints_state,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Sweep { token_pool } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::sweep_treasury(
+                        &manager,
+                        &token_pool,
+                        &pool.treasurey,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ListNft {
+            nft_mint,
+            nft_account,
+            amount,
+            end_time,
+            floor_price,
+            expires_in,
+        } => {
+            let signer = config.signer()?;
+            let escrow = Keypair::new();
+            let instructions = [
+                config
+                    .create_program_account(&signer.pubkey(), &escrow, escrow_account_size())
+                    .await?,
+                match (end_time, floor_price) {
+                    (Some(end_time), Some(floor_price)) => instruction::list_nft_dutch_auction(
+                        &signer.pubkey(),
+                        &escrow.pubkey(),
+                        &nft_mint,
+                        &nft_account,
+                        amount,
+                        floor_price,
+                        end_time,
+                        expires_in,
+                    ),
+                    _ => instruction::list_nft(
+                        &signer.pubkey(),
+                        &escrow.pubkey(),
+                        &nft_mint,
+                        &nft_account,
+                        amount,
+                        expires_in,
+                    ),
+                },
+            ];
+            let signature = config.send(&signer, &instructions, &[&escrow]).await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "escrow": escrow.pubkey().to_string(),
+            }))
+        }
+        Command::ListAuction {
+            nft_mint,
+            nft_account,
+            reserve_price,
+            min_bid_increment,
+            end_time,
+        } => {
+            let signer = config.signer()?;
+            let escrow = Keypair::new();
+            let instructions = vec![
+                config
+                    .create_program_account(&signer.pubkey(), &escrow, escrow_account_size())
+                    .await?,
+                instruction::list_nft_auction(
+                    &signer.pubkey(),
+                    &escrow.pubkey(),
+                    &nft_mint,
+                    &nft_account,
+                    reserve_price,
+                    min_bid_increment,
+                    end_time,
+                ),
+            ];
+            let signature = config.send(&signer, &instructions, &[&escrow]).await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "escrow": escrow.pubkey().to_string(),
+            }))
+        }
+        Command::Bid {
+            escrow,
+            amount,
+            token_pool,
+        } => {
+            let signer = config.signer()?;
+            let escrow_state = get_escrow(&config.rpc_client, &escrow).await?;
+            let pool = match token_pool {
+                Some(token_pool) => Some(get_token_pool(&config.rpc_client, &token_pool).await?),
+                None => None,
+            };
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::place_bid(
+                        &signer.pubkey(),
+                        &escrow,
+                        &escrow_state,
+                        token_pool.as_ref().zip(pool.as_ref()),
+                        amount,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::SettleAuction { escrow } => {
+            let signer = config.signer()?;
+            let escrow_state = get_escrow(&config.rpc_client, &escrow).await?;
+            let pool = if escrow_state.bidder_pool != Pubkey::default() {
+                Some(get_token_pool(&config.rpc_client, &escrow_state.bidder_pool).await?)
+            } else {
+                None
+            };
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::settle_auction(
+                        &escrow,
+                        &escrow_state,
+                        pool.as_ref(),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::SetFungibleTarget {
+            token_pool,
+            mint,
+            token_amount,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_fungible_target(
+                        &manager,
+                        &token_pool,
+                        &mint,
+                        token_amount,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ListTokens {
+            mint,
+            token_account,
+            amount,
+            expires_at,
+        } => {
+            let signer = config.signer()?;
+            let escrow = Keypair::new();
+            let mut instructions = vec![
+                config
+                    .create_program_account(&signer.pubkey(), &escrow, escrow_account_size())
+                    .await?,
+                instruction::list_tokens(
+                    &signer.pubkey(),
+                    &escrow.pubkey(),
+                    &mint,
+                    &token_account,
+                    amount,
+                ),
+            ];
+            if let Some(expires_at) = expires_at {
+                instructions.push(instruction::set_escrow_expiry(
+                    &signer.pubkey(),
+                    &escrow.pubkey(),
+                    expires_at,
+                ));
+            }
+            let signature = config.send(&signer, &instructions, &[&escrow]).await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "escrow": escrow.pubkey().to_string(),
+            }))
+        }
+        Command::Redeem {
+            token_pool,
+            token_account,
+            pool_token_account,
+        } => {
+            let signer = config.signer()?;
+            let pool_token_account = match pool_token_account {
+                Some(pool_token_account) => pool_token_account,
+                None => {
+                    let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+                    let accounts = config
+                        .rpc_client
+                        .get_token_accounts_by_owner(
+                            &pool.vault,
+                            TokenAccountsFilter::Mint(pool.target_token),
+                        )
+                        .await?;
+                    let account = accounts
+                        .first()
+                        .ok_or("the token pool vault has no token account of its target")?;
+                    account.pubkey.parse()?
+                }
+            };
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::redeem(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &pool_token_account,
+                        &token_account,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ExecuteBuy {
+            token_pool,
+            escrow,
+            amount,
+        } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let escrow_state = get_escrow(&config.rpc_client, &escrow).await?;
+            let amount = amount.unwrap_or(escrow_state.amount);
+            let buy_instruction = if escrow_state.listing == ListingKind::Tokens {
+                instruction::execute_token_buy(
+                    &signer.pubkey(),
+                    &token_pool,
+                    &pool,
+                    &escrow,
+                    &escrow_state,
+                    amount,
+                )
+            } else {
+                instruction::execute_nft_buy(
+                    &signer.pubkey(),
+                    &token_pool,
+                    &pool,
+                    &escrow,
+                    &escrow_state,
+                    amount,
+                )
+            };
+            let signature = config.send(&signer, &[buy_instruction], &[]).await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ProposeManager {
+            token_pool,
+            new_manager,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::propose_manager(
+                        &manager,
+                        &token_pool,
+                        &new_manager,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::AcceptManager { token_pool } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::accept_manager(&manager, &token_pool)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::CancelManagerTransfer { token_pool } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::cancel_manager_transfer(&manager, &token_pool)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Pause { token_pool, resume } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_pool_paused(&manager, &token_pool, !resume)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ClaimNft {
+            token_pool,
+            nft_account,
+            nft_mint,
+        } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let (nft_mint, inventory) = if pool.target_kind == PoolTargetKind::Basket {
+                let nft_mint =
+                    nft_mint.ok_or("--nft-mint is needed to claim an nft of a basket pool")?;
+                let inventory = get_inventory(&config.rpc_client, &pool.inventory).await?;
+                (nft_mint, Some(inventory))
+            } else {
+                (pool.target_token, None)
+            };
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::get_nft_authority(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &nft_mint,
+                        &nft_account,
+                        inventory
+                            .as_ref()
+                            .map(|inventory| (&pool.inventory, inventory)),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::BidShare {
+            token_pool,
+            price_per_unit,
+            quantity,
+        } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let gate_token_account = config.gate_token_account(&pool, &signer.pubkey()).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::place_share_bid(
+                        &signer.pubkey(),
+                        &token_pool,
+                        gate_token_account.as_ref(),
+                        price_per_unit,
+                        quantity,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::CancelShareBid { token_pool } => {
+            let signer = config.signer()?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::cancel_share_bid(&signer.pubkey(), &token_pool)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::FillBid {
+            token_pool,
+            units,
+            bidder,
+            min_price_per_unit,
+        } => {
+            let signer = config.signer()?;
+            let bidder = match bidder {
+                Some(bidder) => bidder,
+                None => get_share_bids(&config.rpc_client, &token_pool)
+                    .await?
+                    .into_iter()
+                    .map(|(_, share_bid)| share_bid.bidder)
+                    .find(|bidder| *bidder != signer.pubkey())
+                    .ok_or("the token pool has no share bids")?,
+            };
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::fill_bid(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &bidder,
+                        units,
+                        min_price_per_unit,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "bidder": bidder.to_string(),
+            }))
+        }
+        Command::Bids { token_pool } => {
+            let share_bids: Vec<CliShareBid> = get_share_bids(&config.rpc_client, &token_pool)
+                .await?
+                .iter()
+                .map(|(address, share_bid)| CliShareBid::new(address, share_bid))
+                .collect();
+            print_json(&share_bids)
+        }
+        Command::AllowMints {
+            token_pool,
+            mints,
+            max_mints,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let mut instructions = vec![];
+            let mut extra_signers = vec![];
+            let allowed_mints_account = Keypair::new();
+            let allowed_mints = if pool.target_kind == PoolTargetKind::Collection {
+                pool.target_token
+            } else {
+                // the mint the pool was buying is allowed along with the new ones
+                let space = allowed_mints_account_size(max_mints.max(mints.len() + 1));
+                instructions.push(
+                    config
+                        .create_program_account(&signer.pubkey(), &allowed_mints_account, space)
+                        .await?,
+                );
+                extra_signers.push(&allowed_mints_account);
+                allowed_mints_account.pubkey()
+            };
+            instructions.push(instruction::add_allowed_mints(
+                &manager,
+                &token_pool,
+                &allowed_mints,
+                &mints,
+            ));
+            let signature = config
+                .send_as_manager(&signer, &instructions, &extra_signers)
+                .await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "allowedMints": allowed_mints.to_string(),
+            }))
+        }
+        Command::Basket {
+            token_pool,
+            max_nfts,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let inventory = Keypair::new();
+            let instructions = vec![
+                config
+                    .create_program_account(
+                        &signer.pubkey(),
+                        &inventory,
+                        inventory_account_size(max_nfts),
+                    )
+                    .await?,
+                instruction::initialize_basket(&manager, &token_pool, &inventory.pubkey()),
+            ];
+            let signature = config
+                .send_as_manager(&signer, &instructions, &[&inventory])
+                .await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "inventory": inventory.pubkey().to_string(),
+            }))
+        }
+        Command::BuyoutTerms {
+            token_pool,
+            reserve_price,
+            objection_period,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_buyout_terms(
+                        &manager,
+                        &token_pool,
+                        reserve_price,
+                        objection_period,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::StartBuyout { token_pool, price } => {
+            let signer = config.signer()?;
+            let current_buyout = get_buyout(&config.rpc_client, &token_pool).await.ok();
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::start_buyout(
+                        &signer.pubkey(),
+                        &token_pool,
+                        current_buyout.as_ref(),
+                        price,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "buyout": find_buyout_address(&token_pool).0.to_string(),
+            }))
+        }
+        Command::ObjectBuyout { token_pool, member } => {
+            let signer = config.signer()?;
+            let (member, delegate) = member_and_delegate(&signer.pubkey(), member);
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::object_buyout(
+                        &member,
+                        &token_pool,
+                        delegate.as_ref(),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::CompleteBuyout {
+            token_pool,
+            nft_account,
+        } => {
+            let signer = config.signer()?;
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let buyout = get_buyout(&config.rpc_client, &token_pool).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::complete_buyout(
+                        &token_pool,
+                        &pool,
+                        &buyout,
+                        &nft_account,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ClaimPayout { token_pool, member } => {
+            let signer = config.signer()?;
+            let (member, delegate) = member_and_delegate(&signer.pubkey(), member);
+            let buyout = get_buyout(&config.rpc_client, &token_pool).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::claim_buyout_payout(
+                        &member,
+                        &token_pool,
+                        &buyout,
+                        delegate.as_ref(),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ShowBuyout { token_pool } => {
+            let buyout = get_buyout(&config.rpc_client, &token_pool).await?;
+            print_json(&CliBuyout::new(
+                &find_buyout_address(&token_pool).0,
+                &buyout,
+            ))
+        }
+        Command::Gate { token_pool, mint } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_gate_mint(
+                        &manager,
+                        &token_pool,
+                        mint.as_ref(),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::MaxContribution {
+            token_pool,
+            maximum_amount,
+            percent,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let maximum_kind = if percent {
+                MaximumKind::Percent
+            } else {
+                MaximumKind::Amount
+            };
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_maximum_amount(
+                        &manager,
+                        &token_pool,
+                        maximum_amount,
+                        maximum_kind,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ManagerShare { token_pool, units } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_manager_share(&manager, &token_pool, units)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Admission {
+            token_pool,
+            even_split,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let admission_policy = if even_split {
+                AdmissionPolicy::EvenSplit
+            } else {
+                AdmissionPolicy::LastMember
+            };
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_admission_policy(
+                        &manager,
+                        &token_pool,
+                        admission_policy,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Oversubscribe {
+            token_pool,
+            subscription_end,
+        } => {
+            let signer = config.signer()?;
+            let manager = config.manager(&signer);
+            let signature = config
+                .send_as_manager(
+                    &signer,
+                    &[instruction::set_subscription_end(
+                        &manager,
+                        &token_pool,
+                        subscription_end,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::CloseSubscription { token_pool } => {
+            let signer = config.signer()?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::close_subscription(&token_pool)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ClaimRefund { token_pool, member } => {
+            let signer = config.signer()?;
+            let (member, delegate) = member_and_delegate(&signer.pubkey(), member);
+            let pool = get_token_pool(&config.rpc_client, &token_pool).await?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::claim_refund(
+                        &member,
+                        &token_pool,
+                        &pool.treasurey,
+                        delegate.as_ref(),
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ApproveDelegate {
+            token_pool,
+            delegate,
+            vote,
+            sell,
+            claim,
+        } => {
+            let signer = config.signer()?;
+            let permissions = [
+                (vote, DELEGATE_VOTE),
+                (sell, DELEGATE_SELL),
+                (claim, DELEGATE_CLAIM),
+            ]
+            .iter()
+            .filter(|(granted, _)| *granted)
+            .fold(0, |permissions, (_, permission)| permissions | permission);
+            if permissions == 0 {
+                return Err("give the delegate at least one of --vote, --sell or --claim".into());
+            }
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::approve_delegate(
+                        &signer.pubkey(),
+                        &token_pool,
+                        &delegate,
+                        permissions,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::RevokeDelegate { token_pool } => {
+            let signer = config.signer()?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::revoke_delegate(&signer.pubkey(), &token_pool)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Platform { max_members } => {
+            let signer = config.signer()?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::set_platform_config(
+                        &signer.pubkey(),
+                        max_members,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::ShowPlatform => {
+            let platform_config = get_platform_config(&config.rpc_client).await?;
+            print_json(&CliPlatformConfig::new(
+                &find_platform_config_address().0,
+                &platform_config,
+            ))
+        }
+        Command::PausePlatform { resume } => {
+            let signer = config.signer()?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::set_platform_paused(&signer.pubkey(), !resume)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Multisig { m, signers } => {
+            let signer = config.signer()?;
+            let multisig = Keypair::new();
+            let instructions = vec![
+                config
+                    .create_program_account(&signer.pubkey(), &multisig, multisig_account_size())
+                    .await?,
+                instruction::initialize_multisig(&multisig.pubkey(), &signers, m),
+            ];
+            let signature = config.send(&signer, &instructions, &[&multisig]).await?;
+            print_json(&json!({
+                "signature": signature.to_string(),
+                "multisig": multisig.pubkey().to_string(),
+            }))
+        }
+        Command::CollectFees {
+            destination,
+            amount,
+        } => {
+            let signer = config.signer()?;
+            let multisig = config
+                .multisig
+                .ok_or("--multisig is needed to collect fees")?;
+            let multisig_signers = config.multisig_signers()?;
+            let signer_keys: Vec<Pubkey> = multisig_signers.iter().map(|s| s.pubkey()).collect();
+            let signers: Vec<&Keypair> = multisig_signers.iter().collect();
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::collect_fees(
+                        &multisig,
+                        &destination.unwrap_or_else(|| signer.pubkey()),
+                        &signer_keys,
+                        amount,
+                    )],
+                    &signers,
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::MigratePool { token_pool } => {
+            let signer = config.signer()?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::migrate_token_pool(
+                        &signer.pubkey(),
+                        &token_pool,
+                    )],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::MigrateEscrow { escrow } => {
+            let signer = config.signer()?;
+            let signature = config
+                .send(
+                    &signer,
+                    &[instruction::migrate_escrow(&signer.pubkey(), &escrow)],
+                    &[],
+                )
+                .await?;
+            print_json(&json!({ "signature": signature.to_string() }))
+        }
+        Command::Show { address } => {
+            let account = config.rpc_client.get_account(&address).await?;
+            if account.owner != token_pool::id() {
+                return Err(format!("{} is not owned by the token pool program", address).into());
+            }
+            if account.data.len() == share_bid_account_size() {
+                let share_bid = decode_share_bid(&address, &account.data)?;
+                print_json(&CliShareBid::new(&address, &share_bid))
+            } else if account.data.len() == multisig_account_size() {
+                let multisig = decode_multisig(&address, &account.data)?;
+                print_json(&CliMultisig::new(&address, &multisig))
+            } else if let Ok(pool) = decode_token_pool(&address, &account.data) {
+                print_json(&CliTokenPool::new(&address, &pool))
+            } else {
+                let escrow = decode_escrow(&address, &account.data)?;
+                print_json(&CliEscrow::new(&address, &escrow))
+            }
+        }
+        Command::List { target_mint } => {
+            let pools = match target_mint {
+                Some(target_mint) => {
+                    find_pools_by_target_mint(&config.rpc_client, &target_mint).await?
+                }
+                None => get_token_pools(&config.rpc_client).await?,
+            };
+            let pools: Vec<CliTokenPool> = pools
+                .iter()
+                .map(|(address, pool)| CliTokenPool::new(address, pool))
+                .collect();
+            print_json(&pools)
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use {
+    serde::Serialize,
+    solana_sdk::pubkey::Pubkey,
+    token_pool_client::{
+        state::pool_members, units_to_share, Buyout, Escrow, Multisig, PlatformConfig,
+        PoolMemberShareInfo, ShareBid, TokenPool,
+    },
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliTokenPool {
+    pub address: String,
+    pub stage: String,
+    pub target_amount: u64,
+    pub minimum_amount: u64,
+    pub minimum_exemption_amount: u64,
+    pub current_balance: u64,
+    pub target_kind: String,
+    pub target_token: String,
+    pub target_token_amount: u64,
+    pub inventory: String,
+    pub reserve_price: u64,
+    pub objection_period: i64,
+    pub gate_mint: String,
+    pub maximum_amount: u64,
+    pub maximum_kind: String,
+    pub manager_share: u64,
+    pub admission_policy: String,
+    pub subscription_end: i64,
+    pub description: String,
+    pub vault: String,
+    pub manager: String,
+    pub pending_manager: String,
+    pub paused: bool,
+    pub rent_payer: String,
+    pub treasury: String,
+    pub committed_bid: u64,
+    pub bid_escrow: String,
+    pub buyout_end: i64,
+    pub max_members: u32,
+    pub members: Vec<CliPoolMember>,
+}
+
+impl CliTokenPool {
+    pub fn new(address: &Pubkey, token_pool: &TokenPool) -> Self {
+        Self {
+            address: address.to_string(),
+            stage: format!("{:?}", token_pool.stage),
+            target_amount: token_pool.target_amount,
+            minimum_amount: token_pool.minimum_amount,
+            minimum_exemption_amount: token_pool.minimum_exemption_amount,
+            current_balance: token_pool.current_balance,
+            target_kind: format!("{:?}", token_pool.target_kind),
+            target_token: token_pool.target_token.to_string(),
+            target_token_amount: token_pool.target_token_amount,
+            inventory: token_pool.inventory.to_string(),
+            reserve_price: token_pool.reserve_price,
+            objection_period: token_pool.objection_period,
+            gate_mint: token_pool.gate_mint.to_string(),
+            maximum_amount: token_pool.maximum_amount,
+            maximum_kind: format!("{:?}", token_pool.maximum_kind),
+            manager_share: token_pool.manager_share,
+            admission_policy: format!("{:?}", token_pool.admission_policy),
+            subscription_end: token_pool.subscription_end,
+            description: token_pool.description.clone(),
+            vault: token_pool.vault.to_string(),
+            manager: token_pool.manager.to_string(),
+            pending_manager: token_pool.pending_manager.to_string(),
+            paused: token_pool.paused,
+            rent_payer: token_pool.rent_payer.to_string(),
+            treasury: token_pool.treasurey.to_string(),
+            committed_bid: token_pool.committed_bid,
+            bid_escrow: token_pool.bid_escrow.to_string(),
+            buyout_end: token_pool.buyout_end,
+            max_members: token_pool.pool_member_list.header.max_members,
+            members: pool_members(token_pool)
+                .iter()
+                .map(CliPoolMember::new)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliPoolMember {
+    pub member: String,
+    pub amount_deposited: u64,
+    pub share: f64,
+    pub share_stage: String,
+    pub escrow: String,
+    pub refund_due: u64,
+    pub delegate: String,
+    pub delegate_permissions: u8,
+}
+
+impl CliPoolMember {
+    pub fn new(member: &PoolMemberShareInfo) -> Self {
+        Self {
+            member: member.member_key.to_string(),
+            amount_deposited: member.amount_deposited,
+            share: member.share,
+            share_stage: format!("{:?}", member.share_stage),
+            escrow: member.escrow.to_string(),
+            refund_due: member.refund_due,
+            delegate: member.delegate.to_string(),
+            delegate_permissions: member.delegate_permissions,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliEscrow {
+    pub address: String,
+    pub stage: String,
+    pub seller: String,
+    pub allowed_buyer: String,
+    pub escrow_vault: String,
+    pub share: f64,
+    pub nft: String,
+    pub nft_mint: String,
+    pub amount: u64,
+    pub listing: String,
+    pub end_time: i64,
+    pub min_bid_increment: u64,
+    pub highest_bid: u64,
+    pub highest_bidder: String,
+    pub bidder_pool: String,
+    pub start_time: i64,
+    pub floor_price: u64,
+    pub token_amount: u64,
+    pub expires_at: i64,
+    pub rent_payer: String,
+}
+
+impl CliEscrow {
+    pub fn new(address: &Pubkey, escrow: &Escrow) -> Self {
+        Self {
+            address: address.to_string(),
+            stage: format!("{:?}", escrow.stage),
+            seller: escrow.seller.to_string(),
+            allowed_buyer: escrow.allowed_buyer.to_string(),
+            escrow_vault: escrow.escrow_vault.to_string(),
+            share: escrow.share,
+            nft: escrow.nft.to_string(),
+            nft_mint: escrow.nft_mint.to_string(),
+            amount: escrow.amount,
+            listing: format!("{:?}", escrow.listing),
+            end_time: escrow.end_time,
+            min_bid_increment: escrow.min_bid_increment,
+            highest_bid: escrow.highest_bid,
+            highest_bidder: escrow.highest_bidder.to_string(),
+            bidder_pool: escrow.bidder_pool.to_string(),
+            start_time: escrow.start_time,
+            floor_price: escrow.floor_price,
+            token_amount: escrow.token_amount,
+            expires_at: escrow.expires_at,
+            rent_payer: escrow.rent_payer.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliShareBid {
+    pub address: String,
+    pub token_pool: String,
+    pub bidder: String,
+    pub price_per_unit: u64,
+    pub quantity: u64,
+    pub share: f64,
+}
+
+impl CliShareBid {
+    pub fn new(address: &Pubkey, share_bid: &ShareBid) -> Self {
+        Self {
+            address: address.to_string(),
+            token_pool: share_bid.token_pool.to_string(),
+            bidder: share_bid.bidder.to_string(),
+            price_per_unit: share_bid.price_per_unit,
+            quantity: share_bid.quantity,
+            share: units_to_share(share_bid.quantity),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliBuyout {
+    pub address: String,
+    pub stage: String,
+    pub token_pool: String,
+    pub buyer: String,
+    pub price: u64,
+    pub deposit: u64,
+    pub end_time: i64,
+    pub objection_share: f64,
+    pub objectors: Vec<String>,
+}
+
+impl CliBuyout {
+    pub fn new(address: &Pubkey, buyout: &Buyout) -> Self {
+        Self {
+            address: address.to_string(),
+            stage: format!("{:?}", buyout.stage),
+            token_pool: buyout.token_pool.to_string(),
+            buyer: buyout.buyer.to_string(),
+            price: buyout.price,
+            deposit: buyout.deposit,
+            end_time: buyout.end_time,
+            objection_share: buyout.objection_share,
+            objectors: buyout.objectors.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliPlatformConfig {
+    pub address: String,
+    pub authority: String,
+    pub max_members: u32,
+    pub paused: bool,
+}
+
+impl CliPlatformConfig {
+    pub fn new(address: &Pubkey, platform_config: &PlatformConfig) -> Self {
+        Self {
+            address: address.to_string(),
+            authority: platform_config.authority.to_string(),
+            max_members: platform_config.max_members,
+            paused: platform_config.paused,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliMultisig {
+    pub address: String,
+    pub m: u8,
+    pub signers: Vec<String>,
+}
+
+impl CliMultisig {
+    pub fn new(address: &Pubkey, multisig: &Multisig) -> Self {
+        Self {
+            address: address.to_string(),
+            m: multisig.m,
+            signers: multisig.signers[..multisig.n as usize]
+                .iter()
+                .map(|signer| signer.to_string())
+                .collect(),
+        }
+    }
+}
@@ -4,11 +4,13 @@ import {
   struct,
   u32,
   u64,
+  i64,
   u8,
   option,
   vec,
   str,
   f64,
+  array,
 } from "@project-serum/borsh";
 import { Float } from "@solana/buffer-layout";
 
@@ -20,8 +22,53 @@ export enum TokenPoolInstructions {
   UpgradeShare = 4,
   ListNFT = 5,
   buyNft = 6,
-  setManager = 7,
+  ProposeManager = 7,
   GetNFTAuthority = 8,
+  CancelShareSale = 9,
+  CancelNFTListing = 10,
+  ListNFTAuction = 11,
+  PlaceBid = 12,
+  SettleAuction = 13,
+  PlaceShareBid = 14,
+  CancelShareBid = 15,
+  FillBid = 16,
+  AddAllowedMints = 17,
+  InitializeBasket = 18,
+  SetFungibleTarget = 19,
+  ListTokens = 20,
+  ExecuteTokenBuy = 21,
+  Redeem = 22,
+  SetBuyoutTerms = 23,
+  StartBuyout = 24,
+  ObjectBuyout = 25,
+  CompleteBuyout = 26,
+  ClaimBuyoutPayout = 27,
+  SetGateMint = 28,
+  SetMaximumAmount = 29,
+  SetManagerShare = 30,
+  SetPlatformConfig = 31,
+  SetAdmissionPolicy = 32,
+  SetSubscriptionEnd = 33,
+  CloseSubscription = 34,
+  ClaimRefund = 35,
+  SetEscrowExpiry = 36,
+  ExpireEscrow = 37,
+  TransferShare = 38,
+  ApproveDelegate = 39,
+  RevokeDelegate = 40,
+  InitializeMultisig = 41,
+  CollectFees = 42,
+  AcceptManager = 43,
+  CancelManagerTransfer = 44,
+  SetPoolPaused = 45,
+  SetPlatformPaused = 46,
+  ClosePool = 47,
+  CloseEscrow = 48,
+  CloseTreasury = 49,
+  SweepTreasury = 50,
+  CloseAllowedMints = 51,
+  MigrateTokenPool = 52,
+  MigrateEscrow = 53,
 }
 
 export class Payload extends Struct {
@@ -53,6 +100,9 @@ export interface PoolMemberShareInfo {
   share: number;
   shareStage: ShareStage;
   escrow: PublicKey;
+  refundDue: bigint;
+  delegate: PublicKey;
+  delegatePermissions: number;
 }
 
 export interface PoolMemberList {
@@ -60,7 +110,25 @@ export interface PoolMemberList {
   members: PoolMemberShareInfo[];
 }
 
+export enum PoolTargetKind {
+  SingleMint = 0,
+  Collection = 1,
+  Basket = 2,
+  Fungible = 3,
+}
+
+export enum AdmissionPolicy {
+  LastMember = 0,
+  EvenSplit = 1,
+}
+
+export enum MaximumKind {
+  Amount = 0,
+  Percent = 1,
+}
+
 export interface TokenPool {
+  version: number;
   stage: number;
   targetAmount: bigint;
   minimumExemptionAmount: bigint;
@@ -71,6 +139,23 @@ export interface TokenPool {
   vault: PublicKey;
   manager: PublicKey;
   treasury: PublicKey;
+  committedBid: bigint;
+  bidEscrow: PublicKey;
+  targetKind: PoolTargetKind;
+  inventory: PublicKey;
+  targetTokenAmount: bigint;
+  reservePrice: bigint;
+  objectionPeriod: bigint;
+  buyoutEnd: bigint;
+  gateMint: PublicKey;
+  maximumAmount: bigint;
+  maximumKind: MaximumKind;
+  managerShare: bigint;
+  admissionPolicy: AdmissionPolicy;
+  subscriptionEnd: bigint;
+  pendingManager: PublicKey;
+  paused: number;
+  rentPayer: PublicKey;
   poolMemberList: PoolMemberList;
 }
 
@@ -83,6 +168,9 @@ export const POOL_MEMBER_SHARE_INFO_LAYOUT = struct<PoolMemberShareInfo>([
   f64("share"),
   u8("shareStage"),
   publicKey("escrow"),
+  u64("refundDue"),
+  publicKey("delegate"),
+  u8("delegatePermissions"),
 ]);
 
 export const POOL_MEMBER_LIST_LAYOUT = [
@@ -91,6 +179,7 @@ export const POOL_MEMBER_LIST_LAYOUT = [
 ];
 
 export const TOKEN_POOL_LAYOUT = struct<TokenPool>([
+  u8("version"),
   u8("stage"),
   u64("targetAmount"),
   u64("minimumExemptionAmount"),
@@ -101,6 +190,23 @@ export const TOKEN_POOL_LAYOUT = struct<TokenPool>([
   publicKey("vault"),
   publicKey("manager"),
   publicKey("treasury"),
+  u64("committedBid"),
+  publicKey("bidEscrow"),
+  u8("targetKind"),
+  publicKey("inventory"),
+  u64("targetTokenAmount"),
+  u64("reservePrice"),
+  i64("objectionPeriod"),
+  i64("buyoutEnd"),
+  publicKey("gateMint"),
+  u64("maximumAmount"),
+  u8("maximumKind"),
+  u64("managerShare"),
+  u8("admissionPolicy"),
+  i64("subscriptionEnd"),
+  publicKey("pendingManager"),
+  u8("paused"),
+  publicKey("rentPayer"),
   struct(POOL_MEMBER_LIST_LAYOUT, "poolMemberList"),
 ]);
 
@@ -111,26 +217,134 @@ export enum EscrowStage {
   NftSold = 3,
 }
 
+export enum ListingKind {
+  FixedPrice = 0,
+  EnglishAuction = 1,
+  DutchAuction = 2,
+  Tokens = 3,
+}
+
 export interface Escrow {
   stage: EscrowStage;
   seller: PublicKey;
-  buyer: PublicKey;
+  allowedBuyer: PublicKey;
   escrowVault: PublicKey;
   share: number;
   nft: PublicKey;
   nftMint: PublicKey;
   amount: bigint;
+  listing: ListingKind;
+  endTime: bigint;
+  minBidIncrement: bigint;
+  highestBid: bigint;
+  highestBidder: PublicKey;
+  bidderPool: PublicKey;
+  startTime: bigint;
+  floorPrice: bigint;
+  tokenAmount: bigint;
+  expiresAt: bigint;
+  rentPayer: PublicKey;
 }
 
 export const ESCROW_LAYOUT = struct<Escrow>([
   u8("stage"),
   publicKey("seller"),
-  publicKey("buyer"),
+  publicKey("allowedBuyer"),
   publicKey("escrowVault"),
   f64("share"),
   publicKey("nft"),
   publicKey("nftMint"),
   u64("amount"),
+  u8("listing"),
+  i64("endTime"),
+  u64("minBidIncrement"),
+  u64("highestBid"),
+  publicKey("highestBidder"),
+  publicKey("bidderPool"),
+  i64("startTime"),
+  u64("floorPrice"),
+  u64("tokenAmount"),
+  i64("expiresAt"),
+  publicKey("rentPayer"),
+]);
+
+export enum ShareBidStage {
+  Uninitialized = 0,
+  Open = 1,
+}
+
+export interface ShareBid {
+  stage: ShareBidStage;
+  tokenPool: PublicKey;
+  bidder: PublicKey;
+  pricePerUnit: bigint;
+  quantity: bigint;
+}
+
+export const SHARE_BID_LAYOUT = struct<ShareBid>([
+  u8("stage"),
+  publicKey("tokenPool"),
+  publicKey("bidder"),
+  u64("pricePerUnit"),
+  u64("quantity"),
+]);
+
+export interface PlatformConfig {
+  isInitialized: number;
+  authority: PublicKey;
+  maxMembers: number;
+  paused: number;
+}
+
+export const PLATFORM_CONFIG_LAYOUT = struct<PlatformConfig>([
+  u8("isInitialized"),
+  publicKey("authority"),
+  u32("maxMembers"),
+  u8("paused"),
+]);
+
+export const MAX_SIGNERS = 11;
+
+export interface Multisig {
+  isInitialized: number;
+  m: number;
+  n: number;
+  signers: PublicKey[];
+}
+
+export const MULTISIG_LAYOUT = struct<Multisig>([
+  u8("isInitialized"),
+  u8("m"),
+  u8("n"),
+  array(publicKey(), MAX_SIGNERS, "signers"),
+]);
+
+export enum BuyoutStage {
+  Uninitialized = 0,
+  Active = 1,
+  Succeeded = 2,
+}
+
+export interface Buyout {
+  stage: BuyoutStage;
+  tokenPool: PublicKey;
+  buyer: PublicKey;
+  price: bigint;
+  deposit: bigint;
+  endTime: bigint;
+  objectionShare: number;
+  objectors: PublicKey[];
+}
+
+export const BUYOUT_LAYOUT = struct<Buyout>([
+  u8("stage"),
+  publicKey("tokenPool"),
+  publicKey("buyer"),
+  u64("price"),
+  u64("deposit"),
+  i64("endTime"),
+  f64("objectionShare"),
+  vec(publicKey(), "objectors"),
 ]);
 
 export const getPayload = (
@@ -85,23 +85,20 @@ const get_account_data = async (
   return account_after_buff;
 };
 
+const get_platform_config = async (): Promise<PublicKey> => {
+  const [platform_config] = await PublicKey.findProgramAddress(
+    [Buffer.from("platform")],
+    programId.publicKey
+  );
+  return platform_config;
+};
+
 const max_members = 4;
 export const description = "Monke NFT";
-const TOKEN_POOL_SIZE =
-  1 +
-  8 +
-  8 +
-  8 +
-  8 +
-  32 +
-  24 +
-  32 +
-  32 +
-  32 +
-  (1 + 4) +
-  (1 + 32 + 8 + 8 + 1 + 32) * max_members;
-const ESCROW_STATE_SIZE = 1 + 32 + 32 + 32 + 32 + 32 + 32 + 8;
-const TOKEN_MEMBER_LIST_SIZE = 1 + 4 + (1 + 32 + 8 + 8 + 1 + 32) * max_members;
+const ESCROW_STATE_SIZE =
+  1 + 32 + 32 + 32 + 32 + 32 + 32 + 8 + 1 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 32;
+const TOKEN_MEMBER_LIST_SIZE =
+  1 + 4 + (1 + 32 + 8 + 8 + 1 + 32 + 8 + 32 + 1) * max_members;
 
 /* Since we are saying we would have maximum of 4 members in this token pool so we would initialize the space for max of 4 members */
 
@@ -140,7 +137,7 @@ const main = async () => {
   await buyShareEscrow(pool_member, new_member); // buy share
   await updateShare(pool_member, 0); // update share
   await buyNft();
-  await setManager();
+  await proposeManager();
   await getNftAuthority();
 };
 
@@ -171,15 +168,6 @@ const getNftAuthority = async () => {
     newAccountPubkey: token_members_l.publicKey,
     programId: programId.publicKey,
   });
-  const token_pool_account_inst = SystemProgram.createAccount({
-    space: TOKEN_POOL_SIZE,
-    lamports: await connection.getMinimumBalanceForRentExemption(
-      TOKEN_POOL_SIZE
-    ),
-    fromPubkey: new_manager.publicKey,
-    newAccountPubkey: token_p.publicKey,
-    programId: programId.publicKey,
-  });
 
   const new_treasury = Keypair.generate();
   const [new_vault, new_vault_bump] = await PublicKey.findProgramAddress(
@@ -193,15 +181,21 @@ const getNftAuthority = async () => {
     newAccountPubkey: new_treasury.publicKey,
     programId: programId.publicKey,
   });
+  const [platform_config] = await PublicKey.findProgramAddress(
+    [Buffer.from("platform")],
+    programId.publicKey
+  );
   const transaction_inst = new TransactionInstruction({
     keys: [
       { pubkey: new_manager.publicKey, isSigner: true, isWritable: true },
       { pubkey: new_vault, isSigner: false, isWritable: false },
       { pubkey: nft_mint.publicKey, isSigner: false, isWritable: false }, // mint of the nft
-      { pubkey: token_p.publicKey, isSigner: false, isWritable: true },
+      { pubkey: token_p.publicKey, isSigner: true, isWritable: true },
       { pubkey: new_treasury.publicKey, isSigner: false, isWritable: false },
       { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: platform_config, isSigner: false, isWritable: false },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -210,7 +204,6 @@ const getNftAuthority = async () => {
   tx.add(
     treasury_account_inst,
     token_members_list_inst,
-    token_pool_account_inst,
     transaction_inst
   );
   await sendAndConfirmTransaction(connection, tx, [
@@ -235,6 +228,11 @@ const getNftAuthority = async () => {
       { pubkey: token_p.publicKey, isSigner: false, isWritable: true },
       { pubkey: new_treasury.publicKey, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value2)),
@@ -278,6 +276,11 @@ const getNftAuthority = async () => {
       { pubkey: escrow_data.escrowVault, isSigner: false, isWritable: true },
       { pubkey: pool_data.manager, isSigner: false, isWritable: true },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value3)),
@@ -313,8 +316,57 @@ const getNftAuthority = async () => {
   tx4.add(transaction_inst_3, transaction_inst_4);
   await sendAndConfirmTransaction(connection, tx4, [x_member]);
 
-  const pool_acc_2 = await connection.getAccountInfo(token_p.publicKey);
-  assert.equal(pool_acc_2, null);
+  // member took the whole pool, its treasury and then the pool give their rent back to the manager
+  const pool_acc_2 = await get_account_data(token_p.publicKey);
+  const pool_data_2: TokenPool = TOKEN_POOL_LAYOUT.decode(pool_acc_2.data);
+  pool_data_2.rentPayer.equals(new_manager.publicKey);
+  const close_keys = [
+    { pubkey: new_treasury.publicKey, isSigner: false, isWritable: true },
+    { pubkey: new_manager.publicKey, isSigner: false, isWritable: true },
+  ];
+  const close_treasury_inst = new TransactionInstruction({
+    keys: [
+      { pubkey: token_p.publicKey, isSigner: false, isWritable: false },
+      ...close_keys,
+    ],
+    programId: programId.publicKey,
+    data: Buffer.from(
+      serialize(
+        schema,
+        getPayload(
+          TokenPoolInstructions.CloseTreasury,
+          BigInt(0),
+          BigInt(0),
+          description,
+          max_members
+        )
+      )
+    ),
+  });
+  const close_pool_inst = new TransactionInstruction({
+    keys: [
+      { pubkey: token_p.publicKey, isSigner: false, isWritable: true },
+      ...close_keys,
+    ],
+    programId: programId.publicKey,
+    data: Buffer.from(
+      serialize(
+        schema,
+        getPayload(
+          TokenPoolInstructions.ClosePool,
+          BigInt(0),
+          BigInt(0),
+          description,
+          max_members
+        )
+      )
+    ),
+  });
+  const tx5 = new Transaction();
+  tx5.add(close_treasury_inst, close_pool_inst);
+  await sendAndConfirmTransaction(connection, tx5, [x_member]);
+  assert.equal(await connection.getAccountInfo(token_p.publicKey), null);
+  assert.equal(await connection.getAccountInfo(new_treasury.publicKey), null);
   const nft_acc = await get_account_data(seller_nft_account.publicKey);
   nft_acc.owner.equals(x_member.publicKey);
   const nft_mint_acc = await get_account_data(pool_data.targetToken);
@@ -323,10 +375,10 @@ const getNftAuthority = async () => {
   nft_mint_data.mintAuthority.equals(x_member.publicKey);
 };
 
-const setManager = async () => {
+const proposeManager = async () => {
   const new_manager = Keypair.generate();
   let value = getPayload(
-    TokenPoolInstructions.setManager,
+    TokenPoolInstructions.ProposeManager,
     BigInt(4),
     BigInt(1),
     description,
@@ -352,7 +404,33 @@ const setManager = async () => {
 
   const pool_acc_2 = await get_account_data(token_pool.publicKey);
   const pool_data_2: TokenPool = TOKEN_POOL_LAYOUT.decode(pool_acc_2.data);
-  pool_data_2.manager.equals(new_manager.publicKey);
+  pool_data_2.pendingManager.equals(new_manager.publicKey);
+
+  // the proposed manager takes over once they accept
+  let accept_value = getPayload(
+    TokenPoolInstructions.AcceptManager,
+    BigInt(0),
+    BigInt(0),
+    description,
+    max_members
+  );
+  const accept_tx = new Transaction();
+  accept_tx.add(
+    new TransactionInstruction({
+      keys: [
+        { pubkey: new_manager.publicKey, isSigner: true, isWritable: false },
+        { pubkey: token_pool.publicKey, isSigner: false, isWritable: true },
+      ],
+      programId: programId.publicKey,
+      data: Buffer.from(serialize(schema, accept_value)),
+    })
+  );
+
+  await sendAndConfirmTransaction(connection, accept_tx, [manager, new_manager]);
+
+  const pool_acc_3 = await get_account_data(token_pool.publicKey);
+  const pool_data_3: TokenPool = TOKEN_POOL_LAYOUT.decode(pool_acc_3.data);
+  pool_data_3.manager.equals(new_manager.publicKey);
 };
 
 const buyNft = async () => {
@@ -370,6 +448,11 @@ const buyNft = async () => {
       { pubkey: token_pool.publicKey, isSigner: false, isWritable: true },
       { pubkey: treasury.publicKey, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -414,6 +497,11 @@ const buyNft = async () => {
       { pubkey: escrow_data.escrowVault, isSigner: false, isWritable: true },
       { pubkey: pool_data.manager, isSigner: false, isWritable: true },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -462,7 +550,7 @@ const listNft = async () => {
     BigInt(10),
     BigInt(1),
     description,
-    max_members
+    0 // never expires
   );
 
   nft_escrow_state = Keypair.generate();
@@ -531,6 +619,11 @@ const listNft = async () => {
         isWritable: true,
       },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -578,6 +671,11 @@ const updateShare = async (member: Keypair, index: number) => {
       { pubkey: token_pool.publicKey, isSigner: false, isWritable: true },
       { pubkey: treasury.publicKey, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -625,6 +723,13 @@ const buyShareEscrow = async (addedBuyer: Keypair, seller: Keypair) => {
       { pubkey: escrow_vault, isSigner: false, isWritable: false },
       { pubkey: seller.publicKey, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
+      // seller signed the sale, so they paid the rent of the escrow
+      { pubkey: seller.publicKey, isSigner: false, isWritable: true },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -644,7 +749,7 @@ const buyShareEscrow = async (addedBuyer: Keypair, seller: Keypair) => {
 };
 
 const startSellEscrow = async (member: Keypair, index: number) => {
-  const value = getPayload(2, BigInt(2), BigInt(1), description, max_members); // only id needed , all other are placeholders
+  const value = getPayload(2, BigInt(2), BigInt(1), description, 0); // amount and a sale that never expires, all other are placeholders
   escrow_state = Keypair.generate();
   const create_escrow_inst = SystemProgram.createAccount({
     space: ESCROW_STATE_SIZE,
@@ -669,6 +774,11 @@ const startSellEscrow = async (member: Keypair, index: number) => {
       { pubkey: token_pool.publicKey, isSigner: false, isWritable: true },
       { pubkey: escrow_state.publicKey, isSigner: false, isWritable: true },
       { pubkey: escrow_vault, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -714,6 +824,11 @@ const addMember = async (member: Keypair, index: number) => {
       { pubkey: token_pool.publicKey, isSigner: false, isWritable: true },
       { pubkey: treasury.publicKey, isSigner: false, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      {
+        pubkey: await get_platform_config(),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -773,15 +888,6 @@ const initialize = async () => {
     newAccountPubkey: token_members_list.publicKey,
     programId: programId.publicKey,
   });
-  const token_pool_account_inst = SystemProgram.createAccount({
-    space: TOKEN_POOL_SIZE,
-    lamports: await connection.getMinimumBalanceForRentExemption(
-      TOKEN_POOL_SIZE
-    ),
-    fromPubkey: manager.publicKey,
-    newAccountPubkey: token_pool.publicKey,
-    programId: programId.publicKey,
-  });
 
   treasury = Keypair.generate();
   [vault, _vault_bump] = await PublicKey.findProgramAddress(
@@ -795,15 +901,21 @@ const initialize = async () => {
     newAccountPubkey: treasury.publicKey,
     programId: programId.publicKey,
   });
+  const [platform_config] = await PublicKey.findProgramAddress(
+    [Buffer.from("platform")],
+    programId.publicKey
+  );
   const transaction_inst = new TransactionInstruction({
     keys: [
       { pubkey: manager.publicKey, isSigner: true, isWritable: true },
       { pubkey: vault, isSigner: false, isWritable: false },
       { pubkey: nft_mint.publicKey, isSigner: false, isWritable: false }, // mint of the nft
-      { pubkey: token_pool.publicKey, isSigner: false, isWritable: true },
+      { pubkey: token_pool.publicKey, isSigner: true, isWritable: true },
       { pubkey: treasury.publicKey, isSigner: false, isWritable: false },
       { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: platform_config, isSigner: false, isWritable: false },
     ],
     programId: programId.publicKey,
     data: Buffer.from(serialize(schema, value)),
@@ -812,7 +924,6 @@ const initialize = async () => {
   tx.add(
     treasury_account_inst,
     token_members_list_inst,
-    token_pool_account_inst,
     transaction_inst
   );
   await sendAndConfirmTransaction(connection, tx, [
@@ -830,7 +941,8 @@ const initialize = async () => {
   pool_data.manager.equals(manager.publicKey);
   pool_data.targetToken.equals(nft_mint.publicKey);
   pool_data.treasury.equals(treasury.publicKey);
-  assert.equal(pool_data.poolMemberList.members.length, max_members);
+  // max members is derived by the program from the target and minimum amounts
+  assert.equal(pool_data.poolMemberList.members.length, Math.ceil(10 / 2));
 };
 
 main().then(
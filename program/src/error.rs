@@ -49,6 +49,111 @@ pub enum TokenPoolError {
     /// Member does not own 100% share of the nft in token pool
     #[error("MemberDontOwnFullShare")]
     MemberDontOwnFullShare,
+    /// Instruction is not supported by the kind of listing in escrow
+    #[error("InvalidListingKind")]
+    InvalidListingKind,
+    /// Auction end time should be in the future
+    #[error("InvalidAuctionEndTime")]
+    InvalidAuctionEndTime,
+    /// Auction has ended
+    #[error("AuctionEnded")]
+    AuctionEnded,
+    /// Auction has not ended yet
+    #[error("AuctionNotEnded")]
+    AuctionNotEnded,
+    /// Bid is lower than the minimum bid of the auction
+    #[error("BidTooLow")]
+    BidTooLow,
+    /// Auction already has bids
+    #[error("AuctionHasBids")]
+    AuctionHasBids,
+    /// Treasury of the token pool is committed to a bid on an auction
+    #[error("BidCommitted")]
+    BidCommitted,
+    /// Member does not own enough share
+    #[error("InsufficientShare")]
+    InsufficientShare,
+    /// Share bid is not open for the token pool
+    #[error("InvalidShareBid")]
+    InvalidShareBid,
+    /// Price of the share bid is lower than the minimum price asked
+    #[error("BidPriceBelowMinimum")]
+    BidPriceBelowMinimum,
+    /// Nft mint is not allowed by the token pool
+    #[error("MintNotAllowed")]
+    MintNotAllowed,
+    /// Allowed mints account has no space left
+    #[error("AllowedMintsFull")]
+    AllowedMintsFull,
+    /// Account has the layout of an older program version and has to be migrated
+    #[error("OutdatedLayout")]
+    OutdatedLayout,
+    /// Inventory account of the basket pool has no space left
+    #[error("InventoryFull")]
+    InventoryFull,
+    /// Instruction does not support the target kind of the token pool
+    #[error("WrongTargetKind")]
+    WrongTargetKind,
+    /// Buyout price is lower than the reserve price or the current buyout
+    #[error("BuyoutPriceTooLow")]
+    BuyoutPriceTooLow,
+    /// Buyout is not in the stage needed by the instruction
+    #[error("InvalidBuyoutStage")]
+    InvalidBuyoutStage,
+    /// Objection window of the buyout is still open
+    #[error("ObjectionWindowOpen")]
+    ObjectionWindowOpen,
+    /// Objection window of the buyout has closed
+    #[error("ObjectionWindowClosed")]
+    ObjectionWindowClosed,
+    /// Member has already objected to the buyout
+    #[error("AlreadyObjected")]
+    AlreadyObjected,
+    /// Shares of the token pool can not move while a buyout is open to objections
+    #[error("BuyoutActive")]
+    BuyoutActive,
+    /// Member does not hold a token of the gate mint of the token pool
+    #[error("MemberNotAllowed")]
+    MemberNotAllowed,
+    /// Contribution of the member is more than the maximum amount of the token pool
+    #[error("AboveMaximumAmount")]
+    AboveMaximumAmount,
+    /// Token pool needs more members than the platform allows
+    #[error("MaxMembersExceeded")]
+    MaxMembersExceeded,
+    /// Subscription of the oversubscribed token pool is still open
+    #[error("SubscriptionOpen")]
+    SubscriptionOpen,
+    /// Subscription of the oversubscribed token pool has ended
+    #[error("SubscriptionEnded")]
+    SubscriptionEnded,
+    /// Member has to claim their refund first
+    #[error("RefundNotClaimed")]
+    RefundNotClaimed,
+    /// Escrow has expired
+    #[error("EscrowExpired")]
+    EscrowExpired,
+    /// Escrow has not expired yet
+    #[error("EscrowNotExpired")]
+    EscrowNotExpired,
+    /// Buyer is not the one allowed by the seller
+    #[error("BuyerNotAllowed")]
+    BuyerNotAllowed,
+    /// Signer is not a delegate of the share with the permission needed
+    #[error("DelegateNotAllowed")]
+    DelegateNotAllowed,
+    /// Description of the token pool is longer than allowed
+    #[error("DescriptionTooLong")]
+    DescriptionTooLong,
+    /// Token pool or platform is paused
+    #[error("Paused")]
+    Paused,
+    /// Account is still in use and can not be closed
+    #[error("AccountNotClosable")]
+    AccountNotClosable,
+    /// Accounting of the token pool or its treasury does not reconcile
+    #[error("InvariantViolated")]
+    InvariantViolated,
 }
 
 impl From<TokenPoolError> for ProgramError {
@@ -94,6 +199,83 @@ impl PrintProgramError for TokenPoolError {
             TokenPoolError::MemberDontOwnFullShare => {
                 msg!("Member does not own 100% share of the nft in token pool")
             }
+            TokenPoolError::InvalidListingKind => {
+                msg!("Instruction is not supported by the kind of listing in escrow")
+            }
+            TokenPoolError::InvalidAuctionEndTime => {
+                msg!("Auction end time should be in the future")
+            }
+            TokenPoolError::AuctionEnded => msg!("Auction has ended"),
+            TokenPoolError::AuctionNotEnded => msg!("Auction has not ended yet"),
+            TokenPoolError::BidTooLow => msg!("Bid is lower than the minimum bid of the auction"),
+            TokenPoolError::AuctionHasBids => msg!("Auction already has bids"),
+            TokenPoolError::BidCommitted => {
+                msg!("Treasury of the token pool is committed to a bid on an auction")
+            }
+            TokenPoolError::InsufficientShare => msg!("Member does not own enough share"),
+            TokenPoolError::InvalidShareBid => msg!("Share bid is not open for the token pool"),
+            TokenPoolError::BidPriceBelowMinimum => {
+                msg!("Price of the share bid is lower than the minimum price asked")
+            }
+            TokenPoolError::MintNotAllowed => msg!("Nft mint is not allowed by the token pool"),
+            TokenPoolError::AllowedMintsFull => msg!("Allowed mints account has no space left"),
+            TokenPoolError::OutdatedLayout => {
+                msg!("Account has the layout of an older program version and has to be migrated")
+            }
+            TokenPoolError::InventoryFull => {
+                msg!("Inventory account of the basket pool has no space left")
+            }
+            TokenPoolError::WrongTargetKind => {
+                msg!("Instruction does not support the target kind of the token pool")
+            }
+            TokenPoolError::BuyoutPriceTooLow => {
+                msg!("Buyout price is lower than the reserve price or the current buyout")
+            }
+            TokenPoolError::InvalidBuyoutStage => {
+                msg!("Buyout is not in the stage needed by the instruction")
+            }
+            TokenPoolError::ObjectionWindowOpen => {
+                msg!("Objection window of the buyout is still open")
+            }
+            TokenPoolError::ObjectionWindowClosed => {
+                msg!("Objection window of the buyout has closed")
+            }
+            TokenPoolError::AlreadyObjected => msg!("Member has already objected to the buyout"),
+            TokenPoolError::BuyoutActive => {
+                msg!("Shares of the token pool can not move while a buyout is open to objections")
+            }
+            TokenPoolError::MemberNotAllowed => {
+                msg!("Member does not hold a token of the gate mint of the token pool")
+            }
+            TokenPoolError::AboveMaximumAmount => {
+                msg!("Contribution of the member is more than the maximum amount of the token pool")
+            }
+            TokenPoolError::MaxMembersExceeded => {
+                msg!("Token pool needs more members than the platform allows")
+            }
+            TokenPoolError::SubscriptionOpen => {
+                msg!("Subscription of the oversubscribed token pool is still open")
+            }
+            TokenPoolError::SubscriptionEnded => {
+                msg!("Subscription of the oversubscribed token pool has ended")
+            }
+            TokenPoolError::RefundNotClaimed => msg!("Member has to claim their refund first"),
+            TokenPoolError::EscrowExpired => msg!("Escrow has expired"),
+            TokenPoolError::EscrowNotExpired => msg!("Escrow has not expired yet"),
+            TokenPoolError::BuyerNotAllowed => msg!("Buyer is not the one allowed by the seller"),
+            TokenPoolError::DelegateNotAllowed => {
+                msg!("Signer is not a delegate of the share with the permission needed")
+            }
+            TokenPoolError::DescriptionTooLong => {
+                msg!("Description of the token pool is longer than allowed")
+            }
+            TokenPoolError::Paused => msg!("Token pool or platform is paused"),
+            TokenPoolError::AccountNotClosable => {
+                msg!("Account is still in use and can not be closed")
+            }
+            TokenPoolError::InvariantViolated => {
+                msg!("Accounting of the token pool or its treasury does not reconcile")
+            }
         }
     }
 }
@@ -11,20 +11,23 @@ pub struct Payload {
 }
 #[derive(Debug, BorshDeserialize, BorshSerialize, Clone, PartialEq)]
 pub enum TokenPoolInstructions {
-    /// Initialize a token pool with a target amount for purchasing of specific token
+    /// Initialize a token pool with a target amount for purchasing of specific token, max members is derived
+    /// from the target and minimum amounts and the token pool account is allocated with the size they need.
+    /// description can have at most `MAX_DESCRIPTION_LEN` bytes
     /// accounts required :
-    /// 0 - [signer] token pool manager , who is initializing the token pool
+    /// 0 - [signer, writer] token pool manager , who is initializing the token pool and pays for its account
     /// 1 - [] vault , pda which will own the token bought using the pool money
     /// 2 - [] target token , token which will be bought using pool money
-    /// 3 - [writer] token pool state account
+    /// 3 - [signer, writer] token pool state account, not allocated yet
     /// 4 - [] treasury,which will store all lamports of the pool
     /// 5 - [] rent sysvar
     /// 6 - [] token program
+    /// 7 - [] system program
+    /// 8 - [] platform config, pda of "platform"
     InitializePool {
         target_amount: u64,
         minimum_amount: u64,
         description: String,
-        max_members: u32,
         minimum_exemption_share: String,
     },
     /// AddMember instruction adds a member and their contribution to token pool
@@ -33,22 +36,33 @@ pub enum TokenPoolInstructions {
     /// 1 - [writer] token pool state account
     /// 2 - [writer] treasury , which will store all lamports of the pool
     /// 3 - [] system program
+    /// 4 - [] platform config, pda of "platform"
+    /// 5 - [] token account of member holding a token of the gate mint, only when token pool has a gate mint
     AddMember { amount: u64 },
-    /// SellShare instruction starts escrow to sell the share of a member to some other person
+    /// SellShare instruction starts escrow to sell the share of a member to some other person, not while the
+    /// subscription of an oversubscribed pool is open or the member has a refund to claim. a non zero expires in
+    /// lets anyone close the sale that many seconds after it starts
     /// accounts required :
-    /// 0 - [signer] member, who is selling his share
+    /// 0 - [signer] member, who is selling his share, signs unless a delegate sells for them
     /// 1 - [writer] token pool state account
     /// 2 - [writer] escrow state account
-    /// 3 - [] escrow vault
-    SellShare { amount: u64 },
+    /// 3 - [] escrow vault, pda of "escrow", member and token pool
+    /// 4 - [] platform config, pda of "platform"
+    /// 5 - [] allowed buyer, only for a private sale that no one else can buy, default pubkey lets anyone buy
+    /// 6 - [signer] delegate of member with the sell permission, only when member does not sign
+    SellShare { amount: u64, expires_in: u32 },
     /// BuyShare instruction buys the share of a nft through escrow process and compeletes escrow transacton
     /// accounts required :
     /// 0 - [signer] member, who is buying the share
     /// 1 - [writer] token pool state account
     /// 2 - [writer] escrow state account
-    /// 3 - [] escrow vault
+    /// 3 - [] escrow vault, pda of "escrow", seller and token pool
     /// 4 - [writer] seller , whose share we are buying
     /// 5 - [] system program
+    /// 6 - [] platform config, pda of "platform"
+    /// 7 - [writer] rent payer of the escrow, who gets its rent back
+    /// 8 - [] token account of buyer holding a token of the gate mint, only when token pool has a gate mint
+    ///        and buyer is not a member
     BuyShare { amount: u64 },
     /// UpgradeShare instruction upgrades the share of a member in token pool
     /// accounts required :
@@ -56,16 +70,28 @@ pub enum TokenPoolInstructions {
     /// 1 - [writer] token pool state account
     /// 2 - [writer] treasury , which will store all lamports of the pool
     /// 3 - [] system program
+    /// 4 - [] platform config, pda of "platform"
     UpgradeShare { amount: u64 },
-    /// ListNFT instruction lists the nft on the platform by starting an escrow for it
+    /// ListNFT instruction lists the nft on the platform by starting an escrow for it,
+    /// a non zero end time lists it for a dutch auction whose price decays from amount to floor price.
+    /// a non zero expires in lets anyone close the listing that many seconds after it starts
     /// accounts required :
     /// 0 - [signer] seller, who is selling the nft
     /// 1 - [writer] escrow state account for selling nft
-    /// 2 - [writer] vault,that will own the nft
-    /// 3 - [writer] NFT account
-    /// 4 - [] token program
-    ListNFT { amount: u64 },
-    /// ExecuteNFTBuy instruction buys the nft from the platform
+    /// 2 - [writer] nft mint account
+    /// 3 - [writer] vault,that will own the nft
+    /// 4 - [writer] NFT account
+    /// 5 - [] token program
+    /// 6 - [] platform config, pda of "platform"
+    ListNFT {
+        amount: u64,
+        floor_price: u64,
+        end_time: i64,
+        expires_in: u32,
+    },
+    /// ExecuteNFTBuy instruction buys the nft from the platform, for a dutch auction amount is the
+    /// most the pool pays and the current price is charged. what the price does not use of the current balance is
+    /// due to members as a refund, unless the pool is a basket which keeps it for its next nft
     /// accounts required :
     /// 0 - [signer] buyer, who is buying the nft
     /// 1 - [writer] escrow state account for selling nft
@@ -78,14 +104,20 @@ pub enum TokenPoolInstructions {
     /// 8 - [writer] escrow vault , which has authority over nft
     /// 9 - [writer] token pool manager
     /// 10 - [] token program
+    /// 11 - [] platform config, pda of "platform"
+    /// 12 - [] allowed mints account, only for a collection or basket token pool
+    /// 13 - [writer] inventory account, only for a basket token pool
     ExecuteNFTBuy { amount: u64 },
-    /// SetManager instruction will set a new manager for a token pool
+    /// ProposeManager instruction proposes a new manager for a token pool, who takes over by sending
+    /// AcceptManager. a new proposal replaces the pending one
     /// accounts required :
     /// 0 - [signer] manager, who is giving his authority as a manager of token pool
     /// 1 - [writer] token pool, for which manager authority is changing
-    /// 2 - [writer] new manager
-    SetManager,
-    /// GetNFTAuthority instruction will set the authority of NFT to member who owns 100% of shares
+    /// 2 - [] new manager
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    ProposeManager,
+    /// GetNFTAuthority instruction will set the authority of NFT to member who owns 100% of shares, the member
+    /// leaves the token pool which can then be closed with ClosePool
     /// accounts required :
     /// 0 - [signer] member of token pool, who will get the authority
     /// 1 - [writer] token pool state account
@@ -93,6 +125,403 @@ pub enum TokenPoolInstructions {
     /// 3 - [writer] nft account
     /// 4 - [writer] token pool vault, which currently has authority of nft
     /// 5 - [] token program
+    /// 6 - [writer] inventory account, only for a basket token pool
+    /// 7 - [writer] rent payer of the inventory, only when claiming the last nft of a basket token pool
     GetNFTAuthority, /* Instructions need to be implemented
                       */
+    /// CancelShareSale instruction closes the escrow of a share sale and gives the share back to seller
+    /// accounts required :
+    /// 0 - [signer] seller, who started the share sale, signs unless a delegate cancels for them
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] escrow state account
+    /// 3 - [] escrow vault, which currently holds the share, pda of "escrow", seller and token pool
+    /// 4 - [writer] rent payer of the escrow, who gets its rent back
+    /// 5 - [signer] delegate of seller with the sell permission, only when seller does not sign
+    CancelShareSale,
+    /// CancelNFTListing instruction closes the escrow of a listed nft and gives its authorities back to seller
+    /// accounts required :
+    /// 0 - [signer] seller, who listed the nft
+    /// 1 - [writer] escrow state account for selling nft
+    /// 2 - [writer] nft mint account
+    /// 3 - [] escrow vault, which has authority over nft
+    /// 4 - [writer] NFT account
+    /// 5 - [] token program
+    /// listed token accounts of ListTokens are cancelled the same way, only the owner of the token account goes back
+    CancelNFTListing,
+    /// ListNFTAuction instruction lists the nft on the platform for an english auction by starting an escrow for it
+    /// accounts required :
+    /// 0 - [signer] seller, who is selling the nft
+    /// 1 - [writer] escrow state account for selling nft
+    /// 2 - [writer] nft mint account
+    /// 3 - [writer] vault, that will own the nft
+    /// 4 - [writer] NFT account
+    /// 5 - [] token program
+    /// 6 - [] platform config, pda of "platform"
+    ListNFTAuction {
+        reserve_price: u64,
+        min_bid_increment: u64,
+        end_time: i64,
+    },
+    /// PlaceBid instruction places a bid on an auction, lamports of the bid are kept in escrow and
+    /// previous highest bidder is refunded. Bids of a token pool stay in its treasury till the auction settles
+    /// and are committed there, so the token pool can not buy anything else till it is outbid or the auction settles
+    /// accounts required :
+    /// 0 - [signer] bidder, who is placing the bid or the member bidding for token pool
+    /// 1 - [writer] escrow state account of the auction
+    /// 2 - [writer] previous highest bidder, who will be refunded, or its token pool state account
+    ///        when the previous highest bid was of a token pool
+    /// 3 - [] system program
+    /// 4 - [] platform config, pda of "platform"
+    /// 5 - [writer] token pool state account, optional and only when bidding with token pool treasury
+    /// 6 - [] allowed mints account, only when bidding with the treasury of a collection or basket token pool
+    PlaceBid { amount: u64 },
+    /// SettleAuction instruction gives the nft to highest bidder and the bid to seller once auction has ended,
+    /// nft goes back to seller if there were no bids or the winning token pool can no longer buy it
+    /// accounts required :
+    /// 0 - [writer] escrow state account of the auction
+    /// 1 - [writer] seller , who is selling the nft
+    /// 2 - [writer] nft mint account
+    /// 3 - [] escrow vault , which has authority over nft
+    /// 4 - [writer] NFT account
+    /// 5 - [] token program
+    /// 6 - [] platform config, pda of "platform"
+    /// 7 - [writer] token pool state account, only when a token pool won the auction
+    /// 8 - [writer] treasury, only when a token pool won the auction
+    /// 9 - [writer] token pool manager, only when a token pool won the auction
+    /// 10 - [writer] inventory account, only when a basket token pool won the auction
+    /// 11 - [] allowed mints account, only when a collection or basket token pool won the auction
+    SettleAuction,
+    /// PlaceShareBid instruction posts a standing bid to buy share units of a token pool,
+    /// lamports for the whole bid are kept in the bid account. A unit is one basis point of the pool
+    /// accounts required :
+    /// 0 - [signer] bidder, who is buying the share units
+    /// 1 - [] token pool state account
+    /// 2 - [writer] share bid, pda of bidder and token pool
+    /// 3 - [] system program
+    /// 4 - [] platform config, pda of "platform"
+    /// 5 - [] token account of bidder holding a token of the gate mint, only when token pool has a gate mint
+    ///        and bidder is not a member
+    PlaceShareBid { price_per_unit: u64, quantity: u64 },
+    /// CancelShareBid instruction closes a share bid and refunds its lamports to bidder
+    /// accounts required :
+    /// 0 - [signer] bidder, who posted the bid
+    /// 1 - [writer] share bid
+    CancelShareBid,
+    /// FillBid instruction sells share units of a member into a share bid
+    /// accounts required :
+    /// 0 - [signer] member, who is selling the share units
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] share bid
+    /// 3 - [writer] bidder, who posted the bid
+    /// 4 - [] platform config, pda of "platform"
+    FillBid { units: u64, min_price_per_unit: u64 },
+    /// AddAllowedMints instruction adds nft mints a token pool is allowed to buy till members contribute, the first
+    /// call turns the token pool into a collection pool which buys whichever allowed nft it can afford, the mint
+    /// it was buying included
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] allowed mints account, owned by the program
+    /// 3.. - [] nft mints to allow
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    AddAllowedMints,
+    /// InitializeBasket instruction turns a collection token pool into a basket pool, which keeps buying
+    /// allowed nfts into its inventory while its treasury lasts. Members own the whole basket by their shares
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] inventory account, owned by the program
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    InitializeBasket,
+    /// SetFungibleTarget instruction makes a token pool buy an amount of an spl token instead of an nft, till members
+    /// contribute
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// 2 - [] mint of the spl token
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetFungibleTarget { token_amount: u64 },
+    /// ListTokens instruction lists a token account of an spl token for a fixed price by starting an escrow for it
+    /// accounts required :
+    /// 0 - [signer] seller, who is selling the tokens
+    /// 1 - [writer] escrow state account for selling tokens
+    /// 2 - [] mint of the spl token
+    /// 3 - [] vault, that will own the token account
+    /// 4 - [writer] token account holding the tokens
+    /// 5 - [] token program
+    /// 6 - [] platform config, pda of "platform"
+    ListTokens { amount: u64 },
+    /// ExecuteTokenBuy instruction buys listed tokens for a fungible token pool, its vault becomes owner of the token account.
+    /// what the price does not use of the current balance is due to members as a refund
+    /// accounts required :
+    /// 0 - [signer] buyer, member of token pool who is buying the tokens
+    /// 1 - [writer] escrow state account for selling tokens
+    /// 2 - [] token pool vault, that will own the token account
+    /// 3 - [writer] token account holding the tokens
+    /// 4 - [writer] token pool state account
+    /// 5 - [writer] treasury, which is storing all lamports of the pool
+    /// 6 - [writer] seller, who is selling the tokens
+    /// 7 - [] mint of the spl token
+    /// 8 - [] escrow vault, which owns the token account
+    /// 9 - [writer] token pool manager
+    /// 10 - [] token program
+    /// 11 - [] platform config, pda of "platform"
+    ExecuteTokenBuy { amount: u64 },
+    /// Redeem instruction clears the share of a member in a fungible token pool and transfers the member
+    /// their pro-rata amount of the tokens owned by token pool vault
+    /// accounts required :
+    /// 0 - [signer] member, who is redeeming the share
+    /// 1 - [writer] token pool state account
+    /// 2 - [] token pool vault
+    /// 3 - [writer] token account owned by token pool vault
+    /// 4 - [writer] token account of member receiving the tokens
+    /// 5 - [] token program
+    Redeem,
+    /// SetBuyoutTerms instruction sets the reserve price for buying out the whole token pool and the time members
+    /// get to object a buyout, a zero reserve price disables buyouts. the objection period can't be zero and the
+    /// terms can only be set till the pool owns its nft
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetBuyoutTerms {
+        reserve_price: u64,
+        objection_period: i64,
+    },
+    /// StartBuyout instruction offers to buy the nft of a token pool from all its members, buyer deposits lamports
+    /// for the shares they don't own and members get the objection period to object. A higher offer during the
+    /// window replaces the current one, which is refunded. Shares of the pool can not be transferred or sold into
+    /// share bids till the window ends, so every share objects at most once
+    /// accounts required :
+    /// 0 - [signer] buyer
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] buyout, pda of token pool
+    /// 3 - [writer] current buyer, who is refunded when the offer is replaced
+    /// 4 - [] system program
+    /// 5 - [] platform config, pda of "platform"
+    StartBuyout { price: u64 },
+    /// ObjectBuyout instruction records the objection of a member to the buyout
+    /// accounts required :
+    /// 0 - [signer] member of token pool, signs unless a delegate objects for them
+    /// 1 - [] token pool state account
+    /// 2 - [writer] buyout
+    /// 3 - [signer] delegate of member with the vote permission, only when member does not sign
+    ObjectBuyout,
+    /// CompleteBuyout instruction ends a buyout after its objection window, the nft goes to buyer unless members
+    /// owning half of the other shares objected, in which case the deposit goes back to buyer
+    /// accounts required :
+    /// 0 - [writer] buyout
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] buyer
+    /// 3 - [writer] nft mint account
+    /// 4 - [writer] nft account
+    /// 5 - [] token pool vault, which has authority over nft
+    /// 6 - [] token program
+    /// 7 - [] platform config, pda of "platform"
+    CompleteBuyout,
+    /// ClaimBuyoutPayout instruction pays a member for their share once the buyout has succeeded
+    /// accounts required :
+    /// 0 - [signer, writer] member of token pool, who is paid, signs unless a delegate claims for them
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] buyout
+    /// 3 - [writer] buyer, who gets what is left in buyout after the last payout
+    /// 4 - [] platform config, pda of "platform"
+    /// 5 - [signer] delegate of member with the claim permission, only when member does not sign
+    ClaimBuyoutPayout,
+    /// SetGateMint instruction restricts joining the token pool to holders of a token of the gate mint,
+    /// leaving out the gate mint lets anyone join again
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// 2 - [] gate mint, optional
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetGateMint,
+    /// SetMaximumAmount instruction caps what a single member can contribute to the token pool,
+    /// as lamports or as a percent of the target amount. zero removes the cap
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetMaximumAmount {
+        maximum_amount: u64,
+        is_percent: bool,
+    },
+    /// SetManagerShare instruction sets the share units granted to the manager as carried interest when the
+    /// token pool buys, other members' shares are scaled down by it. it can only be set before anyone contributes
+    /// and reserves a place in the members list for the manager
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetManagerShare { manager_share: u64 },
+    /// SetPlatformConfig instruction sets the most members a new token pool can have, the upgrade authority
+    /// of the program creates the platform config and becomes its authority
+    /// accounts required :
+    /// 0 - [signer, writer] platform authority
+    /// 1 - [writer] platform config, pda of "platform"
+    /// 2 - [] system program
+    /// 3 - [] program data account of the token pool program, holding its upgrade authority
+    SetPlatformConfig { max_members: u32 },
+    /// SetAdmissionPolicy instruction sets how much a new member has to contribute so the token pool
+    /// can still reach its target with the member slots left
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetAdmissionPolicy { even_split: bool },
+    /// SetSubscriptionEnd instruction lets contributions go beyond the target amount till the subscription end,
+    /// zero stops oversubscribing. pools can't buy while the subscription is open
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetSubscriptionEnd { subscription_end: i64 },
+    /// CloseSubscription instruction scales down every member's contribution pro-rata once the subscription
+    /// has ended, so the pool keeps its target amount and the excess is due to members as refunds
+    /// accounts required :
+    /// 0 - [writer] token pool state account
+    CloseSubscription,
+    /// ClaimRefund instruction pays a member the refund due to them after an oversubscription
+    /// accounts required :
+    /// 0 - [signer, writer] member of token pool, who is paid, signs unless a delegate claims for them
+    /// 1 - [writer] token pool state account
+    /// 2 - [writer] treasury
+    /// 3 - [signer] delegate of member with the claim permission, only when member does not sign
+    ClaimRefund,
+    /// SetEscrowExpiry instruction sets when a share sale or an nft or tokens listing expires, zero never expires it.
+    /// an expired escrow can't be bought
+    /// accounts required :
+    /// 0 - [signer] seller
+    /// 1 - [writer] escrow state account
+    SetEscrowExpiry { expires_at: i64 },
+    /// ExpireEscrow instruction closes an expired escrow, anyone can send it. the share goes back to seller
+    /// or the authorities of the listed account are given back, rent of the escrow goes to its rent payer
+    /// accounts required :
+    /// 0 - [writer] escrow state account
+    /// 1 - [writer] seller
+    /// 2 - [] escrow vault, pda of "escrow", seller and token pool for a share sale
+    /// 3 - [writer] rent payer of the escrow
+    /// for a share sale :
+    /// 4 - [writer] token pool state account
+    /// for an nft or tokens listing :
+    /// 4 - [writer] nft mint account
+    /// 5 - [writer] nft or token account
+    /// 6 - [] token program
+    ExpireEscrow,
+    /// TransferShare instruction moves the share of a member, or a part of it in share units, to another wallet
+    /// without a sale. zero units moves the whole share, the share merges into the recipient's if they are a member
+    /// accounts required :
+    /// 0 - [signer] member, whose share is transferred
+    /// 1 - [writer] token pool state account
+    /// 2 - [] recipient
+    /// 3 - [] platform config, pda of "platform"
+    /// 4 - [] token account of recipient holding a token of the gate mint, only when token pool has a gate mint
+    ///        and recipient is not a member
+    TransferShare { units: u64 },
+    /// ApproveDelegate instruction lets a delegate act on the share of a member, permissions are the bits of
+    /// DELEGATE_VOTE to object buyouts, DELEGATE_SELL to sell the share or cancel its sale and DELEGATE_CLAIM
+    /// to claim payouts and refunds, which are paid to the member. it replaces the current delegate
+    /// accounts required :
+    /// 0 - [signer] member of token pool
+    /// 1 - [writer] token pool state account
+    /// 2 - [] delegate
+    ApproveDelegate { permissions: u8 },
+    /// RevokeDelegate instruction removes the delegate of the share of a member
+    /// accounts required :
+    /// 0 - [signer] member of token pool
+    /// 1 - [writer] token pool state account
+    RevokeDelegate,
+    /// InitializeMultisig instruction sets the m of n signers of a multisig, which can be the manager of
+    /// token pools. the multisig account is allocated rent exempt with the size of Multisig and owned by the
+    /// program in the same transaction
+    /// accounts required :
+    /// 0 - [signer, writer] multisig account
+    /// 1.. - [] signers of the multisig, at most MAX_SIGNERS
+    InitializeMultisig { m: u8 },
+    /// CollectFees instruction moves the fees paid to a multisig manager out of the multisig account, zero
+    /// collects everything the account holds over its rent exemption
+    /// accounts required :
+    /// 0 - [writer] multisig account
+    /// 1 - [writer] destination
+    /// 2.. - [signer] signers of the multisig
+    CollectFees { amount: u64 },
+    /// AcceptManager instruction completes the handover of a token pool to the proposed manager
+    /// accounts required :
+    /// 0 - [signer] proposed manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the proposed manager is a multisig
+    AcceptManager,
+    /// CancelManagerTransfer instruction clears the proposed manager of a token pool
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    CancelManagerTransfer,
+    /// SetPoolPaused instruction pauses or resumes the contributions, trading, purchases and buyouts of a token pool,
+    /// refunds and cancellations are still allowed while it is paused
+    /// accounts required :
+    /// 0 - [signer] token pool manager
+    /// 1 - [writer] token pool state account
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SetPoolPaused { paused: bool },
+    /// SetPlatformPaused instruction pauses or resumes the contributions, trading, listings, purchases and buyouts
+    /// of every token pool, the platform config has to be set up first
+    /// accounts required :
+    /// 0 - [signer] platform authority
+    /// 1 - [writer] platform config, pda of "platform"
+    SetPlatformPaused { paused: bool },
+    /// ClosePool instruction closes a finished token pool and refunds its rent to the rent payer. a pool is
+    /// finished once it owns or sold its nft and no member holds a share, has a refund due or a balance left.
+    /// a pool nobody has joined is closed by its manager. the treasury has to be closed first
+    /// accounts required :
+    /// 0 - [writer] token pool state account
+    /// 1 - [] treasury of the token pool, already closed
+    /// 2 - [writer] rent payer of the token pool
+    /// 3 - [signer] token pool manager, only for a pool nobody has joined
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    ClosePool,
+    /// CloseEscrow instruction closes the escrow of a sold nft and refunds its rent to the rent payer, escrows
+    /// which are cancelled, expired or bought are closed by those instructions
+    /// accounts required :
+    /// 0 - [writer] escrow state account
+    /// 1 - [writer] rent payer of the escrow
+    CloseEscrow,
+    /// CloseTreasury instruction closes the treasury of a finished token pool and refunds its lamports to the
+    /// rent payer of the pool, with the same preconditions as ClosePool. a treasury still owing refunds is not closed
+    /// accounts required :
+    /// 0 - [] token pool state account
+    /// 1 - [writer] treasury of the token pool
+    /// 2 - [writer] rent payer of the token pool
+    /// 3 - [signer] token pool manager, only for a pool nobody has joined
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    CloseTreasury,
+    /// SweepTreasury instruction moves the lamports a treasury holds over its current balance, refunds due
+    /// and rent to the token pool manager, such as the rounding of refunds or lamports sent to it directly
+    /// accounts required :
+    /// 0 - [signer, writer] token pool manager, who receives the excess
+    /// 1 - [] token pool state account
+    /// 2 - [writer] treasury of the token pool
+    /// n.. - [signer] signers of the multisig, only when the manager is a multisig
+    SweepTreasury,
+    /// CloseAllowedMints instruction closes the allowed mints of a token pool which is finished or closed and
+    /// refunds its rent to the manager who set it up
+    /// accounts required :
+    /// 0 - [writer] allowed mints account
+    /// 1 - [] token pool state account
+    /// 2 - [writer] rent payer of the allowed mints
+    CloseAllowedMints,
+    /// MigrateTokenPool instruction moves a token pool created before the layout was versioned to the current
+    /// layout, anyone can send it. Fields added since keep their defaults and the manager becomes the rent payer
+    /// accounts required :
+    /// 0 - [signer, writer] payer, who funds the rent of the bigger account
+    /// 1 - [writer] token pool state account
+    /// 2 - [] system program
+    MigrateTokenPool,
+    /// MigrateEscrow instruction moves an escrow created before listings had kinds to the current layout, anyone
+    /// can send it. The escrow becomes a fixed price sale anyone can buy and its seller the rent payer
+    /// accounts required :
+    /// 0 - [signer, writer] payer, who funds the rent of the bigger account
+    /// 1 - [writer] escrow state account
+    /// 2 - [] system program
+    MigrateEscrow,
 }
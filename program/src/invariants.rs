@@ -0,0 +1,120 @@
+use crate::error::TokenPoolError;
+use crate::state::*;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, rent::Rent, sysvar::Sysvar,
+};
+
+/// Shares are f64 percents, sums of them are only equal up to this
+const SHARE_TOLERANCE: f64 = 1e-6;
+
+/// Check the accounting of a token pool after a state transition. while the pool takes contributions
+/// the members deposited its current balance and own the share of it, once it has bought members own
+/// at most the whole pool. a bid is only committed out of the current balance
+pub fn check_token_pool(token_pool: &TokenPool) -> ProgramResult {
+    if token_pool.committed_bid > token_pool.current_balance {
+        msg!("committed bid is more than the current balance");
+        return Err(TokenPoolError::InvariantViolated.into());
+    }
+    let members = &token_pool.pool_member_list;
+    let total_share = members.total_share();
+    if token_pool.stage == TokenPoolStage::Initialized {
+        if members.total_deposited() != Some(token_pool.current_balance) {
+            msg!("amount deposited by members does not add up to current balance");
+            return Err(TokenPoolError::InvariantViolated.into());
+        }
+        let balance_share = token_pool.find_share(token_pool.current_balance).unwrap();
+        if (total_share - balance_share).abs() > SHARE_TOLERANCE {
+            msg!("shares of members do not add up to the share of current balance");
+            return Err(TokenPoolError::InvariantViolated.into());
+        }
+    } else if total_share > 100.0 + SHARE_TOLERANCE {
+        msg!("shares of members add up to more than the whole pool");
+        return Err(TokenPoolError::InvariantViolated.into());
+    }
+    Ok(())
+}
+
+/// Lamports the treasury has to keep, the current balance and refunds due on top of its rent
+pub fn treasury_reserve(
+    token_pool: &TokenPool,
+    treasury_info: &AccountInfo,
+) -> Result<u64, TokenPoolError> {
+    let rent = Rent::get()
+        .map_err(|_| TokenPoolError::InvalidData)?
+        .minimum_balance(treasury_info.data_len());
+    token_pool
+        .pool_member_list
+        .total_refund_due()
+        .and_then(|refund_due| refund_due.checked_add(token_pool.current_balance))
+        .and_then(|reserve| reserve.checked_add(rent))
+        .ok_or(TokenPoolError::InvariantViolated)
+}
+
+/// Check if the treasury of the token pool holds at least what it owes
+pub fn check_treasury(token_pool: &TokenPool, treasury_info: &AccountInfo) -> ProgramResult {
+    if token_pool.treasurey != *treasury_info.key {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    if treasury_info.lamports() < treasury_reserve(token_pool, treasury_info)? {
+        msg!("treasury holds less than the current balance, refunds due and rent");
+        return Err(TokenPoolError::InvariantViolated.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::tests::token_pool;
+    use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+    fn violated() -> ProgramResult {
+        Err(ProgramError::from(TokenPoolError::InvariantViolated))
+    }
+
+    fn funded_pool() -> TokenPool {
+        let mut pool = token_pool("", 3);
+        pool.pool_member_list
+            .add_member(0, Pubkey::new_unique(), 300, 30.0);
+        pool.pool_member_list
+            .add_member(1, Pubkey::new_unique(), 200, 20.0);
+        pool.current_balance = 500;
+        pool
+    }
+
+    #[test]
+    fn members_have_to_have_deposited_the_current_balance() {
+        let mut pool = funded_pool();
+        assert_eq!(check_token_pool(&pool), Ok(()));
+        pool.current_balance = 501;
+        assert_eq!(check_token_pool(&pool), violated());
+    }
+
+    #[test]
+    fn shares_have_to_match_the_current_balance_while_contributing() {
+        let mut pool = funded_pool();
+        pool.pool_member_list.members[0].share = 31.0;
+        assert_eq!(check_token_pool(&pool), violated());
+    }
+
+    #[test]
+    fn bid_is_committed_out_of_the_current_balance() {
+        let mut pool = funded_pool();
+        pool.committed_bid = 500;
+        assert_eq!(check_token_pool(&pool), Ok(()));
+        pool.committed_bid = 501;
+        assert_eq!(check_token_pool(&pool), violated());
+    }
+
+    #[test]
+    fn members_own_at_most_the_whole_pool_once_it_bought() {
+        let mut pool = funded_pool();
+        pool.stage = TokenPoolStage::NFTOwned;
+        pool.current_balance = 0;
+        pool.pool_member_list.members[0].share = 60.0;
+        pool.pool_member_list.members[1].share = 40.0;
+        assert_eq!(check_token_pool(&pool), Ok(()));
+        pool.pool_member_list.members[1].share = 41.0;
+        assert_eq!(check_token_pool(&pool), violated());
+    }
+}
@@ -0,0 +1,196 @@
+//! Account layouts written by the program before token pools were versioned, decoded only to
+//! migrate them to the current layout
+use crate::state::*;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+/// Token pool as it was before it grew a layout version, every field added since is left at its default
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct LegacyTokenPool {
+    pub stage: TokenPoolStage,                   //1
+    pub target_amount: u64,                      //8
+    pub minimum_exemption_amount: u64,           //8
+    pub minimum_amount: u64,                     //8
+    pub current_balance: u64,                    //8
+    pub target_token: Pubkey,                    //32
+    pub description: String,                     //4 + len
+    pub vault: Pubkey,                           //32
+    pub manager: Pubkey,                         //32
+    pub treasurey: Pubkey,                       //32
+    pub header: TokenPoolHeader,                 //5
+    pub members: Vec<LegacyPoolMemberShareInfo>, // 4 + 82*max_members
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq)]
+pub struct LegacyPoolMemberShareInfo {
+    pub account_type: AccountType, // 1
+    pub member_key: Pubkey,        // 32
+    pub amount_deposited: u64,     // 8
+    pub share: f64,                // 8
+    pub share_stage: ShareStage,   //1
+    pub escrow: Pubkey,            //32
+}
+
+impl LegacyTokenPool {
+    /// the token pool in the current layout, its manager set it up so they get the rent back on close
+    pub fn migrate(self) -> TokenPool {
+        TokenPool {
+            version: TOKEN_POOL_VERSION,
+            stage: self.stage,
+            target_amount: self.target_amount,
+            minimum_exemption_amount: self.minimum_exemption_amount,
+            minimum_amount: self.minimum_amount,
+            current_balance: self.current_balance,
+            target_token: self.target_token,
+            description: self.description,
+            vault: self.vault,
+            manager: self.manager,
+            treasurey: self.treasurey,
+            target_kind: PoolTargetKind::SingleMint,
+            inventory: Pubkey::default(),
+            target_token_amount: 0,
+            reserve_price: 0,
+            objection_period: 0,
+            gate_mint: Pubkey::default(),
+            maximum_amount: 0,
+            maximum_kind: MaximumKind::Amount,
+            manager_share: 0,
+            admission_policy: AdmissionPolicy::LastMember,
+            subscription_end: 0,
+            pending_manager: Pubkey::default(),
+            paused: false,
+            rent_payer: self.manager,
+            committed_bid: 0,
+            bid_escrow: Pubkey::default(),
+            buyout_end: 0,
+            pool_member_list: PoolMemberList {
+                header: self.header,
+                members: self
+                    .members
+                    .iter()
+                    .map(|member| PoolMemberShareInfo {
+                        account_type: member.account_type,
+                        member_key: member.member_key,
+                        amount_deposited: member.amount_deposited,
+                        share: member.share,
+                        share_stage: member.share_stage,
+                        escrow: member.escrow,
+                        ..PoolMemberShareInfo::default()
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Escrow as it was before listings, auctions and expiries, its buyer was never set
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct LegacyEscrow {
+    pub stage: EscrowStage,   //1
+    pub seller: Pubkey,       //32
+    pub buyer: Pubkey,        //32
+    pub escrow_vault: Pubkey, //32
+    pub share: f64,           //32
+    pub nft: Pubkey,          //32
+    pub nft_mint: Pubkey,     //32
+    pub amount: u64,          //8
+}
+
+impl LegacyEscrow {
+    /// size the program gave legacy escrow accounts
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 32 + 32 + 8;
+
+    /// the escrow in the current layout, a fixed price sale anyone can buy whose seller paid the rent
+    pub fn migrate(self) -> Escrow {
+        Escrow {
+            stage: self.stage,
+            seller: self.seller,
+            escrow_vault: self.escrow_vault,
+            share: self.share,
+            nft: self.nft,
+            nft_mint: self.nft_mint,
+            amount: self.amount,
+            listing: ListingKind::FixedPrice,
+            rent_payer: self.seller,
+            ..Escrow::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program_pack::Pack;
+
+    #[test]
+    fn legacy_token_pool_loads_after_migration() {
+        let mut members = vec![
+            LegacyPoolMemberShareInfo {
+                account_type: AccountType::Uninitialized,
+                member_key: Pubkey::default(),
+                amount_deposited: 0,
+                share: 0.0,
+                share_stage: ShareStage::Uninitialized,
+                escrow: Pubkey::default(),
+            };
+            2
+        ];
+        members[0] = LegacyPoolMemberShareInfo {
+            account_type: AccountType::TokenPoolMember,
+            member_key: Pubkey::new_unique(),
+            amount_deposited: 400,
+            share: 40.0,
+            share_stage: ShareStage::Hold,
+            escrow: Pubkey::default(),
+        };
+        let manager = Pubkey::new_unique();
+        let legacy = LegacyTokenPool {
+            stage: TokenPoolStage::Initialized,
+            target_amount: 1_000,
+            minimum_exemption_amount: 0,
+            minimum_amount: 100,
+            current_balance: 400,
+            target_token: Pubkey::new_unique(),
+            description: "legacy".to_string(),
+            vault: Pubkey::new_unique(),
+            manager,
+            treasurey: Pubkey::new_unique(),
+            header: TokenPoolHeader {
+                account_type: AccountType::TokenPoolMember,
+                max_members: 2,
+            },
+            members,
+        };
+        let data = legacy.try_to_vec().unwrap();
+        // a legacy pool starts with its stage, which is never taken for the current version
+        assert!(TokenPool::load(&data).is_err());
+
+        let migrated = legacy.migrate().try_to_vec().unwrap();
+        let token_pool = TokenPool::load(&migrated).unwrap();
+        assert_eq!(token_pool.current_balance, 400);
+        assert_eq!(token_pool.rent_payer, manager);
+        assert_eq!(token_pool.pool_member_list.header.max_members, 2);
+        let member = token_pool.pool_member_list.members[0];
+        assert_eq!(member.amount_deposited, 400);
+        assert_eq!(member.share_stage, ShareStage::Hold);
+        assert_eq!(member.refund_due, 0);
+    }
+
+    #[test]
+    fn legacy_escrow_migrates_to_a_fixed_price_sale() {
+        let legacy = LegacyEscrow {
+            stage: EscrowStage::Initialized,
+            seller: Pubkey::new_unique(),
+            amount: 5_000,
+            ..LegacyEscrow::default()
+        };
+        // the account is sized for a 32 bytes share, the escrow itself is smaller
+        assert!(legacy.try_to_vec().unwrap().len() <= LegacyEscrow::LEN);
+
+        let escrow = legacy.clone().migrate();
+        assert_eq!(escrow.listing, ListingKind::FixedPrice);
+        assert_eq!(escrow.rent_payer, legacy.seller);
+        assert_eq!(escrow.amount, 5_000);
+        assert!(escrow.try_to_vec().unwrap().len() <= Escrow::LEN);
+    }
+}
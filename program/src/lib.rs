@@ -1,6 +1,9 @@
+#[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 pub mod error;
 pub mod instructions;
+pub mod invariants;
+pub mod legacy;
 pub mod processor;
 pub mod state;
 pub use solana_program;
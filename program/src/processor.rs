@@ -1,20 +1,24 @@
 use crate::error::TokenPoolError;
 use crate::id;
 use crate::instructions::*;
+use crate::invariants::{check_token_pool, check_treasury, treasury_reserve};
+use crate::legacy::{LegacyEscrow, LegacyTokenPool};
 use crate::state::*;
 use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::borsh::try_from_slice_unchecked;
+use solana_program::borsh::{get_instance_packed_len, try_from_slice_unchecked};
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
 use solana_program::program::invoke_signed;
 use solana_program::system_instruction::create_account;
 use solana_program::system_instruction::transfer;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    clock::Clock,
+    clock::{Clock, UnixTimestamp},
     entrypoint::ProgramResult,
     msg,
     program::invoke,
     program_error::ProgramError,
     program_pack::Pack,
+    program_utils::limited_deserialize,
     pubkey::Pubkey,
     rent::Rent,
     sysvar::Sysvar,
@@ -40,43 +44,93 @@ pub fn process_instruction(
             let treasury_info = next_account_info(accounts_iter)?;
             let rent = Rent::from_account_info(next_account_info(accounts_iter)?)?;
             let _token_program = next_account_info(accounts_iter)?;
+            let system_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
 
-            if !rent.is_exempt(token_pool_info.lamports(), token_pool_info.data_len()) {
-                return Err(ProgramError::AccountNotRentExempt);
+            if !manager_info.is_signer || !token_pool_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            // token pool account is allocated here with the size needed by its members
+            if token_pool_info.lamports() > 0 {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+
+            // description is stored in the token pool account, its size is bounded with the members
+            if instruction.arg3.len() > MAX_DESCRIPTION_LEN {
+                return Err(TokenPoolError::DescriptionTooLong.into());
             }
 
             // check if target amount is less than minimum amount to be member
-            if instruction.arg1 < instruction.arg2 {
+            if instruction.arg2 == 0 || instruction.arg1 < instruction.arg2 {
                 return Err(TokenPoolError::WrongAmountData.into());
             }
 
+            // every member contributes at least the minimum amount, so these many members always reach the target
+            let max_members = max_members_for(instruction.arg1, instruction.arg2)
+                .ok_or(TokenPoolError::MaxMembersExceeded)?;
             // check if max members is atleast 2 to make a token pool
-            if instruction.arg4 < 2 {
+            if max_members < 2 {
                 return Err(TokenPoolError::MaxMemberAtleastTwo.into());
             }
+            if max_members > platform_max_members(platform_config_info)? {
+                return Err(TokenPoolError::MaxMembersExceeded.into());
+            }
 
             let share_sent = instruction.arg5;
-
-            msg!("Deserialize token pool account !");
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
-
             msg!("{}", share_sent);
 
-            let pool_members_list: PoolMemberList = PoolMemberList::new(instruction.arg4);
-            token_pool.current_balance = 0;
-            token_pool.minimum_exemption_amount = share_sent;
-            token_pool.description = instruction.arg3;
-            token_pool.target_amount = instruction.arg1;
-            token_pool.manager = *manager_info.key;
-            token_pool.target_token = *target_token.key;
-            token_pool.treasurey = *treasury_info.key;
-            token_pool.vault = *vault_info.key;
-            token_pool.minimum_amount = instruction.arg2;
-            token_pool.pool_member_list = pool_members_list;
-            token_pool.stage = TokenPoolStage::Initialized;
+            let token_pool = TokenPool {
+                version: TOKEN_POOL_VERSION,
+                stage: TokenPoolStage::Initialized,
+                target_amount: instruction.arg1,
+                minimum_exemption_amount: share_sent,
+                minimum_amount: instruction.arg2,
+                current_balance: 0,
+                target_token: *target_token.key,
+                description: instruction.arg3,
+                vault: *vault_info.key,
+                manager: *manager_info.key,
+                treasurey: *treasury_info.key,
+                committed_bid: 0,
+                bid_escrow: Pubkey::default(),
+                target_kind: PoolTargetKind::SingleMint,
+                inventory: Pubkey::default(),
+                target_token_amount: 0,
+                reserve_price: 0,
+                objection_period: 0,
+                buyout_end: 0,
+                gate_mint: Pubkey::default(),
+                maximum_amount: 0,
+                maximum_kind: MaximumKind::Amount,
+                manager_share: 0,
+                admission_policy: AdmissionPolicy::LastMember,
+                subscription_end: 0,
+                pending_manager: Pubkey::default(),
+                paused: false,
+                rent_payer: *manager_info.key,
+                pool_member_list: PoolMemberList::new(max_members),
+            };
+
+            msg!("create token pool account !");
+            let space = get_instance_packed_len(&token_pool)?;
+            let create_inst = create_account(
+                manager_info.key,
+                token_pool_info.key,
+                rent.minimum_balance(space),
+                space as u64,
+                &id(),
+            );
+            invoke(
+                &create_inst,
+                &[
+                    manager_info.clone(),
+                    token_pool_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
 
             msg!("Serialize the data in token pool account !");
+            check_token_pool(&token_pool)?;
             token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
 
             Ok(())
@@ -88,29 +142,35 @@ pub fn process_instruction(
             let token_pool_info = next_account_info(accounts_iter)?;
             let treasury_info = next_account_info(accounts_iter)?;
             let system_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
 
             msg!("Deserialize token pool account !");
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
 
             // check if token pool is initialized
             if token_pool.stage != TokenPoolStage::Initialized {
                 return Err(TokenPoolError::UninitializedTokenPool.into());
             }
 
+            // an oversubscribed pool takes contributions beyond its target till the subscription ends
+            let oversubscribing = check_subscription(&token_pool)?;
+
             // check if the current balance is already reached the target balance
-            if token_pool.current_balance >= token_pool.target_amount {
+            if !oversubscribing && token_pool.current_balance >= token_pool.target_amount {
                 return Err(TokenPoolError::TargetBalanceReached.into());
             }
 
-            let max_amount = token_pool.target_amount - token_pool.current_balance;
+            let max_amount = token_pool
+                .target_amount
+                .saturating_sub(token_pool.current_balance);
 
-            // Check if member contributing minimum amount to be added in pool and amount left to reach target amount is more than minimum amount
-            if instruction.arg1 < token_pool.minimum_amount
-                && max_amount >= token_pool.minimum_amount
-            {
-                return Err(ProgramError::InsufficientFunds);
-            }
+            // only holders of the gate mint can join a gated pool
+            check_gate(
+                &token_pool,
+                member_info,
+                next_account_info(accounts_iter).ok(),
+            )?;
 
             // if member already exists in the pool then he can only update his share using update share instruction
             if token_pool.pool_member_list.find_member(*member_info.key) {
@@ -123,25 +183,27 @@ pub fn process_instruction(
                 return Err(TokenPoolError::NoMemberSpaceLeft.into()); // to do , need to change to custom error
             }
 
-            // last member should give all the left out amount need to be added to reach the target amount
-            if first_empty_member.unwrap()
-                == (token_pool.pool_member_list.header.max_members - 1)
-                    .try_into()
-                    .unwrap()
-            {
-                if instruction.arg1 < max_amount {
+            // admission policy of the pool keeps the target reachable with the slots left
+            let mut minimum_admission = token_pool.minimum_admission();
+            if oversubscribing {
+                minimum_admission = minimum_admission.max(token_pool.minimum_amount);
+            }
+            if instruction.arg1 < minimum_admission {
+                // last member should give all the left out amount need to be added to reach the target amount
+                if token_pool.pool_member_list.empty_slots() == 1 {
                     return Err(TokenPoolError::InsufficientFundsAsLastMember.into());
-                    // to do , need to change to custom error
                 }
+                return Err(ProgramError::InsufficientFunds);
             }
 
             /* check if the amount depositing is greater than amount left to reach target ,
             if this is the case only deposited amount needed to reach the target amount */
 
             let mut depositable_amount = instruction.arg1;
-            if max_amount < depositable_amount {
+            if !oversubscribing && max_amount < depositable_amount {
                 depositable_amount = max_amount;
             }
+            check_maximum_amount(&token_pool, depositable_amount)?;
 
             msg!("add the pool member !");
             let first_empty_member = first_empty_member.unwrap();
@@ -169,6 +231,8 @@ pub fn process_instruction(
             token_pool.current_balance += depositable_amount;
 
             msg!("Serialize the data in token pool account !");
+            check_token_pool(&token_pool)?;
+            check_treasury(&token_pool, treasury_info)?;
             token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
 
             Ok(())
@@ -180,11 +244,14 @@ pub fn process_instruction(
             let token_pool_info = next_account_info(accounts_iter)?;
             let escrow_state_info = next_account_info(accounts_iter)?;
             let escrow_vault_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let allowed_buyer_info = next_account_info(accounts_iter).ok();
+            let delegate_info = next_account_info(accounts_iter).ok();
             /* Create an escrow for selling share */
 
             msg!("Deserialize token pool account !");
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
 
             // check if token pool is initialized or not
             if token_pool.stage != TokenPoolStage::Initialized {
@@ -195,6 +262,18 @@ pub fn process_instruction(
             if !token_pool.pool_member_list.find_member(*member_info.key) {
                 return Err(TokenPoolError::MemberNotInPool.into());
             }
+            // the refund of an oversubscribed pool is only known once the subscription closes
+            if token_pool.subscription_end != 0 {
+                return Err(TokenPoolError::SubscriptionOpen.into());
+            }
+            check_refund_claimed(&token_pool, member_info.key)?;
+            check_share_authority(
+                &token_pool,
+                member_info.key,
+                member_info,
+                delegate_info,
+                DELEGATE_SELL,
+            )?;
 
             msg!("Deserialize escrow state account !");
             let mut escrow_state = Escrow::unpack_unchecked(*escrow_state_info.data.borrow())?;
@@ -203,6 +282,7 @@ pub fn process_instruction(
                 return Err(TokenPoolError::InvalidEscrowStage.into());
             }
             // vault should depend on seller and token pool keys
+            check_share_escrow_vault(member_info.key, token_pool_info.key, escrow_vault_info.key)?;
             escrow_state.stage = EscrowStage::Initialized;
             escrow_state.amount = instruction.arg1;
             escrow_state.seller = *member_info.key;
@@ -211,6 +291,16 @@ pub fn process_instruction(
                 .pool_member_list
                 .get_member_share(*member_info.key);
             escrow_state.escrow_vault = *escrow_vault_info.key;
+            escrow_state.expires_at = escrow_expiry(instruction.arg4)?;
+            // whoever signed the sale paid for the escrow account
+            escrow_state.rent_payer = match delegate_info {
+                Some(delegate_info) if !member_info.is_signer => *delegate_info.key,
+                _ => *member_info.key,
+            };
+            // a private sale can only be bought by the buyer the seller arranged it with
+            if let Some(allowed_buyer_info) = allowed_buyer_info {
+                escrow_state.allowed_buyer = *allowed_buyer_info.key;
+            }
 
             /* give authority of the share to vault and init escrow*/
             token_pool.pool_member_list.init_escrow(
@@ -218,6 +308,7 @@ pub fn process_instruction(
                 *escrow_state_info.key,
                 *escrow_vault_info.key,
             );
+            check_token_pool(&token_pool)?;
             token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
             msg!("serialize escrow strate account after initializing !");
             escrow_state.serialize(&mut &mut escrow_state_info.data.borrow_mut()[..])?;
@@ -233,10 +324,12 @@ pub fn process_instruction(
             let escrow_vault_info = next_account_info(accounts_iter)?;
             let seller_info = next_account_info(accounts_iter)?;
             let system_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let rent_payer_info = next_account_info(accounts_iter)?;
             /* take ownership of share from the escrow */
             msg!("Deserialize token pool account !");
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
 
             // check if token pool is initialized or not
             if token_pool.stage != TokenPoolStage::Initialized {
@@ -244,34 +337,67 @@ pub fn process_instruction(
             }
 
             let escrow_state = Escrow::unpack_unchecked(&mut escrow_state_info.data.borrow())?;
+            if escrow_state.is_expired(Clock::get()?.unix_timestamp) {
+                return Err(TokenPoolError::EscrowExpired.into());
+            }
             // check if buying amount is correct
             let buying_amount = instruction.arg1;
             if buying_amount != escrow_state.amount {
                 return Err(TokenPoolError::WrongAmountData.into());
             }
             // check if escrow account has same escrow vault
-            if escrow_state.escrow_vault != *escrow_vault_info.key {
+            if escrow_state.escrow_vault != *escrow_vault_info.key
+                || escrow_state.rent_payer != *rent_payer_info.key
+            {
                 return Err(TokenPoolError::InvalidData.into());
             }
-            // check if the escrow vault is member of pool list
-            if !token_pool
-                .pool_member_list
-                .find_member(*escrow_vault_info.key)
+            check_share_escrow_vault(
+                &escrow_state.seller,
+                token_pool_info.key,
+                escrow_vault_info.key,
+            )?;
+            if escrow_state.allowed_buyer != Pubkey::default()
+                && escrow_state.allowed_buyer != *buyer_info.key
             {
-                return Err(TokenPoolError::InvalidData.into());
+                return Err(TokenPoolError::BuyerNotAllowed.into());
+            }
+            // check if the escrow vault is member of pool list, a refund due to the share is the seller's
+            // and is claimed after cancelling the sale, it never moves to the buyer
+            let escrowed_index = token_pool
+                .pool_member_list
+                .get_member_index(*escrow_vault_info.key)
+                .ok_or(TokenPoolError::InvalidData)?;
+            if token_pool.pool_member_list.members[escrowed_index].refund_due > 0 {
+                return Err(TokenPoolError::RefundNotClaimed.into());
             }
 
             // check if buyer is part of the token pool then increase his share instead of adding them as member again
             let is_buyer_member = token_pool.pool_member_list.find_member(*buyer_info.key);
+            let members = &token_pool.pool_member_list;
+            check_maximum_amount(
+                &token_pool,
+                members.amount_deposited_by(*buyer_info.key)
+                    + members.amount_deposited_by(*escrow_vault_info.key),
+            )?;
+            if !is_buyer_member {
+                check_gate(
+                    &token_pool,
+                    buyer_info,
+                    next_account_info(accounts_iter).ok(),
+                )?;
+            }
             if is_buyer_member {
-                // upgrade buyers share
+                // upgrade buyers share, the amount deposited for the share moves with it
                 let increased_share = token_pool
                     .pool_member_list
                     .get_member_share(*escrow_vault_info.key);
+                let increased_amount = token_pool
+                    .pool_member_list
+                    .amount_deposited_by(*escrow_vault_info.key);
                 token_pool.pool_member_list.increase_member_share(
                     increased_share,
                     *buyer_info.key,
-                    buying_amount,
+                    increased_amount,
                 );
                 // remove the escrow vault from the members list
                 token_pool
@@ -284,6 +410,10 @@ pub fn process_instruction(
                     .update_key(*escrow_vault_info.key, *buyer_info.key);
                 // removing escrow account from the members share info
                 token_pool.pool_member_list.remove_escrow(*buyer_info.key);
+                // delegate of the seller does not act for the buyer
+                token_pool
+                    .pool_member_list
+                    .revoke_delegate(*buyer_info.key)?;
             }
 
             msg!("transfer lamports to seller");
@@ -296,16 +426,10 @@ pub fn process_instruction(
                     system_program_info.clone(),
                 ],
             )?;
-            msg!("close escrow account and tranfer lamports to seller");
-            let dest_starting_lamports = seller_info.lamports();
-            **seller_info.lamports.borrow_mut() = dest_starting_lamports
-                .checked_add(escrow_state_info.lamports())
-                .unwrap();
-            **escrow_state_info.lamports.borrow_mut() = 0;
-
-            let mut source_data = escrow_state_info.data.borrow_mut();
-            source_data.fill(0);
+            msg!("close escrow account and refund rent to rent payer");
+            close_account(escrow_state_info, rent_payer_info)?;
             msg!("serialize the token pool account");
+            check_token_pool(&token_pool)?;
             token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
 
             Ok(())
@@ -317,10 +441,11 @@ pub fn process_instruction(
             let token_pool_info = next_account_info(accounts_iter)?;
             let treasury_info = next_account_info(accounts_iter)?;
             let system_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
             let upgrading_amount = instruction.arg1;
             msg!("Deserialize token pool account !");
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
 
             // check if token pool is initialized or not
             if token_pool.stage != TokenPoolStage::Initialized {
@@ -328,7 +453,9 @@ pub fn process_instruction(
             }
 
             // check if the current balance is already reached the target balance
-            if token_pool.current_balance >= token_pool.target_amount {
+            if !check_subscription(&token_pool)?
+                && token_pool.current_balance >= token_pool.target_amount
+            {
                 return Err(TokenPoolError::TargetBalanceReached.into());
             }
 
@@ -342,6 +469,7 @@ pub fn process_instruction(
                 .unwrap();
             let total_amount = upgrading_amount
                 + token_pool.pool_member_list.members[member_index].amount_deposited;
+            check_maximum_amount(&token_pool, total_amount)?;
             let new_share = token_pool.find_share(total_amount).unwrap();
             token_pool.pool_member_list.update_member_share(
                 new_share,
@@ -364,6 +492,8 @@ pub fn process_instruction(
 
             // serailize the data
             msg!("serialize the token pool account");
+            check_token_pool(&token_pool)?;
+            check_treasury(&token_pool, treasury_info)?;
             token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
 
             Ok(())
@@ -377,18 +507,43 @@ pub fn process_instruction(
             let vault_info = next_account_info(accounts_iter)?;
             let nft_info = next_account_info(accounts_iter)?;
             let token_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
             let selling_amount = instruction.arg1;
+            let floor_price = instruction.arg2;
+            let end_time = instruction.arg5 as UnixTimestamp;
+
+            check_platform_not_paused(platform_config_info)?;
 
             msg!("Deserialize escrow pool account !");
             let mut escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Uninitialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
 
             escrow.amount = selling_amount;
             escrow.seller = *seller_info.key;
+            escrow.rent_payer = *seller_info.key;
             escrow.share = 100.0;
             escrow.escrow_vault = *vault_info.key;
             escrow.stage = EscrowStage::Initialized;
             escrow.nft = *nft_info.key;
             escrow.nft_mint = *nft_mint_info.key;
+            escrow.expires_at = escrow_expiry(instruction.arg4)?;
+
+            // an end time lists the nft for a dutch auction, price decays from amount to floor price till then
+            if end_time != 0 {
+                let now = Clock::get()?.unix_timestamp;
+                if end_time <= now {
+                    return Err(TokenPoolError::InvalidAuctionEndTime.into());
+                }
+                if floor_price > selling_amount {
+                    return Err(TokenPoolError::WrongAmountData.into());
+                }
+                escrow.listing = ListingKind::DutchAuction;
+                escrow.start_time = now;
+                escrow.end_time = end_time;
+                escrow.floor_price = floor_price;
+            }
 
             let transfer_authority = set_authority(
                 token_program_info.key,
@@ -462,54 +617,102 @@ pub fn process_instruction(
             let escrow_vault_info = next_account_info(accounts_iter)?;
             let manager_info = next_account_info(accounts_iter)?;
             let token_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
 
-            let buying_amount = instruction.arg1;
+            let mut buying_amount = instruction.arg1;
 
             msg!("Deserialize token pool account !");
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
 
-            // check if token pool is initialized
-            if token_pool.stage != TokenPoolStage::Initialized {
+            // check if token pool is initialized, a basket pool keeps buying after its first nft
+            if !token_pool.can_buy() {
                 return Err(TokenPoolError::InvalidData.into());
             }
+            if token_pool.committed_bid != 0 {
+                return Err(TokenPoolError::BidCommitted.into());
+            }
 
             msg!("Deserialize escrow pool account !");
             let mut escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+            if escrow.is_expired(Clock::get()?.unix_timestamp) {
+                return Err(TokenPoolError::EscrowExpired.into());
+            }
 
             //check if buyer is part of token pool or not
             if !token_pool.pool_member_list.find_member(*buyer_info.key) {
                 return Err(TokenPoolError::MemberNotInPool.into());
             }
+            // price goes to the seller of the listing and the fee to the manager of the pool
+            if escrow.seller != *seller_info.key || token_pool.manager != *manager_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
 
-            // check if token pool has reached the target amount
-            // check if token pool's treasury have enough funds
-            msg!("check if token pool's treasury have enough funds !");
-            if buying_amount != escrow.amount
-                && token_pool.current_balance != token_pool.target_amount
-            {
-                return Err(TokenPoolError::WrongAmountData.into());
+            match escrow.listing {
+                ListingKind::FixedPrice => {
+                    // a fixed price listing is bought at its price, whatever the pool has raised
+                    msg!("check the amount is the price of the listing !");
+                    if buying_amount != escrow.amount {
+                        return Err(TokenPoolError::WrongAmountData.into());
+                    }
+                }
+                ListingKind::DutchAuction => {
+                    // amount sent is the most buyer agrees to pay, the current price is charged
+                    msg!("calculate the current price of dutch auction !");
+                    let price = escrow
+                        .current_price(Clock::get()?.unix_timestamp)
+                        .ok_or(TokenPoolError::InvalidData)?;
+                    if buying_amount < price {
+                        return Err(TokenPoolError::WrongAmountData.into());
+                    }
+                    if token_pool.current_balance < price {
+                        return Err(ProgramError::InsufficientFunds);
+                    }
+                    buying_amount = price;
+                }
+                // auctions are bought through bids and listed tokens through ExecuteTokenBuy
+                ListingKind::EnglishAuction | ListingKind::Tokens => {
+                    return Err(TokenPoolError::InvalidListingKind.into());
+                }
             }
 
             msg!("check if nft is the one that was part of the token pool !");
             // check if nft is the one that was part of the token pool
-            if token_pool.target_token != *nft_mint_info.key {
-                return Err(TokenPoolError::InvalidData.into());
+            check_target_mint(
+                &token_pool,
+                next_account_info(accounts_iter).ok(),
+                nft_mint_info.key,
+            )?;
+            if token_pool.target_kind == PoolTargetKind::Basket {
+                // a basket pool pays the price from what is left in its treasury
+                if token_pool.current_balance < buying_amount {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+                add_to_inventory(
+                    &token_pool,
+                    next_account_info(accounts_iter)?,
+                    InventoryItem {
+                        mint: *nft_mint_info.key,
+                        nft: *nft_info.key,
+                        price: buying_amount,
+                    },
+                )?;
+            } else {
+                token_pool.target_token = *nft_mint_info.key;
             }
 
             msg!("transfer the funds to seller !");
-            // transfer the funds to seller
-            let dest_starting_lamports = treasury_info.lamports();
-            **seller_info.lamports.borrow_mut() = seller_info
-                .lamports()
-                .checked_add(buying_amount - token_pool.minimum_exemption_amount)
-                .unwrap();
-            **treasury_info.lamports.borrow_mut() =
-                dest_starting_lamports.checked_sub(buying_amount).unwrap();
-            **manager_info.lamports.borrow_mut() = manager_info
-                .lamports()
-                .checked_add(token_pool.minimum_exemption_amount)
-                .unwrap();
+            // a dutch auction may have come down below the minimum exemption amount of the pool
+            pay_from_treasury(
+                treasury_info,
+                seller_info,
+                manager_info,
+                buying_amount,
+                token_pool.minimum_exemption_amount,
+            )?;
 
             // transfer nft's authority
             let state_seeds = vec![b"listnft".as_ref(), escrow.nft.as_ref()];
@@ -574,33 +777,45 @@ pub fn process_instruction(
                 &[&[&b"listnft"[..], escrow.nft.as_ref(), &[_bump]]],
             )?;
 
-            msg!("close escrow !");
-            // close escrow
-            let mut source_data = escrow_state_info.data.borrow_mut();
-            source_data.fill(0);
+            // escrow is sold, CloseEscrow gives its rent back to whoever paid it
+            escrow.stage = EscrowStage::NftSold;
+            escrow.serialize(&mut &mut escrow_state_info.data.borrow_mut()[..])?;
 
+            // pool may buy before reaching its target, members own the nft in proportion to their shares.
+            // a basket pool keeps its balance for the next nft, any other pool refunds what is left
+            token_pool.settle_shares()?;
             token_pool.stage = TokenPoolStage::NFTOwned;
-            token_pool.current_balance = 0;
+            token_pool.current_balance = token_pool
+                .current_balance
+                .checked_sub(buying_amount)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            if token_pool.target_kind != PoolTargetKind::Basket {
+                token_pool.refund_leftover();
+            }
+            check_token_pool(&token_pool)?;
+            check_treasury(&token_pool, treasury_info)?;
             token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
 
             Ok(())
         }
         7 => {
-            msg!("Set manager instruction starts !");
+            msg!("Propose manager instruction starts !");
             let accounts_iter = &mut accounts.iter();
             let manger_info = next_account_info(accounts_iter)?;
             let token_pool_info = next_account_info(accounts_iter)?;
             let new_manager_info = next_account_info(accounts_iter)?;
 
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
 
-            if token_pool.manager != *manger_info.key {
-                return Err(TokenPoolError::WrongManager.into());
+            check_manager(&token_pool, manger_info, accounts)?;
+            if *new_manager_info.key == Pubkey::default() {
+                return Err(TokenPoolError::InvalidData.into());
             }
 
-            token_pool.manager = *new_manager_info.key;
+            // the new manager takes over once they accept
+            token_pool.pending_manager = *new_manager_info.key;
 
+            check_token_pool(&token_pool)?;
             token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
 
             Ok(())
@@ -615,8 +830,7 @@ pub fn process_instruction(
             let token_pool_vault_info = next_account_info(accounts_iter)?;
             let token_program_info = next_account_info(accounts_iter)?;
 
-            let mut token_pool =
-                try_from_slice_unchecked::<TokenPool>(&token_pool_info.data.borrow())?;
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
 
             // check if member is in token pool
             if !token_pool.pool_member_list.find_member(*member_info.key) {
@@ -635,6 +849,28 @@ pub fn process_instruction(
                 return Err(TokenPoolError::MemberDontOwnFullShare.into());
             }
 
+            // nfts of a basket pool are claimed one by one, pool stays open till its inventory is empty
+            let mut basket_items_left = 0;
+            if token_pool.target_kind == PoolTargetKind::Basket {
+                let inventory_info = next_account_info(accounts_iter)?;
+                basket_items_left = remove_from_inventory(
+                    &token_pool,
+                    inventory_info,
+                    nft_mint_info.key,
+                    nft_info.key,
+                )?;
+                if basket_items_left == 0 {
+                    let rent_payer_info = next_account_info(accounts_iter)?;
+                    let inventory =
+                        try_from_slice_unchecked::<Inventory>(&inventory_info.data.borrow())?;
+                    if inventory.rent_payer != *rent_payer_info.key {
+                        return Err(TokenPoolError::InvalidData.into());
+                    }
+                    msg!("close the empty inventory and refund rent to rent payer");
+                    close_account(inventory_info, rent_payer_info)?;
+                }
+            }
+
             // transfer nft's authority
             let state_seeds = vec![b"pool".as_ref(), token_pool_info.key.as_ref()];
             let (_vault_pda, _bump) = Pubkey::find_program_address(state_seeds.as_slice(), &id());
@@ -698,18 +934,2846 @@ pub fn process_instruction(
                 &[&[&b"pool"[..], token_pool_info.key.as_ref(), &[_bump]]],
             )?;
 
-            // close token pool account and transfer the lamports in the members account
-            let starting_lamports = token_pool_info.lamports();
-            **member_info.lamports.borrow_mut() = member_info
-                .lamports()
-                .checked_add(starting_lamports)
-                .unwrap();
-            **token_pool_info.lamports.borrow_mut() = 0;
-            let mut source_data = token_pool_info.data.borrow_mut();
-            source_data.fill(0);
+            if basket_items_left > 0 {
+                return Ok(());
+            }
+
+            // member took the whole pool, ClosePool gives the rent of the pool back to whoever paid it
+            token_pool.pool_member_list.remove_member(*member_info.key);
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
 
             Ok(())
         }
-        _ => return Err(ProgramError::InvalidArgument),
-    }
+        9 => {
+            msg!("Cancel share sale instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let seller_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let escrow_vault_info = next_account_info(accounts_iter)?;
+            let rent_payer_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+
+            let escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+
+            // check if the escrow belongs to seller and the share is held by its escrow vault
+            if escrow.seller != *seller_info.key
+                || escrow.escrow_vault != *escrow_vault_info.key
+                || escrow.rent_payer != *rent_payer_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_share_escrow_vault(seller_info.key, token_pool_info.key, escrow_vault_info.key)?;
+            if !token_pool
+                .pool_member_list
+                .find_member(*escrow_vault_info.key)
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            // escrowed share is held by the escrow vault with the delegate of the seller
+            check_share_authority(
+                &token_pool,
+                escrow_vault_info.key,
+                seller_info,
+                next_account_info(accounts_iter).ok(),
+                DELEGATE_SELL,
+            )?;
+
+            give_back_escrowed_share(&mut token_pool, seller_info.key, escrow_vault_info.key);
+
+            msg!("close escrow account and refund rent to rent payer");
+            close_account(escrow_state_info, rent_payer_info)?;
+
+            msg!("serialize the token pool account");
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
+
+            Ok(())
+        }
+        10 => {
+            msg!("Cancel nft listing instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let seller_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let nft_mint_info = next_account_info(accounts_iter)?;
+            let escrow_vault_info = next_account_info(accounts_iter)?;
+            let nft_info = next_account_info(accounts_iter)?;
+            let token_program_info = next_account_info(accounts_iter)?;
+
+            if !seller_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            msg!("Deserialize escrow pool account !");
+            let escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+
+            // bidders' lamports are locked in escrow till the auction settles
+            if escrow.highest_bid > 0 {
+                return Err(TokenPoolError::AuctionHasBids.into());
+            }
+
+            // check if the listing belongs to seller and is for the nft passed
+            if escrow.seller != *seller_info.key
+                || escrow.escrow_vault != *escrow_vault_info.key
+                || escrow.nft != *nft_info.key
+                || escrow.nft_mint != *nft_mint_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("give nft's authorities back to seller !");
+            let (_vault_pda, bump) =
+                Pubkey::find_program_address(&[b"listnft", escrow.nft.as_ref()], &id());
+            if escrow.listing == ListingKind::Tokens {
+                transfer_account_owner(
+                    token_program_info,
+                    nft_info,
+                    escrow_vault_info,
+                    seller_info.key,
+                    &[&[b"listnft", escrow.nft.as_ref(), &[bump]]],
+                )?;
+            } else {
+                transfer_nft_authorities(
+                    token_program_info,
+                    nft_info,
+                    nft_mint_info,
+                    escrow_vault_info,
+                    seller_info.key,
+                    &[&[b"listnft", escrow.nft.as_ref(), &[bump]]],
+                )?;
+            }
+
+            msg!("close escrow account and tranfer lamports to seller");
+            close_account(escrow_state_info, seller_info)?;
+
+            Ok(())
+        }
+        11 => {
+            msg!("List nft for auction instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let seller_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let nft_mint_info = next_account_info(accounts_iter)?;
+            let vault_info = next_account_info(accounts_iter)?;
+            let nft_info = next_account_info(accounts_iter)?;
+            let token_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let reserve_price = instruction.arg1;
+            let min_bid_increment = instruction.arg2;
+            let end_time = instruction.arg5 as UnixTimestamp;
+
+            check_platform_not_paused(platform_config_info)?;
+            if end_time <= Clock::get()?.unix_timestamp {
+                return Err(TokenPoolError::InvalidAuctionEndTime.into());
+            }
+
+            // every new bid has to beat the highest one
+            if min_bid_increment == 0 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // vault should be derived from nft account to sign when the auction settles
+            let (vault_pda, _bump) =
+                Pubkey::find_program_address(&[b"listnft", nft_info.key.as_ref()], &id());
+            if vault_pda != *vault_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize escrow pool account !");
+            let mut escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Uninitialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+
+            escrow.amount = reserve_price;
+            escrow.seller = *seller_info.key;
+            escrow.rent_payer = *seller_info.key;
+            escrow.share = 100.0;
+            escrow.escrow_vault = *vault_info.key;
+            escrow.stage = EscrowStage::Initialized;
+            escrow.nft = *nft_info.key;
+            escrow.nft_mint = *nft_mint_info.key;
+            escrow.listing = ListingKind::EnglishAuction;
+            escrow.end_time = end_time;
+            escrow.min_bid_increment = min_bid_increment;
+
+            msg!("give nft's authorities to vault !");
+            transfer_nft_authorities(
+                token_program_info,
+                nft_info,
+                nft_mint_info,
+                seller_info,
+                vault_info.key,
+                &[],
+            )?;
+
+            escrow.serialize(&mut &mut escrow_state_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        12 => {
+            msg!("Place bid instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let bidder_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let previous_bidder_info = next_account_info(accounts_iter)?;
+            let system_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter).ok();
+            let bid = instruction.arg1;
+
+            if !bidder_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            check_platform_not_paused(platform_config_info)?;
+
+            msg!("Deserialize escrow state account !");
+            let mut escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.listing != ListingKind::EnglishAuction {
+                return Err(TokenPoolError::InvalidListingKind.into());
+            }
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+            if Clock::get()?.unix_timestamp >= escrow.end_time {
+                return Err(TokenPoolError::AuctionEnded.into());
+            }
+            if bid < escrow.minimum_bid().ok_or(TokenPoolError::InvalidData)? {
+                return Err(TokenPoolError::BidTooLow.into());
+            }
+
+            let previous_bid = escrow.highest_bid;
+            let previous_bidder = escrow.highest_bidder;
+            let previous_bidder_pool = escrow.bidder_pool;
+
+            if let Some(token_pool_info) = token_pool_info {
+                if *token_pool_info.owner != id() {
+                    return Err(ProgramError::IllegalOwner);
+                }
+                msg!("Deserialize token pool account !");
+                let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+                if token_pool.paused {
+                    return Err(TokenPoolError::Paused.into());
+                }
+
+                if !token_pool.can_buy() {
+                    return Err(TokenPoolError::UninitializedTokenPool.into());
+                }
+                // only members can bid with the pool's treasury and only for the nft of the pool
+                if !token_pool.pool_member_list.find_member(*bidder_info.key) {
+                    return Err(TokenPoolError::MemberNotInPool.into());
+                }
+                check_target_mint(
+                    &token_pool,
+                    next_account_info(accounts_iter).ok(),
+                    &escrow.nft_mint,
+                )?;
+                // a pool bids on one auction at a time, raising its own bid replaces the committed one
+                if token_pool.committed_bid != 0 && token_pool.bid_escrow != *escrow_state_info.key
+                {
+                    return Err(TokenPoolError::BidCommitted.into());
+                }
+                if bid > token_pool.current_balance {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+
+                msg!("commit the bid out of the current balance of token pool !");
+                token_pool.committed_bid = bid;
+                token_pool.bid_escrow = *escrow_state_info.key;
+                check_token_pool(&token_pool)?;
+                token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+                escrow.highest_bidder = token_pool.vault;
+                escrow.bidder_pool = *token_pool_info.key;
+            } else {
+                msg!("move the bid to escrow !");
+                let transfer_inst = transfer(bidder_info.key, escrow_state_info.key, bid);
+                invoke(
+                    &transfer_inst,
+                    &[
+                        bidder_info.clone(),
+                        escrow_state_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                )?;
+
+                escrow.highest_bidder = *bidder_info.key;
+                escrow.bidder_pool = Pubkey::default();
+            }
+            escrow.highest_bid = bid;
+
+            // an outbid token pool gets its committed bid back, bids of token pools never left their treasury
+            if previous_bid > 0
+                && previous_bidder_pool != Pubkey::default()
+                && token_pool_info.map(|info| *info.key) != Some(previous_bidder_pool)
+            {
+                if *previous_bidder_info.key != previous_bidder_pool {
+                    return Err(TokenPoolError::InvalidData.into());
+                }
+                msg!("release the bid committed by the previous token pool !");
+                release_committed_bid(previous_bidder_info, escrow_state_info.key)?;
+            }
+
+            // refund the previous highest bidder
+            if previous_bid > 0 && previous_bidder_pool == Pubkey::default() {
+                if *previous_bidder_info.key != previous_bidder {
+                    return Err(TokenPoolError::InvalidData.into());
+                }
+                msg!("refund the previous highest bidder !");
+                let escrow_starting_lamports = escrow_state_info.lamports();
+                **escrow_state_info.lamports.borrow_mut() = escrow_starting_lamports
+                    .checked_sub(previous_bid)
+                    .ok_or(ProgramError::InsufficientFunds)?;
+                **previous_bidder_info.lamports.borrow_mut() = previous_bidder_info
+                    .lamports()
+                    .checked_add(previous_bid)
+                    .ok_or(ProgramError::InvalidArgument)?;
+            }
+
+            escrow.serialize(&mut &mut escrow_state_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        13 => {
+            msg!("Settle auction instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let seller_info = next_account_info(accounts_iter)?;
+            let nft_mint_info = next_account_info(accounts_iter)?;
+            let escrow_vault_info = next_account_info(accounts_iter)?;
+            let nft_info = next_account_info(accounts_iter)?;
+            let token_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+
+            check_platform_not_paused(platform_config_info)?;
+
+            msg!("Deserialize escrow state account !");
+            let escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.listing != ListingKind::EnglishAuction {
+                return Err(TokenPoolError::InvalidListingKind.into());
+            }
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+            if Clock::get()?.unix_timestamp < escrow.end_time {
+                return Err(TokenPoolError::AuctionNotEnded.into());
+            }
+            if escrow.seller != *seller_info.key
+                || escrow.escrow_vault != *escrow_vault_info.key
+                || escrow.nft != *nft_info.key
+                || escrow.nft_mint != *nft_mint_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // nft goes back to seller when nobody has bid
+            let mut new_authority = if escrow.highest_bid == 0 {
+                escrow.seller
+            } else {
+                escrow.highest_bidder
+            };
+
+            if escrow.highest_bid > 0 && escrow.bidder_pool != Pubkey::default() {
+                let token_pool_info = next_account_info(accounts_iter)?;
+                let treasury_info = next_account_info(accounts_iter)?;
+                let manager_info = next_account_info(accounts_iter)?;
+
+                if *token_pool_info.key != escrow.bidder_pool {
+                    return Err(TokenPoolError::InvalidData.into());
+                }
+                if *token_pool_info.owner != id() {
+                    return Err(ProgramError::IllegalOwner);
+                }
+
+                msg!("Deserialize token pool account !");
+                let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+                if token_pool.treasurey != *treasury_info.key
+                    || token_pool.manager != *manager_info.key
+                {
+                    return Err(TokenPoolError::InvalidData.into());
+                }
+                let inventory_info = if token_pool.target_kind == PoolTargetKind::Basket {
+                    Some(next_account_info(accounts_iter)?)
+                } else {
+                    None
+                };
+                let inventory_full = match inventory_info {
+                    Some(inventory_info) => !has_inventory_space(&token_pool, inventory_info)?,
+                    None => false,
+                };
+                // the pool has to still be buying the nft it bid for when the auction settles
+                let allowed_mints_info = if token_pool.target_kind == PoolTargetKind::SingleMint {
+                    None
+                } else {
+                    Some(next_account_info(accounts_iter)?)
+                };
+                let target_allowed = token_pool.can_buy()
+                    && match check_target_mint(&token_pool, allowed_mints_info, &escrow.nft_mint) {
+                        Ok(()) => true,
+                        Err(error) if error == TokenPoolError::MintNotAllowed.into() => false,
+                        Err(error) => return Err(error),
+                    };
+
+                // the bid is settled either way, so the pool gets its committed bid back
+                if token_pool.bid_escrow == *escrow_state_info.key {
+                    token_pool.committed_bid = 0;
+                    token_pool.bid_escrow = Pubkey::default();
+                }
+
+                // a pool which can no longer pay does not keep the nft locked, it goes back to seller.
+                // a paused pool does not buy either, its committed bid is released all the same
+                if token_pool.paused
+                    || !target_allowed
+                    || token_pool.current_balance < escrow.highest_bid
+                    || inventory_full
+                {
+                    msg!("token pool can not pay its bid, nft goes back to seller !");
+                    new_authority = escrow.seller;
+                } else {
+                    msg!("transfer the winning bid from treasury to seller !");
+                    pay_from_treasury(
+                        treasury_info,
+                        seller_info,
+                        manager_info,
+                        escrow.highest_bid,
+                        token_pool.minimum_exemption_amount,
+                    )?;
+
+                    if let Some(inventory_info) = inventory_info {
+                        add_to_inventory(
+                            &token_pool,
+                            inventory_info,
+                            InventoryItem {
+                                mint: escrow.nft_mint,
+                                nft: escrow.nft,
+                                price: escrow.highest_bid,
+                            },
+                        )?;
+                        token_pool.current_balance -= escrow.highest_bid;
+                    } else {
+                        token_pool.target_token = escrow.nft_mint;
+                        token_pool.current_balance -= escrow.highest_bid;
+                        token_pool.refund_leftover();
+                    }
+                    token_pool.settle_shares()?;
+                    token_pool.stage = TokenPoolStage::NFTOwned;
+                }
+                check_token_pool(&token_pool)?;
+                check_treasury(&token_pool, treasury_info)?;
+                token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+            }
+
+            msg!("transfer nft's authorities !");
+            let (_vault_pda, bump) =
+                Pubkey::find_program_address(&[b"listnft", escrow.nft.as_ref()], &id());
+            transfer_nft_authorities(
+                token_program_info,
+                nft_info,
+                nft_mint_info,
+                escrow_vault_info,
+                &new_authority,
+                &[&[b"listnft", escrow.nft.as_ref(), &[bump]]],
+            )?;
+
+            msg!("close escrow, bid of a wallet is in its lamports and goes to seller");
+            close_account(escrow_state_info, seller_info)?;
+
+            Ok(())
+        }
+        14 => {
+            msg!("Place share bid instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let bidder_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let share_bid_info = next_account_info(accounts_iter)?;
+            let system_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let price_per_unit = instruction.arg1;
+            let quantity = instruction.arg2;
+
+            if !bidder_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if price_per_unit == 0 || quantity == 0 || quantity > TOTAL_SHARE_UNITS {
+                return Err(TokenPoolError::WrongAmountData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
+            if token_pool.stage == TokenPoolStage::Uninitialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            // shares of a bought out pool are only paid out
+            if token_pool.stage == TokenPoolStage::BoughtOut {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            // filling the bid makes bidder a member
+            if !token_pool.pool_member_list.find_member(*bidder_info.key) {
+                check_gate(
+                    &token_pool,
+                    bidder_info,
+                    next_account_info(accounts_iter).ok(),
+                )?;
+            }
+
+            // bid account should be a pda of the token pool and bidder, a bidder has one open bid per pool
+            let (share_bid_key, bump) = Pubkey::find_program_address(
+                &[
+                    b"bid",
+                    token_pool_info.key.as_ref(),
+                    bidder_info.key.as_ref(),
+                ],
+                &id(),
+            );
+            if share_bid_key != *share_bid_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if share_bid_info.lamports() > 0 {
+                return Err(TokenPoolError::InvalidShareBid.into());
+            }
+
+            let bid_amount = price_per_unit
+                .checked_mul(quantity)
+                .ok_or(TokenPoolError::WrongAmountData)?;
+            let rent = Rent::get()?.minimum_balance(ShareBid::LEN);
+
+            msg!("create share bid account holding the bid !");
+            let create_inst = create_account(
+                bidder_info.key,
+                share_bid_info.key,
+                rent.checked_add(bid_amount)
+                    .ok_or(TokenPoolError::WrongAmountData)?,
+                ShareBid::LEN as u64,
+                &id(),
+            );
+            invoke_signed(
+                &create_inst,
+                &[
+                    bidder_info.clone(),
+                    share_bid_info.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[
+                    b"bid",
+                    token_pool_info.key.as_ref(),
+                    bidder_info.key.as_ref(),
+                    &[bump],
+                ]],
+            )?;
+
+            let share_bid = ShareBid {
+                stage: ShareBidStage::Open,
+                token_pool: *token_pool_info.key,
+                bidder: *bidder_info.key,
+                price_per_unit,
+                quantity,
+            };
+            share_bid.serialize(&mut &mut share_bid_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        15 => {
+            msg!("Cancel share bid instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let bidder_info = next_account_info(accounts_iter)?;
+            let share_bid_info = next_account_info(accounts_iter)?;
+
+            if !bidder_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            let share_bid = ShareBid::unpack_unchecked(&share_bid_info.data.borrow())?;
+            if share_bid.stage != ShareBidStage::Open {
+                return Err(TokenPoolError::InvalidShareBid.into());
+            }
+            if share_bid.bidder != *bidder_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("close share bid account and refund lamports to bidder");
+            close_account(share_bid_info, bidder_info)?;
+
+            Ok(())
+        }
+        16 => {
+            msg!("Fill bid instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let seller_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let share_bid_info = next_account_info(accounts_iter)?;
+            let bidder_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let units = instruction.arg1;
+            let min_price_per_unit = instruction.arg2;
+
+            if !seller_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
+            if token_pool.stage == TokenPoolStage::Uninitialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            // shares of a bought out pool are only paid out
+            if token_pool.stage == TokenPoolStage::BoughtOut {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_no_active_buyout(&token_pool)?;
+
+            let mut share_bid = ShareBid::unpack_unchecked(&share_bid_info.data.borrow())?;
+            if share_bid.stage != ShareBidStage::Open
+                || share_bid.token_pool != *token_pool_info.key
+            {
+                return Err(TokenPoolError::InvalidShareBid.into());
+            }
+            if share_bid.bidder != *bidder_info.key || share_bid.bidder == *seller_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if share_bid.price_per_unit < min_price_per_unit {
+                return Err(TokenPoolError::BidPriceBelowMinimum.into());
+            }
+
+            // only a share which is held and not escrowed for a sale can fill a bid
+            let seller_index = token_pool
+                .pool_member_list
+                .get_member_index(*seller_info.key)
+                .ok_or(TokenPoolError::MemberNotInPool)?;
+            let seller_share = token_pool.pool_member_list.members[seller_index];
+            if seller_share.share_stage != ShareStage::Hold {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_refund_claimed(&token_pool, seller_info.key)?;
+            let seller_units = share_to_units(seller_share.share);
+            if units == 0 || units > share_bid.quantity || units > seller_units {
+                return Err(TokenPoolError::InsufficientShare.into());
+            }
+
+            msg!("move the share units to bidder !");
+            let share = if units == seller_units {
+                seller_share.share
+            } else {
+                units_to_share(units)
+            };
+            token_pool.pool_member_list.transfer_share(
+                *seller_info.key,
+                *bidder_info.key,
+                share,
+            )?;
+            check_maximum_amount(
+                &token_pool,
+                token_pool
+                    .pool_member_list
+                    .amount_deposited_by(*bidder_info.key),
+            )?;
+
+            msg!("pay the seller from share bid !");
+            let payment = units
+                .checked_mul(share_bid.price_per_unit)
+                .ok_or(TokenPoolError::WrongAmountData)?;
+            **share_bid_info.lamports.borrow_mut() = share_bid_info
+                .lamports()
+                .checked_sub(payment)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            **seller_info.lamports.borrow_mut() = seller_info
+                .lamports()
+                .checked_add(payment)
+                .ok_or(ProgramError::InvalidArgument)?;
+
+            share_bid.quantity -= units;
+            if share_bid.quantity == 0 {
+                msg!("bid is filled, close share bid account and refund rent to bidder");
+                close_account(share_bid_info, bidder_info)?;
+            } else {
+                share_bid.serialize(&mut &mut share_bid_info.data.borrow_mut()[..])?;
+            }
+
+            msg!("serialize the token pool account");
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
+
+            Ok(())
+        }
+        17 => {
+            msg!("Add allowed mints instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let allowed_mints_info = next_account_info(accounts_iter)?;
+
+            if *allowed_mints_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if !Rent::get()?.is_exempt(allowed_mints_info.lamports(), allowed_mints_info.data_len())
+            {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+            // members join knowing the mints the pool buys, they can't change once they contributed
+            if token_pool.current_balance != 0 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize allowed mints account !");
+            let mut allowed_mints =
+                try_from_slice_unchecked::<AllowedMints>(&allowed_mints_info.data.borrow())?;
+            let capacity = AllowedMints::capacity(allowed_mints_info.data_len());
+            match token_pool.target_kind {
+                PoolTargetKind::SingleMint => {
+                    // a new list turns the token pool into a collection pool, still allowed to buy its mint
+                    if allowed_mints.token_pool != Pubkey::default() {
+                        return Err(TokenPoolError::InvalidData.into());
+                    }
+                    if capacity == 0 {
+                        return Err(TokenPoolError::AllowedMintsFull.into());
+                    }
+                    allowed_mints.token_pool = *token_pool_info.key;
+                    allowed_mints.rent_payer = *manager_info.key;
+                    allowed_mints.mints.push(token_pool.target_token);
+                    token_pool.target_kind = PoolTargetKind::Collection;
+                    token_pool.target_token = *allowed_mints_info.key;
+                }
+                PoolTargetKind::Fungible => {
+                    return Err(TokenPoolError::WrongTargetKind.into());
+                }
+                PoolTargetKind::Collection | PoolTargetKind::Basket => {
+                    if token_pool.target_token != *allowed_mints_info.key
+                        || allowed_mints.token_pool != *token_pool_info.key
+                    {
+                        return Err(TokenPoolError::InvalidData.into());
+                    }
+                }
+            }
+
+            // signers of a multisig manager follow the mints
+            for mint_info in accounts_iter.filter(|account| !account.is_signer) {
+                if allowed_mints.contains(mint_info.key) {
+                    continue;
+                }
+                if allowed_mints.mints.len() >= capacity {
+                    return Err(TokenPoolError::AllowedMintsFull.into());
+                }
+                allowed_mints.mints.push(*mint_info.key);
+            }
+
+            allowed_mints.serialize(&mut &mut allowed_mints_info.data.borrow_mut()[..])?;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        18 => {
+            msg!("Initialize basket instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let inventory_info = next_account_info(accounts_iter)?;
+
+            if *inventory_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if !Rent::get()?.is_exempt(inventory_info.lamports(), inventory_info.data_len()) {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+            if Inventory::capacity(inventory_info.data_len()) == 0 {
+                return Err(TokenPoolError::InventoryFull.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+            // basket buys from the allowed mints of a collection pool
+            if token_pool.target_kind != PoolTargetKind::Collection {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize inventory account !");
+            let mut inventory =
+                try_from_slice_unchecked::<Inventory>(&inventory_info.data.borrow())?;
+            if inventory.token_pool != Pubkey::default() {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            inventory.token_pool = *token_pool_info.key;
+            inventory.rent_payer = *manager_info.key;
+            token_pool.target_kind = PoolTargetKind::Basket;
+            token_pool.inventory = *inventory_info.key;
+
+            inventory.serialize(&mut &mut inventory_info.data.borrow_mut()[..])?;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        19 => {
+            msg!("Set fungible target instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let mint_info = next_account_info(accounts_iter)?;
+            let token_amount = instruction.arg1;
+
+            if *mint_info.owner != spl_token::id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if token_amount == 0 {
+                return Err(TokenPoolError::WrongAmountData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+            // members join knowing the token the pool buys, it can't change once they contributed
+            if token_pool.current_balance != 0 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if token_pool.target_kind != PoolTargetKind::SingleMint
+                && token_pool.target_kind != PoolTargetKind::Fungible
+            {
+                return Err(TokenPoolError::WrongTargetKind.into());
+            }
+
+            token_pool.target_kind = PoolTargetKind::Fungible;
+            token_pool.target_token = *mint_info.key;
+            token_pool.target_token_amount = token_amount;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        20 => {
+            msg!("List tokens instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let seller_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let mint_info = next_account_info(accounts_iter)?;
+            let vault_info = next_account_info(accounts_iter)?;
+            let token_account_info = next_account_info(accounts_iter)?;
+            let token_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+
+            if !seller_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            check_platform_not_paused(platform_config_info)?;
+
+            let token_account =
+                spl_token::state::Account::unpack(&token_account_info.data.borrow())?;
+            if token_account.mint != *mint_info.key || token_account.owner != *seller_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if token_account.amount == 0 {
+                return Err(TokenPoolError::WrongAmountData.into());
+            }
+
+            // vault is derived the same way as for a listed nft account
+            let (vault_pda, _bump) =
+                Pubkey::find_program_address(&[b"listnft", token_account_info.key.as_ref()], &id());
+            if vault_pda != *vault_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize escrow state account !");
+            let mut escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Uninitialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+
+            escrow.stage = EscrowStage::Initialized;
+            escrow.listing = ListingKind::Tokens;
+            escrow.amount = instruction.arg1;
+            escrow.seller = *seller_info.key;
+            escrow.rent_payer = *seller_info.key;
+            escrow.share = 100.0;
+            escrow.escrow_vault = *vault_info.key;
+            escrow.nft = *token_account_info.key;
+            escrow.nft_mint = *mint_info.key;
+            escrow.token_amount = token_account.amount;
+
+            msg!("give owner of the token account to vault !");
+            transfer_account_owner(
+                token_program_info,
+                token_account_info,
+                seller_info,
+                vault_info.key,
+                &[],
+            )?;
+
+            escrow.serialize(&mut &mut escrow_state_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        21 => {
+            msg!("Buy tokens using token pool treasury !");
+            let accounts_iter = &mut accounts.iter();
+            let buyer_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let token_pool_vault_info = next_account_info(accounts_iter)?;
+            let token_account_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let treasury_info = next_account_info(accounts_iter)?;
+            let seller_info = next_account_info(accounts_iter)?;
+            let mint_info = next_account_info(accounts_iter)?;
+            let escrow_vault_info = next_account_info(accounts_iter)?;
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+
+            if !buyer_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            if token_pool.subscription_end != 0 {
+                return Err(TokenPoolError::SubscriptionOpen.into());
+            }
+            if token_pool.committed_bid != 0 {
+                return Err(TokenPoolError::BidCommitted.into());
+            }
+            if token_pool.target_kind != PoolTargetKind::Fungible {
+                return Err(TokenPoolError::WrongTargetKind.into());
+            }
+            if !token_pool.pool_member_list.find_member(*buyer_info.key) {
+                return Err(TokenPoolError::MemberNotInPool.into());
+            }
+            if token_pool.vault != *token_pool_vault_info.key
+                || token_pool.treasurey != *treasury_info.key
+                || token_pool.manager != *manager_info.key
+                || token_pool.target_token != *mint_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize escrow state account !");
+            let escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.listing != ListingKind::Tokens {
+                return Err(TokenPoolError::InvalidListingKind.into());
+            }
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+            if escrow.is_expired(Clock::get()?.unix_timestamp) {
+                return Err(TokenPoolError::EscrowExpired.into());
+            }
+            if escrow.seller != *seller_info.key
+                || escrow.escrow_vault != *escrow_vault_info.key
+                || escrow.nft != *token_account_info.key
+                || escrow.nft_mint != *mint_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // price asked by the seller is the price the buyer agreed to
+            let price = escrow.amount;
+            if instruction.arg1 != price {
+                return Err(TokenPoolError::WrongAmountData.into());
+            }
+            if token_pool.current_balance < price {
+                return Err(ProgramError::InsufficientFunds);
+            }
+            let token_account =
+                spl_token::state::Account::unpack(&token_account_info.data.borrow())?;
+            if token_account.amount < token_pool.target_token_amount {
+                return Err(TokenPoolError::WrongAmountData.into());
+            }
+
+            msg!("transfer the price from treasury to seller !");
+            pay_from_treasury(
+                treasury_info,
+                seller_info,
+                manager_info,
+                price,
+                token_pool.minimum_exemption_amount,
+            )?;
+
+            msg!("give owner of the token account to token pool vault !");
+            let (_vault_pda, bump) =
+                Pubkey::find_program_address(&[b"listnft", escrow.nft.as_ref()], &id());
+            transfer_account_owner(
+                token_program_info,
+                token_account_info,
+                escrow_vault_info,
+                &token_pool.vault,
+                &[&[b"listnft", escrow.nft.as_ref(), &[bump]]],
+            )?;
+
+            msg!("close escrow account and tranfer lamports to seller");
+            close_account(escrow_state_info, seller_info)?;
+
+            // members own the tokens in proportion to their shares, what the price did not use is refunded
+            token_pool.settle_shares()?;
+            token_pool.stage = TokenPoolStage::NFTOwned;
+            token_pool.current_balance -= price;
+            token_pool.refund_leftover();
+            check_token_pool(&token_pool)?;
+            check_treasury(&token_pool, treasury_info)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        22 => {
+            msg!("Redeem share instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let member_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let token_pool_vault_info = next_account_info(accounts_iter)?;
+            let pool_token_account_info = next_account_info(accounts_iter)?;
+            let member_token_account_info = next_account_info(accounts_iter)?;
+            let token_program_info = next_account_info(accounts_iter)?;
+
+            if !member_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::NFTOwned {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if token_pool.target_kind != PoolTargetKind::Fungible {
+                return Err(TokenPoolError::WrongTargetKind.into());
+            }
+            if token_pool.vault != *token_pool_vault_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // an escrowed share has to be cancelled before redeeming it
+            let index = token_pool
+                .pool_member_list
+                .get_member_index(*member_info.key)
+                .ok_or(TokenPoolError::MemberNotInPool)?;
+            let member = token_pool.pool_member_list.members[index];
+            if member.share_stage != ShareStage::Hold {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_refund_claimed(&token_pool, member_info.key)?;
+
+            let pool_token_account =
+                spl_token::state::Account::unpack(&pool_token_account_info.data.borrow())?;
+            if pool_token_account.owner != token_pool.vault
+                || pool_token_account.mint != token_pool.target_token
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // shares of the members who have redeemed are gone, so the share is of what is left
+            let total_share = token_pool.pool_member_list.total_share();
+            let redeem_amount = if member.share >= total_share {
+                pool_token_account.amount
+            } else {
+                (pool_token_account.amount as f64 * member.share / total_share) as u64
+            };
+
+            msg!("transfer the tokens to member !");
+            let (_vault_pda, bump) =
+                Pubkey::find_program_address(&[b"pool", token_pool_info.key.as_ref()], &id());
+            let transfer_inst = token_transfer(
+                token_program_info.key,
+                pool_token_account_info.key,
+                member_token_account_info.key,
+                token_pool_vault_info.key,
+                &[token_pool_vault_info.key],
+                redeem_amount,
+            )?;
+            invoke_signed(
+                &transfer_inst,
+                &[
+                    token_program_info.clone(),
+                    pool_token_account_info.clone(),
+                    member_token_account_info.clone(),
+                    token_pool_vault_info.clone(),
+                ],
+                &[&[b"pool", token_pool_info.key.as_ref(), &[bump]]],
+            )?;
+
+            token_pool.pool_member_list.remove_member(*member_info.key);
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        23 => {
+            msg!("Set buyout terms instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let objection_period = instruction.arg5 as UnixTimestamp;
+
+            // members always get some time to object a buyout
+            if objection_period <= 0 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            // members join on the terms, they are locked once the pool owns its nft, before any buyout can start
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+
+            token_pool.reserve_price = instruction.arg1;
+            token_pool.objection_period = objection_period;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        24 => {
+            msg!("Start buyout instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let buyer_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let buyout_info = next_account_info(accounts_iter)?;
+            let current_buyer_info = next_account_info(accounts_iter)?;
+            let system_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let price = instruction.arg1;
+
+            if !buyer_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
+            if token_pool.stage != TokenPoolStage::NFTOwned {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            // only a pool owning a single nft can be bought out
+            if token_pool.target_kind != PoolTargetKind::SingleMint
+                && token_pool.target_kind != PoolTargetKind::Collection
+            {
+                return Err(TokenPoolError::WrongTargetKind.into());
+            }
+            if token_pool.reserve_price == 0 || price < token_pool.reserve_price {
+                return Err(TokenPoolError::BuyoutPriceTooLow.into());
+            }
+
+            let (buyout_key, bump) =
+                Pubkey::find_program_address(&[b"buyout", token_pool_info.key.as_ref()], &id());
+            if buyout_key != *buyout_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // buyer only pays the other members for their shares
+            let buyer_share = token_pool.pool_member_list.share_of(*buyer_info.key);
+            let deposit = Buyout::deposit_for(price, buyer_share);
+            let now = Clock::get()?.unix_timestamp;
+
+            if buyout_info.lamports() == 0 {
+                msg!("create buyout account !");
+                let space = Buyout::account_size(token_pool.pool_member_list.header.max_members);
+                let create_inst = create_account(
+                    buyer_info.key,
+                    buyout_info.key,
+                    Rent::get()?.minimum_balance(space),
+                    space as u64,
+                    &id(),
+                );
+                invoke_signed(
+                    &create_inst,
+                    &[
+                        buyer_info.clone(),
+                        buyout_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                    &[&[b"buyout", token_pool_info.key.as_ref(), &[bump]]],
+                )?;
+            } else {
+                let buyout = try_from_slice_unchecked::<Buyout>(&buyout_info.data.borrow())?;
+                if buyout.stage != BuyoutStage::Active {
+                    return Err(TokenPoolError::InvalidBuyoutStage.into());
+                }
+                if now >= buyout.end_time {
+                    return Err(TokenPoolError::ObjectionWindowClosed.into());
+                }
+                if price <= buyout.price {
+                    return Err(TokenPoolError::BuyoutPriceTooLow.into());
+                }
+                if buyout.buyer != *current_buyer_info.key {
+                    return Err(TokenPoolError::InvalidData.into());
+                }
+
+                msg!("refund the current buyer !");
+                **buyout_info.lamports.borrow_mut() = buyout_info
+                    .lamports()
+                    .checked_sub(buyout.deposit)
+                    .ok_or(ProgramError::InsufficientFunds)?;
+                **current_buyer_info.lamports.borrow_mut() = current_buyer_info
+                    .lamports()
+                    .checked_add(buyout.deposit)
+                    .ok_or(ProgramError::InvalidArgument)?;
+            }
+
+            msg!("move the deposit to buyout !");
+            let transfer_inst = transfer(buyer_info.key, buyout_info.key, deposit);
+            invoke(
+                &transfer_inst,
+                &[
+                    buyer_info.clone(),
+                    buyout_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+
+            // a new offer restarts the objection window
+            let buyout = Buyout {
+                stage: BuyoutStage::Active,
+                token_pool: *token_pool_info.key,
+                buyer: *buyer_info.key,
+                price,
+                deposit,
+                end_time: now
+                    .checked_add(token_pool.objection_period)
+                    .ok_or(TokenPoolError::InvalidData)?,
+                objection_share: 0.0,
+                objectors: vec![],
+            };
+            buyout.serialize(&mut &mut buyout_info.data.borrow_mut()[..])?;
+
+            // shares are frozen for the objection window, so a share can not object twice under another key
+            token_pool.buyout_end = buyout.end_time;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        25 => {
+            msg!("Object buyout instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let member_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let buyout_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if !token_pool.pool_member_list.find_member(*member_info.key) {
+                return Err(TokenPoolError::MemberNotInPool.into());
+            }
+            check_share_authority(
+                &token_pool,
+                member_info.key,
+                member_info,
+                next_account_info(accounts_iter).ok(),
+                DELEGATE_VOTE,
+            )?;
+
+            msg!("Deserialize buyout account !");
+            let mut buyout = try_from_slice_unchecked::<Buyout>(&buyout_info.data.borrow())?;
+            if buyout.stage != BuyoutStage::Active || buyout.token_pool != *token_pool_info.key {
+                return Err(TokenPoolError::InvalidBuyoutStage.into());
+            }
+            if Clock::get()?.unix_timestamp >= buyout.end_time {
+                return Err(TokenPoolError::ObjectionWindowClosed.into());
+            }
+            if buyout.buyer == *member_info.key || buyout.objectors.contains(member_info.key) {
+                return Err(TokenPoolError::AlreadyObjected.into());
+            }
+            if Buyout::account_size(buyout.objectors.len() as u32 + 1) > buyout_info.data_len() {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            buyout.objection_share += token_pool.pool_member_list.share_of(*member_info.key);
+            buyout.objectors.push(*member_info.key);
+            buyout.serialize(&mut &mut buyout_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        26 => {
+            msg!("Complete buyout instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let buyout_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let buyer_info = next_account_info(accounts_iter)?;
+            let nft_mint_info = next_account_info(accounts_iter)?;
+            let nft_info = next_account_info(accounts_iter)?;
+            let token_pool_vault_info = next_account_info(accounts_iter)?;
+            let token_program_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize buyout account !");
+            let mut buyout = try_from_slice_unchecked::<Buyout>(&buyout_info.data.borrow())?;
+            if buyout.stage != BuyoutStage::Active || buyout.token_pool != *token_pool_info.key {
+                return Err(TokenPoolError::InvalidBuyoutStage.into());
+            }
+            if Clock::get()?.unix_timestamp < buyout.end_time {
+                return Err(TokenPoolError::ObjectionWindowOpen.into());
+            }
+            if buyout.buyer != *buyer_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::NFTOwned {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if token_pool.target_token != *nft_mint_info.key
+                || token_pool.vault != *token_pool_vault_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            let buyer_share = token_pool.pool_member_list.share_of(*buyer_info.key);
+            // deposit has to cover the other shares, buyer may have sold a part of its share meanwhile
+            if buyout.is_rejected(buyer_share)
+                || Buyout::deposit_for(buyout.price, buyer_share) > buyout.deposit
+            {
+                msg!("buyout is rejected, close buyout and refund the deposit to buyer");
+                return close_account(buyout_info, buyer_info);
+            }
+            // refunding a rejected buyout stays open while paused, the nft does not move
+            check_not_paused(&token_pool, platform_config_info)?;
+
+            msg!("transfer nft's authorities to buyer !");
+            let (_vault_pda, bump) =
+                Pubkey::find_program_address(&[b"pool", token_pool_info.key.as_ref()], &id());
+            transfer_nft_authorities(
+                token_program_info,
+                nft_info,
+                nft_mint_info,
+                token_pool_vault_info,
+                buyer_info.key,
+                &[&[b"pool", token_pool_info.key.as_ref(), &[bump]]],
+            )?;
+
+            // buyer is not paid for its own share, other members claim their payout from buyout.
+            // a refund still due to the buyer stays with its record till it is claimed
+            if let Some(index) = token_pool
+                .pool_member_list
+                .get_member_index(*buyer_info.key)
+            {
+                if token_pool.pool_member_list.members[index].refund_due > 0 {
+                    token_pool.pool_member_list.members[index].share = 0.0;
+                } else {
+                    token_pool.pool_member_list.remove_member(*buyer_info.key);
+                }
+            }
+            token_pool.stage = TokenPoolStage::BoughtOut;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            if token_pool.pool_member_list.total_share() == 0.0 {
+                msg!("no member is left to pay, close buyout");
+                return close_account(buyout_info, buyer_info);
+            }
+            buyout.stage = BuyoutStage::Succeeded;
+            buyout.serialize(&mut &mut buyout_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        27 => {
+            msg!("Claim buyout payout instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let member_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let buyout_info = next_account_info(accounts_iter)?;
+            let buyer_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize buyout account !");
+            let buyout = try_from_slice_unchecked::<Buyout>(&buyout_info.data.borrow())?;
+            if buyout.stage != BuyoutStage::Succeeded || buyout.token_pool != *token_pool_info.key {
+                return Err(TokenPoolError::InvalidBuyoutStage.into());
+            }
+            if buyout.buyer != *buyer_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::BoughtOut {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_not_paused(&token_pool, platform_config_info)?;
+            // an escrowed share has to be cancelled before claiming its payout
+            let index = token_pool
+                .pool_member_list
+                .get_member_index(*member_info.key)
+                .ok_or(TokenPoolError::MemberNotInPool)?;
+            let member = token_pool.pool_member_list.members[index];
+            if member.share_stage != ShareStage::Hold {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_refund_claimed(&token_pool, member_info.key)?;
+            check_share_authority(
+                &token_pool,
+                member_info.key,
+                member_info,
+                next_account_info(accounts_iter).ok(),
+                DELEGATE_CLAIM,
+            )?;
+
+            let payout = (buyout.price as f64 * member.share / 100.0) as u64;
+            msg!("pay the member for its share !");
+            **buyout_info.lamports.borrow_mut() = buyout_info
+                .lamports()
+                .checked_sub(payout)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            **member_info.lamports.borrow_mut() = member_info
+                .lamports()
+                .checked_add(payout)
+                .ok_or(ProgramError::InvalidArgument)?;
+
+            token_pool.pool_member_list.remove_member(*member_info.key);
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            if token_pool.pool_member_list.total_share() == 0.0 {
+                msg!("every member is paid, close buyout and give what is left to buyer");
+                close_account(buyout_info, buyer_info)?;
+            }
+
+            Ok(())
+        }
+        28 => {
+            msg!("Set gate mint instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let gate_mint_info = next_account_info(accounts_iter).ok();
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage == TokenPoolStage::Uninitialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+
+            token_pool.gate_mint = match gate_mint_info {
+                Some(gate_mint_info) => {
+                    if *gate_mint_info.owner != spl_token::id() {
+                        return Err(ProgramError::IllegalOwner);
+                    }
+                    *gate_mint_info.key
+                }
+                None => Pubkey::default(),
+            };
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        29 => {
+            msg!("Set maximum amount instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let maximum_kind = match instruction.arg2 {
+                0 => MaximumKind::Amount,
+                1 => MaximumKind::Percent,
+                _ => return Err(TokenPoolError::InvalidData.into()),
+            };
+
+            if maximum_kind == MaximumKind::Percent && instruction.arg1 > 100 {
+                return Err(TokenPoolError::WrongAmountData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            // members only contribute while the pool is raising its target amount
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+
+            token_pool.maximum_amount = instruction.arg1;
+            token_pool.maximum_kind = maximum_kind;
+            // a cap too low for the members the pool can have would never let it reach its target
+            if let Some(maximum_contribution) = token_pool.maximum_contribution() {
+                let max_members = token_pool.pool_member_list.header.max_members as u64;
+                if maximum_contribution.saturating_mul(max_members) < token_pool.target_amount {
+                    return Err(TokenPoolError::WrongAmountData.into());
+                }
+            }
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        30 => {
+            msg!("Set manager share instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let manager_share = instruction.arg1;
+
+            if manager_share >= TOTAL_SHARE_UNITS {
+                return Err(TokenPoolError::WrongAmountData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+            // members join knowing the manager share, it can't change once they contributed
+            if token_pool.current_balance != 0 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            let members = &mut token_pool.pool_member_list;
+            if manager_share > 0 && !members.find_member(*manager_info.key) {
+                msg!("reserve a place for the manager in the members list !");
+                let index = members
+                    .get_empty_member_index()
+                    .ok_or(TokenPoolError::NoMemberSpaceLeft)?;
+                members.add_member(index, *manager_info.key, 0, 0.0);
+            } else if manager_share == 0 && members.find_member(*manager_info.key) {
+                members.remove_member(*manager_info.key);
+            }
+            token_pool.manager_share = manager_share;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        31 => {
+            msg!("Set platform config instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let authority_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let system_program_info = next_account_info(accounts_iter)?;
+            let program_data_info = next_account_info(accounts_iter)?;
+            let max_members = instruction.arg4;
+
+            if !authority_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if !(2..=MAX_POOL_MEMBERS).contains(&max_members) {
+                return Err(TokenPoolError::MaxMembersExceeded.into());
+            }
+
+            let (platform_config_key, bump) = Pubkey::find_program_address(&[b"platform"], &id());
+            if platform_config_key != *platform_config_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            let mut platform_config = if platform_config_info.lamports() == 0 {
+                msg!("create platform config account, upgrade authority becomes its authority !");
+                check_upgrade_authority(program_data_info, authority_info.key)?;
+                let rent = Rent::get()?.minimum_balance(PlatformConfig::LEN);
+                let create_inst = create_account(
+                    authority_info.key,
+                    platform_config_info.key,
+                    rent,
+                    PlatformConfig::LEN as u64,
+                    &id(),
+                );
+                invoke_signed(
+                    &create_inst,
+                    &[
+                        authority_info.clone(),
+                        platform_config_info.clone(),
+                        system_program_info.clone(),
+                    ],
+                    &[&[b"platform", &[bump]]],
+                )?;
+                PlatformConfig {
+                    is_initialized: true,
+                    authority: *authority_info.key,
+                    max_members,
+                    paused: false,
+                }
+            } else {
+                let platform_config =
+                    PlatformConfig::unpack_unchecked(&platform_config_info.data.borrow())?;
+                if platform_config.authority != *authority_info.key {
+                    return Err(TokenPoolError::WrongManager.into());
+                }
+                platform_config
+            };
+
+            platform_config.max_members = max_members;
+            platform_config.serialize(&mut &mut platform_config_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        32 => {
+            msg!("Set admission policy instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let admission_policy = match instruction.arg1 {
+                0 => AdmissionPolicy::LastMember,
+                1 => AdmissionPolicy::EvenSplit,
+                _ => return Err(TokenPoolError::InvalidData.into()),
+            };
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+
+            token_pool.admission_policy = admission_policy;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        33 => {
+            msg!("Set subscription end instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let subscription_end = instruction.arg5 as UnixTimestamp;
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            check_manager(&token_pool, manager_info, accounts)?;
+            // an oversubscribed pool has to close its subscription to refund the excess
+            if token_pool.current_balance > token_pool.target_amount {
+                return Err(TokenPoolError::SubscriptionOpen.into());
+            }
+            if subscription_end != 0 && subscription_end <= Clock::get()?.unix_timestamp {
+                return Err(TokenPoolError::SubscriptionEnded.into());
+            }
+
+            token_pool.subscription_end = subscription_end;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        34 => {
+            msg!("Close subscription instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let token_pool_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage != TokenPoolStage::Initialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            if token_pool.subscription_end == 0 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if Clock::get()?.unix_timestamp < token_pool.subscription_end {
+                return Err(TokenPoolError::SubscriptionOpen.into());
+            }
+
+            msg!("scale down contributions and keep the excess for refunds !");
+            token_pool.refund_excess();
+            token_pool.subscription_end = 0;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        35 => {
+            msg!("Claim refund instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let member_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let treasury_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.treasurey != *treasury_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            let index = token_pool
+                .pool_member_list
+                .get_member_index(*member_info.key)
+                .ok_or(TokenPoolError::MemberNotInPool)?;
+            let refund = token_pool.pool_member_list.members[index].refund_due;
+            if refund == 0 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_share_authority(
+                &token_pool,
+                member_info.key,
+                member_info,
+                next_account_info(accounts_iter).ok(),
+                DELEGATE_CLAIM,
+            )?;
+
+            msg!("pay the refund from treasury !");
+            **treasury_info.lamports.borrow_mut() = treasury_info
+                .lamports()
+                .checked_sub(refund)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            **member_info.lamports.borrow_mut() = member_info
+                .lamports()
+                .checked_add(refund)
+                .ok_or(ProgramError::InvalidArgument)?;
+
+            token_pool.pool_member_list.members[index].refund_due = 0;
+            check_token_pool(&token_pool)?;
+            check_treasury(&token_pool, treasury_info)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        36 => {
+            msg!("Set escrow expiry instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let seller_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let expires_at = instruction.arg5 as UnixTimestamp;
+
+            if !seller_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *escrow_state_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            msg!("Deserialize escrow state account !");
+            let mut escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+            if escrow.seller != *seller_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            // an english auction ends at its end time and settles with its bids
+            if escrow.listing == ListingKind::EnglishAuction {
+                return Err(TokenPoolError::InvalidListingKind.into());
+            }
+            if expires_at != 0 && expires_at <= Clock::get()?.unix_timestamp {
+                return Err(TokenPoolError::EscrowExpired.into());
+            }
+
+            escrow.expires_at = expires_at;
+            escrow.serialize(&mut &mut escrow_state_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        37 => {
+            msg!("Expire escrow instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let seller_info = next_account_info(accounts_iter)?;
+            let escrow_vault_info = next_account_info(accounts_iter)?;
+            let rent_payer_info = next_account_info(accounts_iter)?;
+
+            if *escrow_state_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            msg!("Deserialize escrow state account !");
+            let escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            if escrow.stage != EscrowStage::Initialized {
+                return Err(TokenPoolError::InvalidEscrowStage.into());
+            }
+            if !escrow.is_expired(Clock::get()?.unix_timestamp) {
+                return Err(TokenPoolError::EscrowNotExpired.into());
+            }
+            if escrow.seller != *seller_info.key
+                || escrow.escrow_vault != *escrow_vault_info.key
+                || escrow.rent_payer != *rent_payer_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // listings are held by the vault of the listed account, share sales by the vault of seller and pool
+            let (listing_vault, bump) =
+                Pubkey::find_program_address(&[b"listnft", escrow.nft.as_ref()], &id());
+            if listing_vault == escrow.escrow_vault {
+                let nft_mint_info = next_account_info(accounts_iter)?;
+                let nft_info = next_account_info(accounts_iter)?;
+                let token_program_info = next_account_info(accounts_iter)?;
+                if escrow.nft != *nft_info.key || escrow.nft_mint != *nft_mint_info.key {
+                    return Err(TokenPoolError::InvalidData.into());
+                }
+
+                msg!("give authorities of the listed account back to seller !");
+                if escrow.listing == ListingKind::Tokens {
+                    transfer_account_owner(
+                        token_program_info,
+                        nft_info,
+                        escrow_vault_info,
+                        seller_info.key,
+                        &[&[b"listnft", escrow.nft.as_ref(), &[bump]]],
+                    )?;
+                } else {
+                    transfer_nft_authorities(
+                        token_program_info,
+                        nft_info,
+                        nft_mint_info,
+                        escrow_vault_info,
+                        seller_info.key,
+                        &[&[b"listnft", escrow.nft.as_ref(), &[bump]]],
+                    )?;
+                }
+            } else {
+                let token_pool_info = next_account_info(accounts_iter)?;
+
+                msg!("Deserialize token pool account !");
+                let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+                check_share_escrow_vault(
+                    seller_info.key,
+                    token_pool_info.key,
+                    escrow_vault_info.key,
+                )?;
+                if !token_pool
+                    .pool_member_list
+                    .find_member(*escrow_vault_info.key)
+                {
+                    return Err(TokenPoolError::InvalidData.into());
+                }
+
+                msg!("give the share back to seller !");
+                give_back_escrowed_share(&mut token_pool, seller_info.key, escrow_vault_info.key);
+                check_token_pool(&token_pool)?;
+                token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
+            }
+
+            msg!("close escrow account and refund rent to rent payer");
+            close_account(escrow_state_info, rent_payer_info)?;
+
+            Ok(())
+        }
+        38 => {
+            msg!("Transfer share instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let member_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let recipient_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+            let units = instruction.arg1;
+
+            if !member_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *recipient_info.key == *member_info.key || *recipient_info.key == Pubkey::default() {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_not_paused(&token_pool, platform_config_info)?;
+            if token_pool.stage == TokenPoolStage::Uninitialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+            // shares of a bought out pool are only paid out
+            if token_pool.stage == TokenPoolStage::BoughtOut {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_no_active_buyout(&token_pool)?;
+
+            // only a share which is held and not escrowed for a sale can be transferred
+            let member_index = token_pool
+                .pool_member_list
+                .get_member_index(*member_info.key)
+                .ok_or(TokenPoolError::MemberNotInPool)?;
+            let member_share = token_pool.pool_member_list.members[member_index];
+            if member_share.share_stage != ShareStage::Hold {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_refund_claimed(&token_pool, member_info.key)?;
+            if !token_pool.pool_member_list.find_member(*recipient_info.key) {
+                check_gate(
+                    &token_pool,
+                    recipient_info,
+                    next_account_info(accounts_iter).ok(),
+                )?;
+            }
+
+            // zero units transfers the whole share
+            let member_units = share_to_units(member_share.share);
+            let share = if units == 0 || units == member_units {
+                member_share.share
+            } else if units < member_units {
+                units_to_share(units)
+            } else {
+                return Err(TokenPoolError::InsufficientShare.into());
+            };
+
+            msg!("move the share to recipient !");
+            token_pool.pool_member_list.transfer_share(
+                *member_info.key,
+                *recipient_info.key,
+                share,
+            )?;
+            check_maximum_amount(
+                &token_pool,
+                token_pool
+                    .pool_member_list
+                    .amount_deposited_by(*recipient_info.key),
+            )?;
+
+            msg!("serialize the token pool account");
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut *token_pool_info.data.borrow_mut())?;
+
+            Ok(())
+        }
+        39 => {
+            msg!("Approve delegate instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let member_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let delegate_info = next_account_info(accounts_iter)?;
+            let permissions = instruction.arg1;
+
+            if !member_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if permissions == 0
+                || permissions & !u64::from(DELEGATE_VOTE | DELEGATE_SELL | DELEGATE_CLAIM) != 0
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            if *delegate_info.key == *member_info.key || *delegate_info.key == Pubkey::default() {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.stage == TokenPoolStage::Uninitialized {
+                return Err(TokenPoolError::UninitializedTokenPool.into());
+            }
+
+            token_pool.pool_member_list.approve_delegate(
+                *member_info.key,
+                *delegate_info.key,
+                permissions as u8,
+            )?;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        40 => {
+            msg!("Revoke delegate instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let member_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+
+            if !member_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            token_pool
+                .pool_member_list
+                .revoke_delegate(*member_info.key)?;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        41 => {
+            msg!("Initialize multisig instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let multisig_info = next_account_info(accounts_iter)?;
+            let signer_infos = accounts_iter.as_slice();
+            let m = instruction.arg1;
+
+            // multisig account signs so it can't be taken over between its creation and initialization
+            if !multisig_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *multisig_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if !Rent::get()?.is_exempt(multisig_info.lamports(), multisig_info.data_len()) {
+                return Err(ProgramError::AccountNotRentExempt);
+            }
+
+            msg!("Deserialize multisig account !");
+            let mut multisig = Multisig::unpack_unchecked(&multisig_info.data.borrow())?;
+            if multisig.is_initialized {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            if signer_infos.is_empty()
+                || signer_infos.len() > MAX_SIGNERS
+                || m == 0
+                || m > signer_infos.len() as u64
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            multisig.is_initialized = true;
+            multisig.m = m as u8;
+            multisig.n = signer_infos.len() as u8;
+            for (signer, signer_info) in multisig.signers.iter_mut().zip(signer_infos) {
+                *signer = *signer_info.key;
+            }
+            multisig.serialize(&mut &mut multisig_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        42 => {
+            msg!("Collect fees instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let multisig_info = next_account_info(accounts_iter)?;
+            let destination_info = next_account_info(accounts_iter)?;
+
+            // fees paid to a multisig manager are kept in its account
+            if *multisig_info.owner != id() || multisig_info.data_len() != Multisig::LEN {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_authority(multisig_info, accounts)?;
+
+            let collectable = multisig_info
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(Multisig::LEN));
+            let amount = match instruction.arg1 {
+                0 => collectable,
+                amount if amount <= collectable => amount,
+                _ => return Err(ProgramError::InsufficientFunds),
+            };
+
+            msg!("transfer the fees to destination !");
+            **multisig_info.lamports.borrow_mut() -= amount;
+            **destination_info.lamports.borrow_mut() = destination_info
+                .lamports()
+                .checked_add(amount)
+                .ok_or(ProgramError::InvalidArgument)?;
+
+            Ok(())
+        }
+        43 => {
+            msg!("Accept manager instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let new_manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.pending_manager == Pubkey::default()
+                || token_pool.pending_manager != *new_manager_info.key
+            {
+                return Err(TokenPoolError::WrongManager.into());
+            }
+            check_authority(new_manager_info, accounts)?;
+
+            // the place reserved for the manager share moves to the new manager
+            let old_manager = token_pool.manager;
+            let members = &mut token_pool.pool_member_list;
+            if let Some(index) = members.get_member_index(old_manager) {
+                let reserved = members.members[index];
+                if reserved.share == 0.0 && reserved.amount_deposited == 0 {
+                    if members.find_member(*new_manager_info.key) {
+                        members.remove_member(old_manager);
+                    } else {
+                        members.update_key(old_manager, *new_manager_info.key);
+                    }
+                }
+            }
+
+            token_pool.manager = *new_manager_info.key;
+            token_pool.pending_manager = Pubkey::default();
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        44 => {
+            msg!("Cancel manager transfer instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_manager(&token_pool, manager_info, accounts)?;
+            if token_pool.pending_manager == Pubkey::default() {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            token_pool.pending_manager = Pubkey::default();
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        45 => {
+            msg!("Set pool paused instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_manager(&token_pool, manager_info, accounts)?;
+            if instruction.arg1 > 1 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            token_pool.paused = instruction.arg1 == 1;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        46 => {
+            msg!("Set platform paused instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let authority_info = next_account_info(accounts_iter)?;
+            let platform_config_info = next_account_info(accounts_iter)?;
+
+            if !authority_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if instruction.arg1 > 1 {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            // the platform config has to be set up before the platform can be paused
+            let mut platform_config =
+                platform_config(platform_config_info)?.ok_or(TokenPoolError::InvalidData)?;
+            if platform_config.authority != *authority_info.key {
+                return Err(TokenPoolError::WrongManager.into());
+            }
+
+            platform_config.paused = instruction.arg1 == 1;
+            platform_config.serialize(&mut &mut platform_config_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        47 => {
+            msg!("Close pool instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let treasury_info = next_account_info(accounts_iter)?;
+            let rent_payer_info = next_account_info(accounts_iter)?;
+            let manager_info = next_account_info(accounts_iter).ok();
+
+            msg!("Deserialize token pool account !");
+            let token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.treasurey != *treasury_info.key
+                || token_pool.rent_payer != *rent_payer_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_pool_closable(&token_pool, manager_info, accounts)?;
+            // the treasury is found through the pool, so it is closed first
+            if treasury_info.lamports() > 0 {
+                return Err(TokenPoolError::AccountNotClosable.into());
+            }
+
+            msg!("close token pool account and refund rent to rent payer");
+            close_account(token_pool_info, rent_payer_info)
+        }
+        48 => {
+            msg!("Close escrow instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let rent_payer_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize escrow state account !");
+            let escrow = Escrow::unpack_unchecked(&escrow_state_info.data.borrow())?;
+            // escrows in any other end state are closed by the instruction ending them
+            if escrow.stage != EscrowStage::NftSold {
+                return Err(TokenPoolError::AccountNotClosable.into());
+            }
+            if escrow.rent_payer != *rent_payer_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("close escrow account and refund rent to rent payer");
+            close_account(escrow_state_info, rent_payer_info)
+        }
+        49 => {
+            msg!("Close treasury instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let treasury_info = next_account_info(accounts_iter)?;
+            let rent_payer_info = next_account_info(accounts_iter)?;
+            let manager_info = next_account_info(accounts_iter).ok();
+
+            msg!("Deserialize token pool account !");
+            let token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            if token_pool.treasurey != *treasury_info.key
+                || token_pool.rent_payer != *rent_payer_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            check_pool_closable(&token_pool, manager_info, accounts)?;
+            // lamports still owed to members are never closed to the rent payer
+            let rent = Rent::get()?.minimum_balance(treasury_info.data_len());
+            if treasury_reserve(&token_pool, treasury_info)? > rent {
+                return Err(TokenPoolError::AccountNotClosable.into());
+            }
+
+            msg!("close treasury account and refund rent to rent payer");
+            close_account(treasury_info, rent_payer_info)
+        }
+        50 => {
+            msg!("Sweep treasury instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let manager_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let treasury_info = next_account_info(accounts_iter)?;
+
+            msg!("Deserialize token pool account !");
+            let token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+            check_manager(&token_pool, manager_info, accounts)?;
+            check_treasury(&token_pool, treasury_info)?;
+
+            // lamports over what the treasury owes are not accounted to anyone, e.g. the rounding of
+            // refunds or lamports sent to the treasury directly. what a purchase leaves is owed as refunds
+            let excess = treasury_info.lamports() - treasury_reserve(&token_pool, treasury_info)?;
+            if excess == 0 {
+                return Err(ProgramError::InsufficientFunds);
+            }
+
+            msg!("sweep the excess lamports of treasury to manager !");
+            **treasury_info.lamports.borrow_mut() -= excess;
+            **manager_info.lamports.borrow_mut() = manager_info
+                .lamports()
+                .checked_add(excess)
+                .ok_or(ProgramError::InvalidArgument)?;
+
+            Ok(())
+        }
+        51 => {
+            msg!("Close allowed mints instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let allowed_mints_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let rent_payer_info = next_account_info(accounts_iter)?;
+
+            if *allowed_mints_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            msg!("Deserialize allowed mints account !");
+            let allowed_mints =
+                try_from_slice_unchecked::<AllowedMints>(&allowed_mints_info.data.borrow())?;
+            if allowed_mints.token_pool != *token_pool_info.key
+                || allowed_mints.rent_payer != *rent_payer_info.key
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            // the list is only needed till its token pool is finished or closed
+            if token_pool_info.lamports() > 0 {
+                if *token_pool_info.owner != id() {
+                    return Err(ProgramError::IllegalOwner);
+                }
+                msg!("Deserialize token pool account !");
+                let token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+                if !token_pool.is_finished() {
+                    return Err(TokenPoolError::AccountNotClosable.into());
+                }
+            }
+
+            msg!("close allowed mints account and refund rent to rent payer");
+            close_account(allowed_mints_info, rent_payer_info)
+        }
+        52 => {
+            msg!("Migrate token pool instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let payer_info = next_account_info(accounts_iter)?;
+            let token_pool_info = next_account_info(accounts_iter)?;
+            let system_program_info = next_account_info(accounts_iter)?;
+
+            if !payer_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *token_pool_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            // pools in the current layout start with their version, legacy pools with their stage
+            if token_pool_info.data.borrow().first() == Some(&TOKEN_POOL_VERSION) {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+
+            msg!("Deserialize legacy token pool account !");
+            let legacy =
+                try_from_slice_unchecked::<LegacyTokenPool>(&token_pool_info.data.borrow())?;
+            // only a token pool holds a member list of its max members, other accounts of the program are left alone
+            let header = legacy.header;
+            if legacy.stage == TokenPoolStage::Uninitialized
+                || header.account_type != AccountType::TokenPoolMember
+                || legacy.members.len() != header.max_members as usize
+            {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            let token_pool = legacy.migrate();
+
+            msg!("grow token pool account to the current layout !");
+            grow_account(
+                payer_info,
+                token_pool_info,
+                system_program_info,
+                get_instance_packed_len(&token_pool)?,
+            )?;
+            check_token_pool(&token_pool)?;
+            token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        53 => {
+            msg!("Migrate escrow instruction starts !");
+            let accounts_iter = &mut accounts.iter();
+            let payer_info = next_account_info(accounts_iter)?;
+            let escrow_state_info = next_account_info(accounts_iter)?;
+            let system_program_info = next_account_info(accounts_iter)?;
+
+            if !payer_info.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            if *escrow_state_info.owner != id() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            // no other account of the program has the size of a legacy escrow
+            if escrow_state_info.data_len() != LegacyEscrow::LEN {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+
+            msg!("Deserialize legacy escrow state account !");
+            let legacy =
+                try_from_slice_unchecked::<LegacyEscrow>(&escrow_state_info.data.borrow())?;
+            let escrow = legacy.migrate();
+
+            msg!("grow escrow state account to the current layout !");
+            grow_account(
+                payer_info,
+                escrow_state_info,
+                system_program_info,
+                Escrow::LEN,
+            )?;
+            escrow.serialize(&mut &mut escrow_state_info.data.borrow_mut()[..])?;
+
+            Ok(())
+        }
+        _ => return Err(ProgramError::InvalidArgument),
+    }
+}
+
+/// Pay the price of an nft from token pool treasury, manager keeps the minimum exemption amount
+fn pay_from_treasury(
+    treasury_info: &AccountInfo,
+    seller_info: &AccountInfo,
+    manager_info: &AccountInfo,
+    price: u64,
+    minimum_exemption_amount: u64,
+) -> ProgramResult {
+    let seller_amount = price
+        .checked_sub(minimum_exemption_amount)
+        .ok_or(TokenPoolError::WrongAmountData)?;
+    **treasury_info.lamports.borrow_mut() = treasury_info
+        .lamports()
+        .checked_sub(price)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **seller_info.lamports.borrow_mut() = seller_info
+        .lamports()
+        .checked_add(seller_amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+    **manager_info.lamports.borrow_mut() = manager_info
+        .lamports()
+        .checked_add(minimum_exemption_amount)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    Ok(())
+}
+
+/// Release the bid a token pool committed to the auction of the escrow once it is outbid
+fn release_committed_bid(token_pool_info: &AccountInfo, escrow: &Pubkey) -> ProgramResult {
+    if *token_pool_info.owner != id() {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let mut token_pool = TokenPool::load(&token_pool_info.data.borrow())?;
+    if token_pool.bid_escrow == *escrow {
+        token_pool.committed_bid = 0;
+        token_pool.bid_escrow = Pubkey::default();
+    }
+    check_token_pool(&token_pool)?;
+    token_pool.serialize(&mut &mut token_pool_info.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Check if shares of the token pool can move, they stay put while a buyout is open to objections
+fn check_no_active_buyout(token_pool: &TokenPool) -> ProgramResult {
+    if Clock::get()?.unix_timestamp < token_pool.buyout_end {
+        return Err(TokenPoolError::BuyoutActive.into());
+    }
+    Ok(())
+}
+
+/// Check if a token pool can buy the nft mint, a collection pool needs its allowed mints account
+fn check_target_mint(
+    token_pool: &TokenPool,
+    allowed_mints_info: Option<&AccountInfo>,
+    nft_mint: &Pubkey,
+) -> ProgramResult {
+    match token_pool.target_kind {
+        PoolTargetKind::SingleMint => {
+            if token_pool.target_token != *nft_mint {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+        }
+        PoolTargetKind::Collection | PoolTargetKind::Basket => {
+            let allowed_mints_info = allowed_mints_info.ok_or(TokenPoolError::InvalidData)?;
+            if token_pool.target_token != *allowed_mints_info.key {
+                return Err(TokenPoolError::InvalidData.into());
+            }
+            let allowed_mints =
+                try_from_slice_unchecked::<AllowedMints>(&allowed_mints_info.data.borrow())?;
+            if !allowed_mints.contains(nft_mint) {
+                return Err(TokenPoolError::MintNotAllowed.into());
+            }
+        }
+        PoolTargetKind::Fungible => {
+            return Err(TokenPoolError::WrongTargetKind.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if a member can join a token pool, members of a gated pool have to hold a token of its gate mint
+fn check_gate(
+    token_pool: &TokenPool,
+    member_info: &AccountInfo,
+    gate_token_account_info: Option<&AccountInfo>,
+) -> ProgramResult {
+    if token_pool.gate_mint == Pubkey::default() {
+        return Ok(());
+    }
+    let gate_token_account_info =
+        gate_token_account_info.ok_or(TokenPoolError::MemberNotAllowed)?;
+    if *gate_token_account_info.owner != spl_token::id() {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let gate_token_account =
+        spl_token::state::Account::unpack(&gate_token_account_info.data.borrow())?;
+    if gate_token_account.mint != token_pool.gate_mint
+        || gate_token_account.owner != *member_info.key
+        || gate_token_account.amount == 0
+    {
+        return Err(TokenPoolError::MemberNotAllowed.into());
+    }
+
+    Ok(())
+}
+
+/// Platform config of the program, none while the platform has not set one up
+fn platform_config(
+    platform_config_info: &AccountInfo,
+) -> Result<Option<PlatformConfig>, ProgramError> {
+    let (platform_config_key, _) = Pubkey::find_program_address(&[b"platform"], &id());
+    if platform_config_key != *platform_config_info.key {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    if platform_config_info.lamports() == 0 {
+        return Ok(None);
+    }
+    Ok(Some(PlatformConfig::unpack_unchecked(
+        &platform_config_info.data.borrow(),
+    )?))
+}
+
+/// Most members a new token pool can have, the platform config can lower it
+fn platform_max_members(platform_config_info: &AccountInfo) -> Result<u32, ProgramError> {
+    Ok(
+        platform_config(platform_config_info)?.map_or(MAX_POOL_MEMBERS, |platform_config| {
+            platform_config.max_members
+        }),
+    )
+}
+
+/// Check if the platform is not paused
+fn check_platform_not_paused(platform_config_info: &AccountInfo) -> ProgramResult {
+    if platform_config(platform_config_info)?.is_some_and(|platform_config| platform_config.paused)
+    {
+        return Err(TokenPoolError::Paused.into());
+    }
+    Ok(())
+}
+
+/// Check if neither the token pool nor the platform is paused. contributions, trading, listings, purchases,
+/// auctions and buyouts stop while paused. the ways out stay open so nobody's lamports or nft get stuck:
+/// claiming refunds, cancelling share sales, listings and share bids, expiring escrows, objecting and
+/// refunding a rejected buyout, redeeming tokens and closing accounts
+fn check_not_paused(token_pool: &TokenPool, platform_config_info: &AccountInfo) -> ProgramResult {
+    if token_pool.paused {
+        return Err(TokenPoolError::Paused.into());
+    }
+    check_platform_not_paused(platform_config_info)
+}
+
+/// Check if the key is the upgrade authority of the program, read from the program data account
+fn check_upgrade_authority(program_data_info: &AccountInfo, authority: &Pubkey) -> ProgramResult {
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[id().as_ref()], &bpf_loader_upgradeable::id());
+    if program_data_key != *program_data_info.key
+        || *program_data_info.owner != bpf_loader_upgradeable::id()
+    {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    let program_data = program_data_info.data.borrow();
+    let metadata = program_data
+        .get(..metadata_len)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    match limited_deserialize::<UpgradeableLoaderState>(metadata, metadata_len as u64) {
+        Ok(UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address: Some(upgrade_authority),
+            ..
+        }) if upgrade_authority == *authority => Ok(()),
+        _ => Err(TokenPoolError::WrongManager.into()),
+    }
+}
+
+/// Check if an oversubscribed token pool still takes contributions, returns whether it is oversubscribing
+fn check_subscription(token_pool: &TokenPool) -> Result<bool, ProgramError> {
+    if token_pool.subscription_end == 0 {
+        return Ok(false);
+    }
+    if Clock::get()?.unix_timestamp >= token_pool.subscription_end {
+        return Err(TokenPoolError::SubscriptionEnded.into());
+    }
+    Ok(true)
+}
+
+/// Check if the accounts of a token pool can be closed, a pool nobody has joined can only be closed
+/// by its manager
+fn check_pool_closable(
+    token_pool: &TokenPool,
+    manager_info: Option<&AccountInfo>,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    if token_pool.is_finished() {
+        return Ok(());
+    }
+    if token_pool.stage == TokenPoolStage::Initialized
+        && token_pool.current_balance == 0
+        && !token_pool.pool_member_list.has_members_left()
+    {
+        let manager_info = manager_info.ok_or(ProgramError::MissingRequiredSignature)?;
+        return check_manager(token_pool, manager_info, accounts);
+    }
+    Err(TokenPoolError::AccountNotClosable.into())
+}
+
+/// Check if the manager of the token pool signed, a multisig manager needs m of its signers
+fn check_manager(
+    token_pool: &TokenPool,
+    manager_info: &AccountInfo,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    if token_pool.manager != *manager_info.key {
+        return Err(TokenPoolError::WrongManager.into());
+    }
+    check_authority(manager_info, accounts)
+}
+
+/// Check if the authority signed, an authority which is a multisig of the program needs m of its
+/// signers to sign among the accounts of the instruction
+fn check_authority(authority_info: &AccountInfo, accounts: &[AccountInfo]) -> ProgramResult {
+    if *authority_info.owner != id() || authority_info.data_len() != Multisig::LEN {
+        if !authority_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        return Ok(());
+    }
+
+    let multisig = Multisig::unpack_unchecked(&authority_info.data.borrow())?;
+    if !multisig.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    // each signer of the multisig counts once
+    let mut signed = [false; MAX_SIGNERS];
+    for account in accounts.iter().filter(|account| account.is_signer) {
+        if let Some(position) = multisig.signer_position(account.key) {
+            signed[position] = true;
+        }
+    }
+    if signed.iter().filter(|signed| **signed).count() < multisig.m as usize {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    Ok(())
+}
+
+/// Check if the member signed or a delegate of the share held by the key signed with the permission
+fn check_share_authority(
+    token_pool: &TokenPool,
+    share_key: &Pubkey,
+    member_info: &AccountInfo,
+    delegate_info: Option<&AccountInfo>,
+    permission: u8,
+) -> ProgramResult {
+    if member_info.is_signer {
+        return Ok(());
+    }
+    let delegate_info = delegate_info.ok_or(ProgramError::MissingRequiredSignature)?;
+    if !delegate_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !token_pool
+        .pool_member_list
+        .is_delegate_allowed(*share_key, *delegate_info.key, permission)
+    {
+        return Err(TokenPoolError::DelegateNotAllowed.into());
+    }
+
+    Ok(())
+}
+
+/// Check if a member has claimed their refund, it can't move with their share
+fn check_refund_claimed(token_pool: &TokenPool, member: &Pubkey) -> ProgramResult {
+    let index = token_pool
+        .pool_member_list
+        .get_member_index(*member)
+        .ok_or(TokenPoolError::MemberNotInPool)?;
+    if token_pool.pool_member_list.members[index].refund_due > 0 {
+        return Err(TokenPoolError::RefundNotClaimed.into());
+    }
+
+    Ok(())
+}
+
+/// Check if the amount deposited by a member stays within the maximum amount of the token pool
+fn check_maximum_amount(token_pool: &TokenPool, amount_deposited: u64) -> ProgramResult {
+    if let Some(maximum_contribution) = token_pool.maximum_contribution() {
+        if amount_deposited > maximum_contribution {
+            return Err(TokenPoolError::AboveMaximumAmount.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Record an nft bought by a basket token pool in its inventory
+fn add_to_inventory(
+    token_pool: &TokenPool,
+    inventory_info: &AccountInfo,
+    item: InventoryItem,
+) -> ProgramResult {
+    if token_pool.inventory != *inventory_info.key {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    let mut inventory = try_from_slice_unchecked::<Inventory>(&inventory_info.data.borrow())?;
+    if inventory.items.len() >= Inventory::capacity(inventory_info.data_len()) {
+        return Err(TokenPoolError::InventoryFull.into());
+    }
+    inventory.items.push(item);
+    inventory.serialize(&mut &mut inventory_info.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Find if the inventory of a basket token pool has space for another nft
+fn has_inventory_space(
+    token_pool: &TokenPool,
+    inventory_info: &AccountInfo,
+) -> Result<bool, ProgramError> {
+    if token_pool.inventory != *inventory_info.key {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    let inventory = try_from_slice_unchecked::<Inventory>(&inventory_info.data.borrow())?;
+    Ok(inventory.items.len() < Inventory::capacity(inventory_info.data_len()))
+}
+
+/// Remove an nft claimed from a basket token pool, returns the number of nfts left in its inventory
+fn remove_from_inventory(
+    token_pool: &TokenPool,
+    inventory_info: &AccountInfo,
+    nft_mint: &Pubkey,
+    nft: &Pubkey,
+) -> Result<usize, ProgramError> {
+    if token_pool.inventory != *inventory_info.key {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    let mut inventory = try_from_slice_unchecked::<Inventory>(&inventory_info.data.borrow())?;
+    let index = inventory
+        .get_item_index(nft_mint)
+        .ok_or(TokenPoolError::InvalidData)?;
+    if inventory.items[index].nft != *nft {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    inventory.items.remove(index);
+    inventory.serialize(&mut &mut inventory_info.data.borrow_mut()[..])?;
+
+    Ok(inventory.items.len())
+}
+
+/// Grow an account of the program to the given size, payer funds the rent of the extra bytes
+fn grow_account<'a>(
+    payer_info: &AccountInfo<'a>,
+    account_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    if new_len <= account_info.data_len() {
+        return Ok(());
+    }
+    let lamports = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account_info.lamports());
+    if lamports > 0 {
+        invoke(
+            &transfer(payer_info.key, account_info.key, lamports),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+    account_info.realloc(new_len, true)
+}
+
+/// Find when an escrow started now expires, `expires_in` seconds from now or never when zero
+fn escrow_expiry(expires_in: u32) -> Result<UnixTimestamp, ProgramError> {
+    if expires_in == 0 {
+        return Ok(0);
+    }
+    Ok(Clock::get()?.unix_timestamp + expires_in as UnixTimestamp)
+}
+
+/// Check the escrow vault of a share sale is the one of its seller in the token pool, so that the escrow
+/// of a sale can only move the share it holds in that pool
+fn check_share_escrow_vault(
+    seller: &Pubkey,
+    token_pool: &Pubkey,
+    escrow_vault: &Pubkey,
+) -> ProgramResult {
+    let (share_escrow_vault, _) =
+        Pubkey::find_program_address(&[b"escrow", seller.as_ref(), token_pool.as_ref()], &id());
+    if share_escrow_vault != *escrow_vault {
+        return Err(TokenPoolError::InvalidData.into());
+    }
+    Ok(())
+}
+
+/// Give the share held by an escrow vault back to the seller, merging it if seller has joined the pool again
+fn give_back_escrowed_share(token_pool: &mut TokenPool, seller: &Pubkey, escrow_vault: &Pubkey) {
+    if token_pool.pool_member_list.find_member(*seller) {
+        let index = token_pool
+            .pool_member_list
+            .get_member_index(*escrow_vault)
+            .unwrap();
+        let escrowed = token_pool.pool_member_list.members[index];
+        token_pool.pool_member_list.increase_member_share(
+            escrowed.share,
+            *seller,
+            escrowed.amount_deposited,
+        );
+        // a refund due to the escrowed share stays the seller's
+        let seller_index = token_pool
+            .pool_member_list
+            .get_member_index(*seller)
+            .unwrap();
+        token_pool.pool_member_list.members[seller_index].refund_due += escrowed.refund_due;
+        token_pool.pool_member_list.remove_member(*escrow_vault);
+    } else {
+        token_pool
+            .pool_member_list
+            .update_key(*escrow_vault, *seller);
+        token_pool.pool_member_list.remove_escrow(*seller);
+    }
+}
+
+/// Transfer the owner of the nft account and the mint and freeze authorities of its mint,
+/// signer seeds are needed when a program derived address currently holds the authorities
+fn transfer_nft_authorities<'a>(
+    token_program_info: &AccountInfo<'a>,
+    nft_info: &AccountInfo<'a>,
+    nft_mint_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    new_authority: &Pubkey,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    transfer_account_owner(
+        token_program_info,
+        nft_info,
+        authority_info,
+        new_authority,
+        signers_seeds,
+    )?;
+
+    for authority_type in [AuthorityType::MintTokens, AuthorityType::FreezeAccount] {
+        let transfer_mint_authority = set_authority(
+            token_program_info.key,
+            nft_mint_info.key,
+            Some(new_authority),
+            authority_type,
+            authority_info.key,
+            &[authority_info.key],
+        )?;
+        invoke_signed(
+            &transfer_mint_authority,
+            &[
+                token_program_info.clone(),
+                nft_mint_info.clone(),
+                authority_info.clone(),
+            ],
+            signers_seeds,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Transfer the owner of a token account, signer seeds are needed when a program derived address owns it
+fn transfer_account_owner<'a>(
+    token_program_info: &AccountInfo<'a>,
+    token_account_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    new_owner: &Pubkey,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let transfer_authority = set_authority(
+        token_program_info.key,
+        token_account_info.key,
+        Some(new_owner),
+        AuthorityType::AccountOwner,
+        authority_info.key,
+        &[authority_info.key],
+    )?;
+    invoke_signed(
+        &transfer_authority,
+        &[
+            token_program_info.clone(),
+            token_account_info.clone(),
+            authority_info.clone(),
+        ],
+        signers_seeds,
+    )
+}
+
+/// Close a program account by moving all of its lamports to destination and clearing its data
+fn close_account(account_info: &AccountInfo, destination_info: &AccountInfo) -> ProgramResult {
+    let dest_starting_lamports = destination_info.lamports();
+    **destination_info.lamports.borrow_mut() = dest_starting_lamports
+        .checked_add(account_info.lamports())
+        .ok_or(ProgramError::InvalidArgument)?;
+    **account_info.lamports.borrow_mut() = 0;
+    account_info.data.borrow_mut().fill(0);
+
+    Ok(())
 }
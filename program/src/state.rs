@@ -1,5 +1,9 @@
+use crate::error::TokenPoolError;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
+    borsh::try_from_slice_unchecked,
+    clock::UnixTimestamp,
+    entrypoint::MAX_PERMITTED_DATA_INCREASE,
     msg,
     program_error::ProgramError,
     program_memory::sol_memcmp,
@@ -7,18 +11,40 @@ use solana_program::{
     pubkey::{Pubkey, PUBKEY_BYTES},
 };
 
+/// Layout version of token pool accounts, kept in their first byte. Pools created before the layout was
+/// versioned start with their stage, which never gets this high, and are moved over by MigrateTokenPool
+pub const TOKEN_POOL_VERSION: u8 = 4;
+
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
 pub struct TokenPool {
-    pub stage: TokenPoolStage,            //1
-    pub target_amount: u64,               //8
-    pub minimum_exemption_amount: u64,    //8
-    pub minimum_amount: u64,              //8
-    pub current_balance: u64,             //8
-    pub target_token: Pubkey,             //32
-    pub description: String,              //24
-    pub vault: Pubkey,                    //32
-    pub manager: Pubkey,                  //32
-    pub treasurey: Pubkey,                //32
+    pub version: u8,                       //1 , TOKEN_POOL_VERSION
+    pub stage: TokenPoolStage,             //1
+    pub target_amount: u64,                //8
+    pub minimum_exemption_amount: u64,     //8
+    pub minimum_amount: u64,               //8
+    pub current_balance: u64,              //8
+    pub target_token: Pubkey,              //32
+    pub description: String,               //24
+    pub vault: Pubkey,                     //32
+    pub manager: Pubkey,                   //32
+    pub treasurey: Pubkey,                 //32
+    pub committed_bid: u64,                //8 , current balance reserved for a bid on an auction
+    pub bid_escrow: Pubkey,                //32 , escrow of the auction the committed bid is for
+    pub target_kind: PoolTargetKind,       //1
+    pub inventory: Pubkey,                 //32 , only for a basket pool
+    pub target_token_amount: u64,          //8 , only for a fungible pool
+    pub reserve_price: u64,                //8 , lowest price of a buyout of the whole pool
+    pub objection_period: UnixTimestamp,   //8 , time members get to object a buyout
+    pub buyout_end: UnixTimestamp,         //8 , end of the objection window of the current buyout
+    pub gate_mint: Pubkey,                 //32 , members have to hold a token of it to join
+    pub maximum_amount: u64,               //8 , most a member can contribute, zero for no cap
+    pub maximum_kind: MaximumKind,         //1
+    pub manager_share: u64,                //8 , share units granted to manager when the pool buys
+    pub admission_policy: AdmissionPolicy, //1
+    pub subscription_end: UnixTimestamp, //8 , contributions go beyond target till then, zero when not oversubscribing
+    pub pending_manager: Pubkey,         //32 , proposed manager who has not accepted yet
+    pub paused: bool, //1 , manager paused contributions, trading, purchases and buyouts
+    pub rent_payer: Pubkey, //32 , paid the rent of the pool and treasury, gets it back on close
     pub pool_member_list: PoolMemberList, // TokenPoolHeader + PoolMemberShareInfo*max_members
 }
 
@@ -27,9 +53,201 @@ pub enum TokenPoolStage {
     Uninitialized = 0,
     Initialized = 1,
     NFTOwned = 2,
+    BoughtOut = 3,
+}
+
+/// What a token pool is buying, a collection pool keeps its allowed mints list in `target_token`
+/// till it buys one of them. A basket pool keeps buying allowed nfts into its inventory and a
+/// fungible pool buys `target_token_amount` of the spl token `target_token`
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub enum PoolTargetKind {
+    #[default]
+    SingleMint = 0,
+    Collection = 1,
+    Basket = 2,
+    Fungible = 3,
+}
+
+/// How the maximum amount of a token pool is read, a percent caps a member at that percent of the target amount
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub enum MaximumKind {
+    #[default]
+    Amount = 0,
+    Percent = 1,
+}
+
+/// How much a new member has to contribute so the token pool can still reach its target with the slots left.
+/// with `LastMember` members contribute the minimum amount and the last free slot pays what is left,
+/// with `EvenSplit` every new member pays at least an even part of what is left over the free slots
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub enum AdmissionPolicy {
+    #[default]
+    LastMember = 0,
+    EvenSplit = 1,
 }
 
 impl TokenPool {
+    /// size of a token pool account with an empty description and no member slots
+    pub const BASE_LEN: usize = 1
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 4
+        + 32
+        + 32
+        + 32
+        + 8
+        + 32
+        + 1
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 8
+        + 1
+        + 8
+        + 1
+        + 8
+        + 32
+        + 1
+        + 32
+        + TokenPoolHeader::LEN
+        + 4;
+
+    /// decode a token pool account, a pool with the layout from before versioning has to be migrated first
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.first() != Some(&TOKEN_POOL_VERSION) {
+            msg!("token pool account has an outdated layout");
+            return Err(TokenPoolError::OutdatedLayout.into());
+        }
+        try_from_slice_unchecked::<TokenPool>(data).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// find if the token pool can buy an nft, a basket pool keeps buying after its first nft.
+    /// an oversubscribed pool has to close its subscription first
+    pub fn can_buy(&self) -> bool {
+        self.subscription_end == 0
+            && (self.stage == TokenPoolStage::Initialized
+                || (self.stage == TokenPoolStage::NFTOwned
+                    && self.target_kind == PoolTargetKind::Basket))
+    }
+
+    /// find if nothing is left to pay out of the token pool, its accounts can then be closed
+    pub fn is_finished(&self) -> bool {
+        (self.stage == TokenPoolStage::NFTOwned || self.stage == TokenPoolStage::BoughtOut)
+            && self.current_balance == 0
+            && !self.pool_member_list.has_members_left()
+    }
+
+    /// scale down what every member deposited so the pool keeps the target amount, the excess of
+    /// each member is due as a refund. returns the lamports refunded
+    pub fn refund_excess(&mut self) -> u64 {
+        if self.current_balance <= self.target_amount {
+            return 0;
+        }
+        let excess = self.current_balance - self.target_amount;
+        let mut refunded: u64 = 0;
+        for index in 0..self.pool_member_list.members.len() {
+            let member = self.pool_member_list.members[index];
+            if member.account_type != AccountType::TokenPoolMember {
+                continue;
+            }
+            let refund = (member.amount_deposited as u128 * excess as u128
+                / self.current_balance as u128) as u64;
+            let amount_deposited = member.amount_deposited - refund;
+            let share = self.find_share(amount_deposited).unwrap();
+            let member = &mut self.pool_member_list.members[index];
+            member.amount_deposited = amount_deposited;
+            member.share = share;
+            member.refund_due += refund;
+            refunded += refund;
+        }
+        self.current_balance -= refunded;
+        refunded
+    }
+
+    /// what is left of the current balance once the pool has bought is owed back to the members in
+    /// proportion to what they deposited, rounding leaves at most a lamport per member unowed in the
+    /// treasury. returns the lamports refunded
+    pub fn refund_leftover(&mut self) -> u64 {
+        let leftover = self.current_balance;
+        self.current_balance = 0;
+        let total_deposited = match self.pool_member_list.total_deposited() {
+            Some(total_deposited) if total_deposited > 0 => total_deposited,
+            _ => return 0,
+        };
+        let mut refunded: u64 = 0;
+        for member in self.pool_member_list.members.iter_mut() {
+            if member.account_type != AccountType::TokenPoolMember {
+                continue;
+            }
+            let refund = (member.amount_deposited as u128 * leftover as u128
+                / total_deposited as u128) as u64;
+            member.refund_due += refund;
+            refunded += refund;
+        }
+        refunded
+    }
+
+    /// most lamports a member can have deposited in the pool, none when the pool has no cap
+    pub fn maximum_contribution(&self) -> Option<u64> {
+        if self.maximum_amount == 0 {
+            return None;
+        }
+        match self.maximum_kind {
+            MaximumKind::Amount => Some(self.maximum_amount),
+            MaximumKind::Percent => Some(
+                (self.target_amount as u128 * self.maximum_amount as u128 / 100)
+                    .try_into()
+                    .unwrap_or(u64::MAX),
+            ),
+        }
+    }
+
+    /// members own what the pool bought in proportion to their shares, on the first buy the manager
+    /// share is carved out of every member's share and granted to the manager
+    pub fn settle_shares(&mut self) -> Result<(), TokenPoolError> {
+        self.pool_member_list.normalize_shares();
+        if self.stage != TokenPoolStage::Initialized || self.manager_share == 0 {
+            return Ok(());
+        }
+        let manager_share = units_to_share(self.manager_share);
+        self.pool_member_list
+            .scale_shares((100.0 - manager_share) / 100.0);
+        if !self.pool_member_list.find_member(self.manager) {
+            let index = self
+                .pool_member_list
+                .get_empty_member_index()
+                .ok_or(TokenPoolError::NoMemberSpaceLeft)?;
+            self.pool_member_list
+                .add_member(index, self.manager, 0, 0.0);
+        }
+        self.pool_member_list
+            .increase_by_minimum_exemption_share(self.manager, manager_share);
+        Ok(())
+    }
+
+    /// least a new member has to contribute to join the pool under its admission policy
+    pub fn minimum_admission(&self) -> u64 {
+        let remaining_amount = self.target_amount.saturating_sub(self.current_balance);
+        let remaining_slots = self.pool_member_list.empty_slots() as u64;
+        if remaining_slots <= 1 {
+            return remaining_amount;
+        }
+        let minimum_amount = self.minimum_amount.min(remaining_amount);
+        match self.admission_policy {
+            AdmissionPolicy::LastMember => minimum_amount,
+            AdmissionPolicy::EvenSplit => {
+                minimum_amount.max(remaining_amount.div_ceil(remaining_slots))
+            }
+        }
+    }
+
     /// find the share percent for the amount deposited in the pool
     pub fn find_share(&self, amount: u64) -> Option<f64> {
         let share = (amount as f64 / self.target_amount as f64) * 100 as f64;
@@ -86,12 +304,23 @@ pub struct PoolMemberShareInfo {
     pub share: f64,                // 8
     pub share_stage: ShareStage,   //1
     pub escrow: Pubkey,            //32
+    pub refund_due: u64,           //8 , excess of an oversubscribed pool owed to the member
+    pub delegate: Pubkey,          //32 , acts on the share for the member with its permissions
+    pub delegate_permissions: u8,  //1
 }
+
+/// Delegate can object buyouts for the member
+pub const DELEGATE_VOTE: u8 = 1;
+/// Delegate can sell the share of the member or cancel its sale, the member is paid
+pub const DELEGATE_SELL: u8 = 2;
+/// Delegate can claim the buyout payout and refund of the member, they are paid to the member
+pub const DELEGATE_CLAIM: u8 = 4;
+
 //add escrow
 impl Sealed for PoolMemberShareInfo {}
 
 impl Pack for PoolMemberShareInfo {
-    const LEN: usize = 1 + 32 + 8 + 8 + 1 + 32;
+    const LEN: usize = 1 + 32 + 8 + 8 + 1 + 32 + 8 + 32 + 1;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -120,6 +349,38 @@ impl PoolMemberShareInfo {
 }
 
 impl PoolMemberList {
+    /// find if any member still holds a share or has a refund due
+    pub fn has_members_left(&self) -> bool {
+        self.members.iter().any(|x| {
+            x.account_type == AccountType::TokenPoolMember && (x.share > 0.0 || x.refund_due > 0)
+        })
+    }
+
+    /// lamports deposited by the members together, none on overflow
+    pub fn total_deposited(&self) -> Option<u64> {
+        self.members
+            .iter()
+            .filter(|x| x.account_type == AccountType::TokenPoolMember)
+            .try_fold(0u64, |total, x| total.checked_add(x.amount_deposited))
+    }
+
+    /// lamports the members are due as refunds together, none on overflow
+    pub fn total_refund_due(&self) -> Option<u64> {
+        self.members
+            .iter()
+            .filter(|x| x.account_type == AccountType::TokenPoolMember)
+            .try_fold(0u64, |total, x| total.checked_add(x.refund_due))
+    }
+
+    /// total share of the members, less than 100 once members have redeemed their share
+    pub fn total_share(&self) -> f64 {
+        self.members
+            .iter()
+            .filter(|x| x.account_type == AccountType::TokenPoolMember)
+            .map(|x| x.share)
+            .sum()
+    }
+
     /// initializing the list with default values before assigning actual values
     pub fn new(max_members: u32) -> Self {
         Self {
@@ -174,6 +435,22 @@ impl PoolMemberList {
         self.members[*index].member_key = new_key;
     }
 
+    /// share owned by a key, zero when it is not a member
+    pub fn share_of(&self, member_key: Pubkey) -> f64 {
+        self.members
+            .iter()
+            .find(|x| x.member_key == member_key)
+            .map_or(0.0, |x| x.share)
+    }
+
+    /// lamports deposited by a key, zero when it is not a member
+    pub fn amount_deposited_by(&self, member_key: Pubkey) -> u64 {
+        self.members
+            .iter()
+            .find(|x| x.member_key == member_key)
+            .map_or(0, |x| x.amount_deposited)
+    }
+
     /// get the share of member in the token pool
     pub fn get_member_share(&mut self, member_key: Pubkey) -> f64 {
         let index = self
@@ -243,6 +520,14 @@ impl PoolMemberList {
             .position(|x| x.account_type == AccountType::Uninitialized)
     }
 
+    /// number of member slots which are not taken, slots freed by removed members count as well
+    pub fn empty_slots(&self) -> usize {
+        self.members
+            .iter()
+            .filter(|x| x.account_type == AccountType::Uninitialized)
+            .count()
+    }
+
     /// get member's index in the token pool member list
     pub fn get_member_index(&self, member_key: Pubkey) -> Option<usize> {
         let index = self.members.iter().position(|x| x.member_key == member_key);
@@ -264,15 +549,108 @@ impl PoolMemberList {
             share,
             share_stage: ShareStage::Hold,
             escrow: Pubkey::default(),
+            refund_due: 0,
+            delegate: Pubkey::default(),
+            delegate_permissions: 0,
         }
     }
 
+    /// let a delegate act on the share of a member with the permissions
+    pub fn approve_delegate(
+        &mut self,
+        member_key: Pubkey,
+        delegate: Pubkey,
+        permissions: u8,
+    ) -> Result<(), TokenPoolError> {
+        let index = self
+            .get_member_index(member_key)
+            .ok_or(TokenPoolError::MemberNotInPool)?;
+        self.members[index].delegate = delegate;
+        self.members[index].delegate_permissions = permissions;
+        Ok(())
+    }
+
+    /// remove the delegate of the share of a member
+    pub fn revoke_delegate(&mut self, member_key: Pubkey) -> Result<(), TokenPoolError> {
+        self.approve_delegate(member_key, Pubkey::default(), 0)
+    }
+
+    /// find if the delegate can act with the permission on the share held by the key
+    pub fn is_delegate_allowed(&self, share_key: Pubkey, delegate: Pubkey, permission: u8) -> bool {
+        self.get_member_index(share_key).is_some_and(|index| {
+            let member = &self.members[index];
+            member.delegate == delegate && member.delegate_permissions & permission != 0
+        })
+    }
+
     /// remove the member in the pool by making it uninitialized
     pub fn remove_member(&mut self, member_key: Pubkey) {
         let index = self.get_member_index(member_key).unwrap();
         self.members[index] = PoolMemberShareInfo::default();
     }
 
+    /// move a part of the share of a member to another key, merging it if the key is already a member.
+    /// amount deposited moves in proportion to the share
+    pub fn transfer_share(
+        &mut self,
+        from_key: Pubkey,
+        to_key: Pubkey,
+        share: f64,
+    ) -> Result<(), TokenPoolError> {
+        let from_index = self
+            .get_member_index(from_key)
+            .ok_or(TokenPoolError::MemberNotInPool)?;
+        let from = self.members[from_index];
+        if share <= 0.0 || share > from.share {
+            return Err(TokenPoolError::InsufficientShare);
+        }
+        let is_whole_share = share == from.share;
+        let amount = if is_whole_share {
+            from.amount_deposited
+        } else {
+            (from.amount_deposited as f64 * share / from.share) as u64
+        };
+
+        if self.find_member(to_key) {
+            self.increase_member_share(share, to_key, amount);
+            if is_whole_share {
+                self.remove_member(from_key);
+                return Ok(());
+            }
+        } else if is_whole_share {
+            // delegate of the member does not act for the new holder
+            self.update_key(from_key, to_key);
+            return self.revoke_delegate(to_key);
+        } else {
+            let index = self
+                .get_empty_member_index()
+                .ok_or(TokenPoolError::NoMemberSpaceLeft)?;
+            self.add_member(index, to_key, amount, share);
+        }
+
+        self.members[from_index].share -= share;
+        self.members[from_index].amount_deposited -= amount;
+        Ok(())
+    }
+
+    /// scale the shares of members so that together they own 100% of the pool
+    pub fn normalize_shares(&mut self) {
+        let total_share = self.total_share();
+        if total_share == 0.0 || total_share == 100.0 {
+            return;
+        }
+        for member in self.members.iter_mut() {
+            member.share = member.share / total_share * 100.0;
+        }
+    }
+
+    /// scale the share of every member by the factor
+    pub fn scale_shares(&mut self, factor: f64) {
+        for member in self.members.iter_mut() {
+            member.share *= factor;
+        }
+    }
+
     /// calculating the maximum members that can occupy the pool
     pub fn calculate_max_members(buffer_length: usize) -> usize {
         let header_size = TokenPoolHeader::LEN + 4; // adding extra 4 for metadata , need to confirm
@@ -295,22 +673,72 @@ impl Default for EscrowStage {
     }
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub enum ListingKind {
+    #[default]
+    FixedPrice = 0,
+    EnglishAuction = 1,
+    DutchAuction = 2,
+    Tokens = 3,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
 pub struct Escrow {
-    pub stage: EscrowStage,   //1
-    pub seller: Pubkey,       //32
-    pub buyer: Pubkey,        //32
-    pub escrow_vault: Pubkey, //32
-    pub share: f64,           //32
-    pub nft: Pubkey,          //32
-    pub nft_mint: Pubkey,     //32
-    pub amount: u64,          //8
+    pub stage: EscrowStage,        //1
+    pub seller: Pubkey,            //32
+    pub allowed_buyer: Pubkey,     //32 , only buyer of a share sale, anyone can buy when default
+    pub escrow_vault: Pubkey,      //32
+    pub share: f64,                //32
+    pub nft: Pubkey,               //32
+    pub nft_mint: Pubkey,          //32
+    pub amount: u64,               //8 , price of the listing or reserve price of an auction
+    pub listing: ListingKind,      //1
+    pub end_time: UnixTimestamp,   //8
+    pub min_bid_increment: u64,    //8
+    pub highest_bid: u64,          //8
+    pub highest_bidder: Pubkey,    //32 , bidder's wallet or vault of the token pool bidding
+    pub bidder_pool: Pubkey,       //32 , token pool bidding with its treasury
+    pub start_time: UnixTimestamp, //8
+    pub floor_price: u64,          //8
+    pub token_amount: u64,         //8 , tokens in the listed token account
+    pub expires_at: UnixTimestamp, //8 , zero when the escrow never expires
+    pub rent_payer: Pubkey,        //32 , paid the rent of the escrow, gets it back on close
+}
+
+impl Escrow {
+    /// find if the escrow has expired at the given time
+    pub fn is_expired(&self, now: UnixTimestamp) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// minimum bid needed to become the highest bidder of an auction
+    pub fn minimum_bid(&self) -> Option<u64> {
+        if self.highest_bid == 0 {
+            Some(self.amount)
+        } else {
+            self.highest_bid.checked_add(self.min_bid_increment)
+        }
+    }
+
+    /// price of a dutch auction at the given time, decaying linearly from amount to floor price
+    pub fn current_price(&self, now: UnixTimestamp) -> Option<u64> {
+        if now >= self.end_time {
+            return Some(self.floor_price);
+        }
+        let elapsed = now.checked_sub(self.start_time)?.max(0) as u128;
+        let duration = self.end_time.checked_sub(self.start_time)? as u128;
+        let decayed = (self.amount.checked_sub(self.floor_price)? as u128)
+            .checked_mul(elapsed)?
+            .checked_div(duration)?;
+        self.amount.checked_sub(decayed as u64)
+    }
 }
 
 impl Sealed for Escrow {}
 
 impl Pack for Escrow {
-    const LEN: usize = 1 + 32 + 32 + 32 + 32 + 32 + 32 + 8;
+    const LEN: usize =
+        1 + 32 + 32 + 32 + 32 + 32 + 32 + 8 + 1 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 32;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -325,3 +753,515 @@ impl Pack for Escrow {
         })
     }
 }
+
+/// Most bytes the description of a token pool can have
+pub const MAX_DESCRIPTION_LEN: usize = 200;
+
+/// Most members a token pool can have, the program creates the token pool account through a cross
+/// program invocation which can allocate at most `MAX_PERMITTED_DATA_INCREASE` bytes
+pub const MAX_POOL_MEMBERS: u32 =
+    ((MAX_PERMITTED_DATA_INCREASE - TokenPool::BASE_LEN - MAX_DESCRIPTION_LEN)
+        / PoolMemberShareInfo::LEN) as u32;
+
+/// number of members a token pool needs so that it reaches the target amount when each member
+/// contributes the minimum amount, none when the minimum amount is zero or too small for it
+pub fn max_members_for(target_amount: u64, minimum_amount: u64) -> Option<u32> {
+    if minimum_amount == 0 {
+        return None;
+    }
+    target_amount.div_ceil(minimum_amount).try_into().ok()
+}
+
+/// Config of the platform, set by its authority, limits the token pools created on it
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct PlatformConfig {
+    pub is_initialized: bool, //1
+    pub authority: Pubkey,    //32
+    pub max_members: u32,     //4 , most members a new token pool can have
+    pub paused: bool, //1 , contributions, trading, listings, purchases and buyouts are paused in every pool
+}
+
+impl Sealed for PlatformConfig {}
+
+impl Pack for PlatformConfig {
+    const LEN: usize = 1 + 32 + 4 + 1;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut p = src;
+        PlatformConfig::deserialize(&mut p).map_err(|_| {
+            msg!("Failed to deserialize");
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+/// Most signers a multisig can have
+pub const MAX_SIGNERS: usize = 11;
+
+/// M of N signers acting as one authority, a token pool can be managed by a multisig
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct Multisig {
+    pub is_initialized: bool,           //1
+    pub m: u8,                          //1 , signers needed to act
+    pub n: u8,                          //1 , signers of the multisig
+    pub signers: [Pubkey; MAX_SIGNERS], //32 * MAX_SIGNERS
+}
+
+impl Multisig {
+    /// find the position of a key among the signers of the multisig
+    pub fn signer_position(&self, key: &Pubkey) -> Option<usize> {
+        self.signers[..self.n as usize]
+            .iter()
+            .position(|signer| signer == key)
+    }
+}
+
+impl Sealed for Multisig {}
+
+impl Pack for Multisig {
+    const LEN: usize = 1 + 1 + 1 + 32 * MAX_SIGNERS;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut p = src;
+        Multisig::deserialize(&mut p).map_err(|_| {
+            msg!("Failed to deserialize");
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+/// Share units in 100% of a token pool, a unit is one basis point of the pool
+pub const TOTAL_SHARE_UNITS: u64 = 10_000;
+
+/// convert a share percentage into share units
+pub fn share_to_units(share: f64) -> u64 {
+    (share * (TOTAL_SHARE_UNITS / 100) as f64).round() as u64
+}
+
+/// convert share units into a share percentage
+pub fn units_to_share(units: u64) -> f64 {
+    units as f64 / (TOTAL_SHARE_UNITS / 100) as f64
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub enum ShareBidStage {
+    #[default]
+    Uninitialized = 0,
+    Open = 1,
+}
+
+/// Standing bid to buy share units of a token pool, lamports for the bid are kept in the bid account
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct ShareBid {
+    pub stage: ShareBidStage, //1
+    pub token_pool: Pubkey,   //32
+    pub bidder: Pubkey,       //32
+    pub price_per_unit: u64,  //8
+    pub quantity: u64,        //8 , share units still wanted
+}
+
+impl Sealed for ShareBid {}
+
+impl Pack for ShareBid {
+    const LEN: usize = 1 + 32 + 32 + 8 + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut p = src;
+        ShareBid::deserialize(&mut p).map_err(|_| {
+            msg!("Failed to deserialize");
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+/// List of the nft mints a collection token pool is allowed to buy
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct AllowedMints {
+    pub token_pool: Pubkey, //32
+    pub rent_payer: Pubkey, //32 , manager who set up the list, gets the rent back on close
+    pub mints: Vec<Pubkey>, // 4 + 32*mints
+}
+
+impl AllowedMints {
+    const HEADER_LEN: usize = 32 + 32 + 4;
+
+    /// number of mints which fit in an allowed mints account of the given size
+    pub fn capacity(buffer_length: usize) -> usize {
+        buffer_length.saturating_sub(Self::HEADER_LEN) / PUBKEY_BYTES
+    }
+
+    /// size of an allowed mints account holding the given number of mints
+    pub fn account_size(mints: usize) -> usize {
+        Self::HEADER_LEN + mints * PUBKEY_BYTES
+    }
+
+    /// find if the mint is allowed
+    pub fn contains(&self, mint: &Pubkey) -> bool {
+        self.mints.iter().any(|x| x == mint)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct InventoryItem {
+    pub mint: Pubkey, //32
+    pub nft: Pubkey,  //32 , token account holding the nft
+    pub price: u64,   //8 , lamports paid by the pool
+}
+
+/// Nfts owned by a basket token pool, members own all of them in proportion to their shares
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct Inventory {
+    pub token_pool: Pubkey,        //32
+    pub rent_payer: Pubkey,        //32 , manager who set up the basket, gets the rent back on close
+    pub items: Vec<InventoryItem>, // 4 + 72*items
+}
+
+impl Inventory {
+    const HEADER_LEN: usize = 32 + 32 + 4;
+    const ITEM_LEN: usize = 32 + 32 + 8;
+
+    /// number of nfts which fit in an inventory account of the given size
+    pub fn capacity(buffer_length: usize) -> usize {
+        buffer_length.saturating_sub(Self::HEADER_LEN) / Self::ITEM_LEN
+    }
+
+    /// size of an inventory account holding the given number of nfts
+    pub fn account_size(items: usize) -> usize {
+        Self::HEADER_LEN + items * Self::ITEM_LEN
+    }
+
+    /// find the index of an nft mint in the inventory
+    pub fn get_item_index(&self, mint: &Pubkey) -> Option<usize> {
+        self.items.iter().position(|x| x.mint == *mint)
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub enum BuyoutStage {
+    #[default]
+    Uninitialized = 0,
+    Active = 1,
+    Succeeded = 2,
+}
+
+/// Offer to buy the nft of a token pool from all of its members, kept in a pda of the token pool
+/// along with the lamports for the shares of the other members
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, PartialEq)]
+pub struct Buyout {
+    pub stage: BuyoutStage,      //1
+    pub token_pool: Pubkey,      //32
+    pub buyer: Pubkey,           //32
+    pub price: u64,              //8 , lamports for 100% of the pool
+    pub deposit: u64,            //8 , lamports for the shares buyer does not own
+    pub end_time: UnixTimestamp, //8 , end of the objection window
+    pub objection_share: f64,    //8 , share of the members who have objected
+    pub objectors: Vec<Pubkey>,  // 4 + 32*max_members
+}
+
+impl Buyout {
+    const HEADER_LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8 + 4;
+
+    /// size of a buyout account for a token pool of `max_members`
+    pub fn account_size(max_members: u32) -> usize {
+        Self::HEADER_LEN + max_members as usize * PUBKEY_BYTES
+    }
+
+    /// lamports buyer deposits for the shares of the other members
+    pub fn deposit_for(price: u64, buyer_share: f64) -> u64 {
+        (price as f64 * (100.0 - buyer_share) / 100.0).ceil() as u64
+    }
+
+    /// buyout fails when members owning half of the shares buyer does not own object to it
+    pub fn is_rejected(&self, buyer_share: f64) -> bool {
+        self.objection_share * 2.0 >= 100.0 - buyer_share
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use solana_program::borsh::get_instance_packed_len;
+
+    pub(crate) fn token_pool(description: &str, max_members: u32) -> TokenPool {
+        TokenPool {
+            version: TOKEN_POOL_VERSION,
+            stage: TokenPoolStage::Initialized,
+            target_amount: 1_000,
+            minimum_exemption_amount: 0,
+            minimum_amount: 100,
+            current_balance: 0,
+            target_token: Pubkey::default(),
+            description: description.to_string(),
+            vault: Pubkey::default(),
+            manager: Pubkey::new_unique(),
+            treasurey: Pubkey::default(),
+            target_kind: PoolTargetKind::SingleMint,
+            inventory: Pubkey::default(),
+            target_token_amount: 0,
+            reserve_price: 0,
+            objection_period: 0,
+            gate_mint: Pubkey::default(),
+            maximum_amount: 0,
+            maximum_kind: MaximumKind::Amount,
+            manager_share: 0,
+            admission_policy: AdmissionPolicy::LastMember,
+            subscription_end: 0,
+            pending_manager: Pubkey::default(),
+            paused: false,
+            rent_payer: Pubkey::default(),
+            committed_bid: 0,
+            bid_escrow: Pubkey::default(),
+            buyout_end: 0,
+            pool_member_list: PoolMemberList::new(max_members),
+        }
+    }
+
+    #[test]
+    fn base_len_is_the_size_of_an_empty_token_pool() {
+        assert_eq!(
+            get_instance_packed_len(&token_pool("", 0)).unwrap(),
+            TokenPool::BASE_LEN
+        );
+    }
+
+    #[test]
+    fn largest_token_pool_fits_in_what_the_program_can_allocate() {
+        let description = "x".repeat(MAX_DESCRIPTION_LEN);
+        let largest = token_pool(&description, MAX_POOL_MEMBERS);
+        assert!(get_instance_packed_len(&largest).unwrap() <= MAX_PERMITTED_DATA_INCREASE);
+        let one_more = token_pool(&description, MAX_POOL_MEMBERS + 1);
+        assert!(get_instance_packed_len(&one_more).unwrap() > MAX_PERMITTED_DATA_INCREASE);
+    }
+
+    fn dutch_auction() -> Escrow {
+        Escrow {
+            listing: ListingKind::DutchAuction,
+            amount: 1_000,
+            floor_price: 200,
+            start_time: 100,
+            end_time: 200,
+            ..Escrow::default()
+        }
+    }
+
+    #[test]
+    fn current_price_decays_from_amount_to_floor_price() {
+        let escrow = dutch_auction();
+        assert_eq!(escrow.current_price(100), Some(1_000));
+        assert_eq!(escrow.current_price(150), Some(600));
+        assert_eq!(escrow.current_price(200), Some(200));
+        assert_eq!(escrow.current_price(300), Some(200));
+    }
+
+    #[test]
+    fn minimum_bid_is_reserve_price_then_highest_bid_plus_increment() {
+        let mut escrow = Escrow {
+            listing: ListingKind::EnglishAuction,
+            amount: 500,
+            min_bid_increment: 50,
+            ..Escrow::default()
+        };
+        assert_eq!(escrow.minimum_bid(), Some(500));
+        escrow.highest_bid = 600;
+        assert_eq!(escrow.minimum_bid(), Some(650));
+        escrow.highest_bid = u64::MAX;
+        assert_eq!(escrow.minimum_bid(), None);
+    }
+
+    #[test]
+    fn current_price_before_start_is_amount() {
+        assert_eq!(dutch_auction().current_price(50), Some(1_000));
+    }
+
+    #[test]
+    fn leftover_is_refunded_in_proportion_to_deposits() {
+        let mut pool = token_pool("", 3);
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        pool.pool_member_list.add_member(0, first, 300, 30.0);
+        pool.pool_member_list.add_member(1, second, 100, 10.0);
+        pool.current_balance = 203;
+
+        assert_eq!(pool.refund_leftover(), 202);
+        assert_eq!(pool.current_balance, 0);
+        assert_eq!(pool.pool_member_list.members[0].refund_due, 152);
+        assert_eq!(pool.pool_member_list.members[1].refund_due, 50);
+    }
+
+    #[test]
+    fn buyout_is_rejected_by_half_of_the_shares_buyer_does_not_own() {
+        let mut buyout = Buyout {
+            objection_share: 39.0,
+            ..Buyout::default()
+        };
+        assert!(!buyout.is_rejected(20.0));
+        buyout.objection_share = 40.0;
+        assert!(buyout.is_rejected(20.0));
+        assert!(!buyout.is_rejected(0.0));
+    }
+
+    #[test]
+    fn transfer_share_moves_deposit_in_proportion_and_merges_whole_shares() {
+        let mut members = PoolMemberList::new(3);
+        let from = Pubkey::new_unique();
+        let to = Pubkey::new_unique();
+        members.add_member(0, from, 400, 40.0);
+
+        members.transfer_share(from, to, 10.0).unwrap();
+        assert_eq!(members.amount_deposited_by(from), 300);
+        assert_eq!(members.share_of(from), 30.0);
+        assert_eq!(members.amount_deposited_by(to), 100);
+        assert_eq!(members.share_of(to), 10.0);
+
+        members.transfer_share(to, from, 10.0).unwrap();
+        assert!(!members.find_member(to));
+        assert_eq!(members.amount_deposited_by(from), 400);
+        assert_eq!(members.share_of(from), 40.0);
+
+        assert_eq!(
+            members.transfer_share(from, to, 40.5),
+            Err(TokenPoolError::InsufficientShare)
+        );
+    }
+
+    #[test]
+    fn whole_share_transferred_to_a_new_key_drops_the_delegate() {
+        let mut members = PoolMemberList::new(2);
+        let from = Pubkey::new_unique();
+        let to = Pubkey::new_unique();
+        members.add_member(0, from, 400, 40.0);
+        members
+            .approve_delegate(from, Pubkey::new_unique(), DELEGATE_SELL)
+            .unwrap();
+
+        members.transfer_share(from, to, 40.0).unwrap();
+        assert!(!members.find_member(from));
+        let member = members.members[members.get_member_index(to).unwrap()];
+        assert_eq!(member.amount_deposited, 400);
+        assert_eq!(member.delegate_permissions, 0);
+    }
+
+    #[test]
+    fn manager_share_is_carved_out_of_every_member_share_on_the_first_buy() {
+        let mut pool = token_pool("", 3);
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        pool.pool_member_list.add_member(0, first, 600, 60.0);
+        pool.pool_member_list.add_member(1, second, 400, 40.0);
+        pool.manager_share = 1_000;
+
+        pool.settle_shares().unwrap();
+        let members = &pool.pool_member_list;
+        assert!((members.share_of(first) - 54.0).abs() < 1e-9);
+        assert!((members.share_of(second) - 36.0).abs() < 1e-9);
+        assert!((members.share_of(pool.manager) - 10.0).abs() < 1e-9);
+        assert_eq!(members.amount_deposited_by(pool.manager), 0);
+    }
+
+    #[test]
+    fn settling_after_the_first_buy_only_normalizes_shares() {
+        let mut pool = token_pool("", 2);
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        pool.pool_member_list.add_member(0, first, 300, 30.0);
+        pool.pool_member_list.add_member(1, second, 200, 20.0);
+        pool.manager_share = 1_000;
+        pool.stage = TokenPoolStage::NFTOwned;
+
+        pool.settle_shares().unwrap();
+        assert!((pool.pool_member_list.share_of(first) - 60.0).abs() < 1e-9);
+        assert!((pool.pool_member_list.share_of(second) - 40.0).abs() < 1e-9);
+        assert!(!pool.pool_member_list.find_member(pool.manager));
+    }
+
+    #[test]
+    fn manager_share_needs_a_free_member_slot() {
+        let mut pool = token_pool("", 1);
+        pool.pool_member_list
+            .add_member(0, Pubkey::new_unique(), 1_000, 100.0);
+        pool.manager_share = 1_000;
+        assert_eq!(pool.settle_shares(), Err(TokenPoolError::NoMemberSpaceLeft));
+    }
+
+    #[test]
+    fn max_members_reach_the_target_with_minimum_contributions() {
+        assert_eq!(max_members_for(1_000, 100), Some(10));
+        assert_eq!(max_members_for(1_000, 300), Some(4));
+        assert_eq!(max_members_for(1_000, 0), None);
+        assert_eq!(max_members_for(u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn minimum_admission_follows_the_admission_policy() {
+        let mut pool = token_pool("", 4);
+        assert_eq!(pool.minimum_admission(), 100);
+        pool.admission_policy = AdmissionPolicy::EvenSplit;
+        assert_eq!(pool.minimum_admission(), 250);
+
+        pool.pool_member_list
+            .add_member(0, Pubkey::new_unique(), 500, 50.0);
+        pool.pool_member_list
+            .add_member(1, Pubkey::new_unique(), 450, 45.0);
+        pool.current_balance = 950;
+        // minimum amount never asks for more than the pool still needs
+        assert_eq!(pool.minimum_admission(), 50);
+        pool.admission_policy = AdmissionPolicy::LastMember;
+        assert_eq!(pool.minimum_admission(), 50);
+    }
+
+    #[test]
+    fn last_free_slot_has_to_fill_the_pool() {
+        let mut pool = token_pool("", 3);
+        pool.pool_member_list
+            .add_member(0, Pubkey::new_unique(), 300, 30.0);
+        pool.pool_member_list
+            .add_member(1, Pubkey::new_unique(), 300, 30.0);
+        pool.current_balance = 600;
+        assert_eq!(pool.minimum_admission(), 400);
+    }
+
+    #[test]
+    fn excess_is_refunded_in_proportion_to_deposits() {
+        let mut pool = token_pool("", 2);
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        pool.pool_member_list.add_member(0, first, 800, 80.0);
+        pool.pool_member_list.add_member(1, second, 400, 40.0);
+        pool.current_balance = 1_200;
+
+        assert_eq!(pool.refund_excess(), 199);
+        assert_eq!(pool.current_balance, 1_001);
+        let members = &pool.pool_member_list;
+        assert_eq!(members.members[0].refund_due, 133);
+        assert_eq!(members.amount_deposited_by(first), 667);
+        assert!((members.share_of(first) - 66.7).abs() < 1e-9);
+        assert_eq!(members.members[1].refund_due, 66);
+        assert_eq!(members.amount_deposited_by(second), 334);
+        assert!((members.share_of(second) - 33.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nothing_is_refunded_up_to_the_target() {
+        let mut pool = token_pool("", 2);
+        pool.pool_member_list
+            .add_member(0, Pubkey::new_unique(), 1_000, 100.0);
+        pool.current_balance = 1_000;
+        assert_eq!(pool.refund_excess(), 0);
+        assert_eq!(pool.pool_member_list.members[0].refund_due, 0);
+    }
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use {
+    common::{custom, Test, WALLET_LAMPORTS},
+    solana_program::{instruction::InstructionError, program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::{signature::Signer, transaction::TransactionError},
+    token_pool::{
+        error::TokenPoolError,
+        state::{Escrow, PoolTargetKind},
+    },
+    token_pool_client::instruction,
+};
+
+#[tokio::test]
+async fn pool_outbids_a_wallet_with_its_treasury() {
+    let mut test = Test::start().await;
+    let auction = test.auction(500_000, 100).await;
+    let pool = test
+        .create_pool(&auction.nft_mint, 1_000_000, 100_000)
+        .await;
+    let first = test.wallet();
+    let second = test.wallet();
+    test.join(&pool, &first, 600_000).await;
+    test.join(&pool, &second, 400_000).await;
+    let escrow_lamports = test.lamports(&auction.address).await;
+    let treasury_lamports = test.lamports(&pool.treasury).await;
+
+    let bidder = test.wallet();
+    test.bid(&auction, &bidder, 500_000).await.unwrap();
+    assert_eq!(
+        test.lamports(&bidder.pubkey()).await,
+        WALLET_LAMPORTS - 500_000
+    );
+    assert_eq!(
+        test.lamports(&auction.address).await,
+        escrow_lamports + 500_000
+    );
+
+    // a bid of the pool has to beat the highest one by the increment
+    assert_eq!(
+        test.bid_with_pool(&auction, &pool, &first, 505_000).await,
+        Err(custom(TokenPoolError::BidTooLow))
+    );
+    test.bid_with_pool(&auction, &pool, &first, 600_000)
+        .await
+        .unwrap();
+
+    // the outbid wallet is refunded and the bid of the pool never leaves its treasury
+    assert_eq!(test.lamports(&bidder.pubkey()).await, WALLET_LAMPORTS);
+    assert_eq!(test.lamports(&auction.address).await, escrow_lamports);
+    assert_eq!(test.lamports(&pool.treasury).await, treasury_lamports);
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.committed_bid, 600_000);
+    assert_eq!(token_pool.bid_escrow, auction.address);
+    let escrow = test.escrow(&auction.address).await;
+    assert_eq!(escrow.highest_bid, 600_000);
+    assert_eq!(escrow.highest_bidder, token_pool.vault);
+    assert_eq!(escrow.bidder_pool, pool.address);
+}
+
+#[tokio::test]
+async fn outbid_pool_gets_its_committed_bid_back() {
+    let mut test = Test::start().await;
+    let auction = test.auction(500_000, 100).await;
+    let pool = test
+        .create_pool(&auction.nft_mint, 1_000_000, 100_000)
+        .await;
+    let member = test.wallet();
+    test.join(&pool, &member, 900_000).await;
+    test.bid_with_pool(&auction, &pool, &member, 500_000)
+        .await
+        .unwrap();
+
+    // raising its own bid replaces the committed one
+    test.bid_with_pool(&auction, &pool, &member, 550_000)
+        .await
+        .unwrap();
+    assert_eq!(test.token_pool(&pool.address).await.committed_bid, 550_000);
+
+    let bidder = test.wallet();
+    test.bid(&auction, &bidder, 560_000).await.unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.committed_bid, 0);
+    assert_eq!(token_pool.bid_escrow, Pubkey::default());
+    let escrow = test.escrow(&auction.address).await;
+    assert_eq!(escrow.highest_bidder, bidder.pubkey());
+    assert_eq!(escrow.bidder_pool, Pubkey::default());
+}
+
+#[tokio::test]
+async fn pool_bids_on_one_auction_at_a_time() {
+    let mut test = Test::start().await;
+    let first_auction = test.auction(500_000, 100).await;
+    let second_auction = test.auction(500_000, 100).await;
+    let pool = test
+        .create_pool(&first_auction.nft_mint, 1_000_000, 100_000)
+        .await;
+    let member = test.wallet();
+    test.join(&pool, &member, 900_000).await;
+    test.bid_with_pool(&first_auction, &pool, &member, 500_000)
+        .await
+        .unwrap();
+
+    // the pool also targets the nft of the second auction, as a collection pool would
+    test.update_pool(&pool, |token_pool| {
+        token_pool.target_token = second_auction.nft_mint
+    })
+    .await;
+    assert_eq!(
+        test.bid_with_pool(&second_auction, &pool, &member, 500_000)
+            .await,
+        Err(custom(TokenPoolError::BidCommitted))
+    );
+
+    let bidder = test.wallet();
+    test.bid(&first_auction, &bidder, 510_000).await.unwrap();
+    test.bid_with_pool(&second_auction, &pool, &member, 500_000)
+        .await
+        .unwrap();
+    assert_eq!(
+        test.token_pool(&pool.address).await.bid_escrow,
+        second_auction.address
+    );
+}
+
+#[tokio::test]
+async fn only_members_bid_with_the_pool_and_only_what_it_holds() {
+    let mut test = Test::start().await;
+    let auction = test.auction(500_000, 100).await;
+    let pool = test
+        .create_pool(&auction.nft_mint, 1_000_000, 100_000)
+        .await;
+    let member = test.wallet();
+    test.join(&pool, &member, 400_000).await;
+
+    let stranger = test.wallet();
+    assert_eq!(
+        test.bid_with_pool(&auction, &pool, &stranger, 500_000)
+            .await,
+        Err(custom(TokenPoolError::MemberNotInPool))
+    );
+    assert_eq!(
+        test.bid_with_pool(&auction, &pool, &member, 500_000).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InsufficientFunds
+        ))
+    );
+
+    let end_time = test.escrow(&auction.address).await.end_time;
+    test.warp_to(end_time).await;
+    let bidder = test.wallet();
+    assert_eq!(
+        test.bid(&auction, &bidder, 500_000).await,
+        Err(custom(TokenPoolError::AuctionEnded))
+    );
+}
+
+#[tokio::test]
+async fn listed_escrow_can_not_be_listed_again() {
+    let mut test = Test::start().await;
+    let auction = test.auction(500_000, 100).await;
+    let escrow = test.escrow(&auction.address).await;
+    let seller = test.wallet();
+
+    let list = instruction::list_nft(
+        &seller.pubkey(),
+        &auction.address,
+        &escrow.nft_mint,
+        &escrow.nft,
+        1,
+        0,
+    );
+    assert_eq!(
+        test.send(&[list], &[&seller]).await,
+        Err(custom(TokenPoolError::InvalidEscrowStage))
+    );
+    assert_eq!(test.escrow(&auction.address).await, escrow);
+}
+
+#[tokio::test]
+async fn settling_checks_the_allowed_mints_of_a_winning_collection_pool() {
+    let mut test = Test::start().await;
+    let auction = test.auction(500_000, 100).await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let member = test.wallet();
+    test.join(&pool, &member, 900_000).await;
+    test.update_pool(&pool, |token_pool| {
+        token_pool.target_kind = PoolTargetKind::Collection
+    })
+    .await;
+    let token_pool = test.token_pool(&pool.address).await;
+
+    // the pool won the auction, as a bid of the pool leaves the escrow once it has ended
+    let mut escrow = test.escrow(&auction.address).await;
+    escrow.highest_bid = 500_000;
+    escrow.highest_bidder = token_pool.vault;
+    escrow.bidder_pool = pool.address;
+    escrow.end_time = test.now().await;
+    test.set_state_with_len(&auction.address, &escrow, Escrow::LEN, 0);
+
+    let settle = instruction::settle_auction(&auction.address, &escrow, Some(&token_pool));
+    assert_eq!(
+        settle.accounts.last().unwrap().pubkey,
+        token_pool.target_token
+    );
+    let mut wrong_list = settle.clone();
+    wrong_list.accounts.last_mut().unwrap().pubkey = Pubkey::new_unique();
+    assert_eq!(
+        test.send(&[wrong_list], &[]).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    let mut without_list = settle;
+    without_list.accounts.pop();
+    assert_eq!(
+        test.send(&[without_list], &[]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::NotEnoughAccountKeys
+        ))
+    );
+}
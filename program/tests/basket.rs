@@ -0,0 +1,266 @@
+mod common;
+
+use {
+    common::{custom, Pool, Test},
+    solana_program::{instruction::InstructionError, program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    token_pool::{
+        error::TokenPoolError,
+        state::{
+            AllowedMints, Escrow, EscrowStage, Inventory, InventoryItem, ListingKind,
+            PoolTargetKind, TokenPoolStage,
+        },
+    },
+    token_pool_client::instruction,
+};
+
+/// A collection token pool allowed to buy its target mint and `mint`
+async fn collection_pool(test: &mut Test, mint: &Pubkey) -> Pool {
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let allowed_mints = test.program_account(AllowedMints::account_size(2));
+    test.send(
+        &[instruction::add_allowed_mints(
+            &pool.manager.pubkey(),
+            &pool.address,
+            &allowed_mints,
+            &[*mint],
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    pool
+}
+
+/// A basket pool whose single member bought the nfts of its inventory, room is left for `capacity`
+/// nfts and 400_000 lamports for the next ones
+async fn basket_owning(
+    test: &mut Test,
+    mint: &Pubkey,
+    items: Vec<InventoryItem>,
+    capacity: usize,
+) -> (Pool, Keypair, Pubkey) {
+    let pool = collection_pool(test, mint).await;
+    let inventory = test.program_account(Inventory::account_size(capacity));
+    test.send(
+        &[instruction::initialize_basket(
+            &pool.manager.pubkey(),
+            &pool.address,
+            &inventory,
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    let member = test.wallet();
+    test.join(&pool, &member, 1_000_000).await;
+    test.update_pool(&pool, |token_pool| {
+        token_pool.settle_shares().unwrap();
+        token_pool.stage = TokenPoolStage::NFTOwned;
+        token_pool.current_balance = 400_000;
+    })
+    .await;
+    let inventory_state = Inventory {
+        token_pool: pool.address,
+        rent_payer: pool.manager.pubkey(),
+        items,
+    };
+    test.set_state_with_len(
+        &inventory,
+        &inventory_state,
+        Inventory::account_size(capacity),
+        0,
+    );
+    (pool, member, inventory)
+}
+
+fn item(mint: &Pubkey) -> InventoryItem {
+    InventoryItem {
+        mint: *mint,
+        nft: Pubkey::new_unique(),
+        price: 600_000,
+    }
+}
+
+#[tokio::test]
+async fn basket_is_made_from_a_collection_pool_by_its_manager() {
+    let mut test = Test::start().await;
+    let mint = Pubkey::new_unique();
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let inventory = test.program_account(Inventory::account_size(2));
+    let initialize = |manager: &Pubkey, pool: &Pool, inventory: &Pubkey| {
+        instruction::initialize_basket(manager, &pool.address, inventory)
+    };
+
+    assert_eq!(
+        test.send(
+            &[initialize(&pool.manager.pubkey(), &pool, &inventory)],
+            &[&pool.manager]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    let pool = collection_pool(&mut test, &mint).await;
+    let no_room = test.program_account(Inventory::account_size(0));
+    assert_eq!(
+        test.send(
+            &[initialize(&pool.manager.pubkey(), &pool, &no_room)],
+            &[&pool.manager]
+        )
+        .await,
+        Err(custom(TokenPoolError::InventoryFull))
+    );
+    assert_eq!(
+        test.send(
+            &[initialize(
+                &pool.manager.pubkey(),
+                &pool,
+                &Pubkey::new_unique()
+            )],
+            &[&pool.manager]
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IllegalOwner
+        ))
+    );
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[initialize(&stranger.pubkey(), &pool, &inventory)],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+
+    test.send(
+        &[initialize(&pool.manager.pubkey(), &pool, &inventory)],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.target_kind, PoolTargetKind::Basket);
+    assert_eq!(token_pool.inventory, inventory);
+    assert_eq!(
+        test.state::<Inventory>(&inventory).await,
+        Inventory {
+            token_pool: pool.address,
+            rent_payer: pool.manager.pubkey(),
+            items: Vec::new(),
+        }
+    );
+
+    // a pool has one inventory
+    let other_inventory = test.program_account(Inventory::account_size(2));
+    assert_eq!(
+        test.send(
+            &[initialize(&pool.manager.pubkey(), &pool, &other_inventory)],
+            &[&pool.manager]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+}
+
+#[tokio::test]
+async fn basket_buys_allowed_nfts_while_its_treasury_and_inventory_last() {
+    let mut test = Test::start().await;
+    let mint = Pubkey::new_unique();
+    let (pool, member, _) = basket_owning(&mut test, &mint, vec![item(&mint)], 1).await;
+    let mut list = |nft_mint: Pubkey, price| {
+        let address = Pubkey::new_unique();
+        let seller = test.wallet();
+        let escrow = Escrow {
+            stage: EscrowStage::Initialized,
+            seller: seller.pubkey(),
+            escrow_vault: Pubkey::new_unique(),
+            share: 100.0,
+            nft: Pubkey::new_unique(),
+            nft_mint,
+            amount: price,
+            listing: ListingKind::FixedPrice,
+            rent_payer: seller.pubkey(),
+            ..Escrow::default()
+        };
+        test.set_state_with_len(&address, &escrow, Escrow::LEN, 0);
+        (address, escrow)
+    };
+    let listings = [
+        list(Pubkey::new_unique(), 300_000),
+        list(mint, 500_000),
+        list(mint, 300_000),
+    ];
+
+    let token_pool = test.token_pool(&pool.address).await;
+    let mut results = Vec::new();
+    for (address, escrow) in &listings {
+        let instruction = instruction::execute_nft_buy(
+            &member.pubkey(),
+            &pool.address,
+            &token_pool,
+            address,
+            escrow,
+            escrow.amount,
+        );
+        results.push(test.send(&[instruction], &[&member]).await);
+    }
+    assert_eq!(
+        results,
+        vec![
+            Err(custom(TokenPoolError::MintNotAllowed)),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::InsufficientFunds
+            )),
+            Err(custom(TokenPoolError::InventoryFull)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn basket_nft_is_claimed_only_from_its_inventory() {
+    let mut test = Test::start().await;
+    let mint = Pubkey::new_unique();
+    let items = vec![item(&mint), item(&Pubkey::new_unique())];
+    let (pool, member, inventory) = basket_owning(&mut test, &mint, items.clone(), 2).await;
+    let inventory_state = test.state::<Inventory>(&inventory).await;
+    let claim = |nft_mint: &Pubkey, nft_account: &Pubkey| {
+        instruction::get_nft_authority(
+            &member.pubkey(),
+            &pool.address,
+            nft_mint,
+            nft_account,
+            Some((&inventory, &inventory_state)),
+        )
+    };
+
+    assert_eq!(
+        test.send(&[claim(&Pubkey::new_unique(), &items[0].nft)], &[&member])
+            .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    assert_eq!(
+        test.send(&[claim(&mint, &Pubkey::new_unique())], &[&member])
+            .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    let mut foreign_inventory = claim(&mint, &items[0].nft);
+    foreign_inventory.accounts[6].pubkey = test.program_account(Inventory::account_size(2));
+    assert_eq!(
+        test.send(&[foreign_inventory], &[&member]).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    assert_eq!(test.state::<Inventory>(&inventory).await.items, items);
+}
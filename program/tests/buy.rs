@@ -0,0 +1,132 @@
+mod common;
+
+use {
+    common::{custom, Pool, Test},
+    solana_program::{
+        clock::UnixTimestamp, instruction::InstructionError, program_pack::Pack, pubkey::Pubkey,
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    token_pool::{
+        error::TokenPoolError,
+        state::{Escrow, EscrowStage, ListingKind},
+    },
+    token_pool_client::instruction,
+};
+
+/// an nft listed as `ListNFT` leaves it, a dutch auction decays from `amount` to `floor_price` till `end_time`
+fn list(
+    test: &mut Test,
+    listing: ListingKind,
+    amount: u64,
+    floor_price: u64,
+    start_time: UnixTimestamp,
+    end_time: UnixTimestamp,
+) -> Pubkey {
+    let address = Pubkey::new_unique();
+    let seller = test.wallet();
+    let escrow = Escrow {
+        stage: EscrowStage::Initialized,
+        seller: seller.pubkey(),
+        escrow_vault: Pubkey::new_unique(),
+        share: 100.0,
+        nft: Pubkey::new_unique(),
+        nft_mint: Pubkey::new_unique(),
+        amount,
+        listing,
+        start_time,
+        end_time,
+        floor_price,
+        rent_payer: seller.pubkey(),
+        ..Escrow::default()
+    };
+    test.set_state_with_len(&address, &escrow, Escrow::LEN, 0);
+    address
+}
+
+async fn buy(
+    test: &mut Test,
+    pool: &Pool,
+    member: &Keypair,
+    escrow_address: &Pubkey,
+    amount: u64,
+) -> Result<(), TransactionError> {
+    let token_pool = test.token_pool(&pool.address).await;
+    let escrow = test.escrow(escrow_address).await;
+    let buy = instruction::execute_nft_buy(
+        &member.pubkey(),
+        &pool.address,
+        &token_pool,
+        escrow_address,
+        &escrow,
+        amount,
+    );
+    test.send(&[buy], &[member]).await
+}
+
+#[tokio::test]
+async fn fixed_price_listing_is_bought_at_its_price_only() {
+    let mut test = Test::start().await;
+    let listing = list(&mut test, ListingKind::FixedPrice, 1_000_000, 0, 0, 0);
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let member = test.wallet();
+    test.join(&pool, &member, 1_000_000).await;
+
+    // reaching its target does not let the pool pay less than the price asked
+    assert_eq!(
+        buy(&mut test, &pool, &member, &listing, 900_000).await,
+        Err(custom(TokenPoolError::WrongAmountData))
+    );
+}
+
+#[tokio::test]
+async fn dutch_auction_is_bought_once_its_price_decays_to_the_offer() {
+    let mut test = Test::start().await;
+    let now = test.now().await;
+    let auction = list(
+        &mut test,
+        ListingKind::DutchAuction,
+        1_000_000,
+        200_000,
+        now,
+        now + 100,
+    );
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let member = test.wallet();
+    test.join(&pool, &member, 500_000).await;
+
+    // the price starts at 1_000_000, above the offer
+    assert_eq!(
+        buy(&mut test, &pool, &member, &auction, 700_000).await,
+        Err(custom(TokenPoolError::WrongAmountData))
+    );
+
+    // half way the price is 600_000, under the offer but over what the pool holds
+    test.warp_to(now + 50).await;
+    assert_eq!(
+        buy(&mut test, &pool, &member, &auction, 700_000).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InsufficientFunds
+        ))
+    );
+
+    // at 400_000 the price is accepted and the nft is checked next, the pool buys another mint.
+    // the buy itself hands the nft over through the token program, which native tests can not call
+    test.warp_to(now + 75).await;
+    assert_eq!(
+        buy(&mut test, &pool, &member, &auction, 700_000).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    assert_eq!(
+        test.token_pool(&pool.address).await.current_balance,
+        500_000
+    );
+}
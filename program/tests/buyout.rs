@@ -0,0 +1,242 @@
+mod common;
+
+use {
+    common::{custom, pool_owning_nft, start_buyout, Test},
+    solana_program::pubkey::Pubkey,
+    solana_program_test::tokio,
+    solana_sdk::signature::Signer,
+    std::slice,
+    token_pool::{
+        error::TokenPoolError,
+        state::{Buyout, BuyoutStage, TokenPoolStage},
+    },
+    token_pool_client::instruction,
+};
+
+#[tokio::test]
+async fn shares_are_frozen_while_a_buyout_is_open_to_objections() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let end_time = test.now().await + 100;
+    start_buyout(&mut test, &owned.pool, &owned.first, 2_000_000, end_time).await;
+    let recipient = Pubkey::new_unique();
+    let transfer = instruction::transfer_share(
+        &owned.second.pubkey(),
+        &owned.pool.address,
+        &recipient,
+        None,
+        0,
+    );
+
+    assert_eq!(
+        test.send(slice::from_ref(&transfer), &[&owned.second])
+            .await,
+        Err(custom(TokenPoolError::BuyoutActive))
+    );
+
+    // the refund of the purchase is claimed before the share moves
+    test.warp_to(end_time).await;
+    assert_eq!(
+        test.send(slice::from_ref(&transfer), &[&owned.second])
+            .await,
+        Err(custom(TokenPoolError::RefundNotClaimed))
+    );
+    test.send(
+        &[instruction::claim_refund(
+            &owned.second.pubkey(),
+            &owned.pool.address,
+            &owned.pool.treasury,
+            None,
+        )],
+        &[&owned.second],
+    )
+    .await
+    .unwrap();
+    test.send(&[transfer], &[&owned.second]).await.unwrap();
+    let token_pool = test.token_pool(&owned.pool.address).await;
+    assert_eq!(token_pool.pool_member_list.share_of(recipient), 40.0);
+}
+
+#[tokio::test]
+async fn objecting_members_reject_the_buyout_and_the_buyer_gets_the_deposit_back() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let end_time = test.now().await + 100;
+    let buyout = start_buyout(&mut test, &owned.pool, &owned.first, 2_000_000, end_time).await;
+    let buyout_lamports = test.lamports(&buyout).await;
+
+    let object = |member: &Pubkey| instruction::object_buyout(member, &owned.pool.address, None);
+    test.send(&[object(&owned.second.pubkey())], &[&owned.second])
+        .await
+        .unwrap();
+    let buyout_state = test.state::<Buyout>(&buyout).await;
+    assert_eq!(buyout_state.objection_share, 40.0);
+    assert_eq!(buyout_state.objectors, vec![owned.second.pubkey()]);
+
+    // a member objects once and the buyer never objects to its own offer
+    assert_eq!(
+        test.send(&[object(&owned.second.pubkey())], &[&owned.second])
+            .await,
+        Err(custom(TokenPoolError::AlreadyObjected))
+    );
+    assert_eq!(
+        test.send(&[object(&owned.first.pubkey())], &[&owned.first])
+            .await,
+        Err(custom(TokenPoolError::AlreadyObjected))
+    );
+
+    let token_pool = test.token_pool(&owned.pool.address).await;
+    let complete = instruction::complete_buyout(
+        &owned.pool.address,
+        &token_pool,
+        &buyout_state,
+        &Pubkey::new_unique(),
+    );
+    assert_eq!(
+        test.send(slice::from_ref(&complete), &[]).await,
+        Err(custom(TokenPoolError::ObjectionWindowOpen))
+    );
+
+    test.warp_to(end_time).await;
+    let buyer_lamports = test.lamports(&owned.first.pubkey()).await;
+    test.send(&[complete], &[]).await.unwrap();
+    assert!(test.account(&buyout).await.is_none());
+    assert_eq!(
+        test.lamports(&owned.first.pubkey()).await,
+        buyer_lamports + buyout_lamports
+    );
+    let token_pool = test.token_pool(&owned.pool.address).await;
+    assert_eq!(token_pool.stage, TokenPoolStage::NFTOwned);
+    assert_eq!(
+        token_pool.pool_member_list.share_of(owned.first.pubkey()),
+        60.0
+    );
+}
+
+#[tokio::test]
+async fn objections_after_the_window_are_refused() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let end_time = test.now().await + 100;
+    start_buyout(&mut test, &owned.pool, &owned.first, 2_000_000, end_time).await;
+
+    test.warp_to(end_time).await;
+    assert_eq!(
+        test.send(
+            &[instruction::object_buyout(
+                &owned.second.pubkey(),
+                &owned.pool.address,
+                None
+            )],
+            &[&owned.second]
+        )
+        .await,
+        Err(custom(TokenPoolError::ObjectionWindowClosed))
+    );
+}
+
+#[tokio::test]
+async fn members_claim_their_payout_after_their_refund() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let end_time = test.now().await + 100;
+    let buyout = start_buyout(&mut test, &owned.pool, &owned.first, 2_000_000, end_time).await;
+    // the buyout succeeds as `CompleteBuyout` leaves it once the nft went to the buyer, the buyer
+    // keeps its record for the refund still due to it
+    let mut buyout_state = test.state::<Buyout>(&buyout).await;
+    buyout_state.stage = BuyoutStage::Succeeded;
+    let buyout_lamports = test.lamports(&buyout).await;
+    let buyout_len = test.account(&buyout).await.unwrap().data.len();
+    test.set_state_with_len(&buyout, &buyout_state, buyout_len, 0);
+    test.set_lamports(&buyout, buyout_lamports).await;
+    let buyer = owned.first.pubkey();
+    test.update_pool(&owned.pool, |token_pool| {
+        let index = token_pool.pool_member_list.get_member_index(buyer).unwrap();
+        token_pool.pool_member_list.members[index].share = 0.0;
+        token_pool.stage = TokenPoolStage::BoughtOut;
+    })
+    .await;
+
+    let claim_payout = instruction::claim_buyout_payout(
+        &owned.second.pubkey(),
+        &owned.pool.address,
+        &buyout_state,
+        None,
+    );
+    assert_eq!(
+        test.send(slice::from_ref(&claim_payout), &[&owned.second])
+            .await,
+        Err(custom(TokenPoolError::RefundNotClaimed))
+    );
+    let claim_refund = |member: &Pubkey| {
+        instruction::claim_refund(member, &owned.pool.address, &owned.pool.treasury, None)
+    };
+    test.send(&[claim_refund(&owned.second.pubkey())], &[&owned.second])
+        .await
+        .unwrap();
+
+    // the last payout closes the buyout to the buyer
+    let second_lamports = test.lamports(&owned.second.pubkey()).await;
+    let buyer_lamports = test.lamports(&buyer).await;
+    test.send(&[claim_payout], &[&owned.second]).await.unwrap();
+    assert_eq!(
+        test.lamports(&owned.second.pubkey()).await,
+        second_lamports + 800_000
+    );
+    assert!(test.account(&buyout).await.is_none());
+    assert_eq!(
+        test.lamports(&buyer).await,
+        buyer_lamports + buyout_lamports - 800_000
+    );
+
+    // the refund of the buyer outlives the buyout
+    test.send(&[claim_refund(&buyer)], &[&owned.first])
+        .await
+        .unwrap();
+    assert_eq!(
+        test.lamports(&buyer).await,
+        buyer_lamports + buyout_lamports - 800_000 + 240_000
+    );
+    assert!(test.token_pool(&owned.pool.address).await.is_finished());
+}
+
+#[tokio::test]
+async fn buyout_terms_are_locked_once_the_pool_owns_its_nft() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let set_terms = |objection_period| {
+        instruction::set_buyout_terms(
+            &pool.manager.pubkey(),
+            &pool.address,
+            2_000_000,
+            objection_period,
+        )
+    };
+    assert_eq!(
+        test.send(&[set_terms(0)], &[&pool.manager]).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    test.send(&[set_terms(100)], &[&pool.manager])
+        .await
+        .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.reserve_price, 2_000_000);
+    assert_eq!(token_pool.objection_period, 100);
+
+    let owned = pool_owning_nft(&mut test).await;
+    assert_eq!(
+        test.send(
+            &[instruction::set_buyout_terms(
+                &owned.pool.manager.pubkey(),
+                &owned.pool.address,
+                1,
+                100,
+            )],
+            &[&owned.pool.manager],
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use {
+    common::{custom, pool_owning_nft, Test},
+    solana_program::{instruction::InstructionError, pubkey::Pubkey, system_instruction::transfer},
+    solana_program_test::tokio,
+    solana_sdk::{signature::Signer, transaction::TransactionError},
+    std::slice,
+    token_pool::error::TokenPoolError,
+    token_pool_client::instruction,
+};
+
+#[tokio::test]
+async fn manager_closes_a_pool_nobody_joined() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let rent_payer = pool.manager.pubkey();
+    // the manager who set up the pool keeps its rent after handing the pool over
+    let new_manager = test.wallet();
+    let manager = new_manager.pubkey();
+    test.send(
+        &[
+            instruction::propose_manager(&rent_payer, &pool.address, &manager),
+            instruction::accept_manager(&manager, &pool.address),
+        ],
+        &[&pool.manager, &new_manager],
+    )
+    .await
+    .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+
+    assert_eq!(
+        test.send(
+            &[instruction::close_treasury(
+                &pool.address,
+                &token_pool,
+                None
+            )],
+            &[]
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+    // the treasury is found through the pool, so it is closed first
+    let close_pool = instruction::close_pool(&pool.address, &token_pool, Some(&manager));
+    assert_eq!(
+        test.send(slice::from_ref(&close_pool), &[&new_manager])
+            .await,
+        Err(custom(TokenPoolError::AccountNotClosable))
+    );
+
+    let rent_payer_lamports = test.lamports(&rent_payer).await;
+    let rent = test.lamports(&pool.treasury).await + test.lamports(&pool.address).await;
+    test.send(
+        &[instruction::close_treasury(
+            &pool.address,
+            &token_pool,
+            Some(&manager),
+        )],
+        &[&new_manager],
+    )
+    .await
+    .unwrap();
+    test.send(&[close_pool], &[&new_manager]).await.unwrap();
+    assert!(test.account(&pool.treasury).await.is_none());
+    assert!(test.account(&pool.address).await.is_none());
+    assert_eq!(test.lamports(&rent_payer).await, rent_payer_lamports + rent);
+}
+
+#[tokio::test]
+async fn treasury_owing_refunds_is_not_closed() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let pool = &owned.pool;
+    let token_pool = test.token_pool(&pool.address).await;
+    let close_treasury = instruction::close_treasury(&pool.address, &token_pool, None);
+    assert_eq!(
+        test.send(slice::from_ref(&close_treasury), &[]).await,
+        Err(custom(TokenPoolError::AccountNotClosable))
+    );
+
+    // members holding the nft keep the pool open after claiming their refunds
+    for member in [&owned.first, &owned.second] {
+        test.send(
+            &[instruction::claim_refund(
+                &member.pubkey(),
+                &pool.address,
+                &pool.treasury,
+                None,
+            )],
+            &[member],
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(
+        test.send(&[close_treasury], &[]).await,
+        Err(custom(TokenPoolError::AccountNotClosable))
+    );
+}
+
+#[tokio::test]
+async fn sweep_takes_only_what_the_treasury_does_not_owe() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let pool = &owned.pool;
+    let manager = pool.manager.pubkey();
+    let sweep = instruction::sweep_treasury(&manager, &pool.address, &pool.treasury);
+    assert_eq!(
+        test.send(slice::from_ref(&sweep), &[&pool.manager]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InsufficientFunds
+        ))
+    );
+
+    // lamports sent to the treasury directly are not owed to anyone
+    let sender = test.wallet();
+    test.send(
+        &[transfer(&sender.pubkey(), &pool.treasury, 5_000)],
+        &[&sender],
+    )
+    .await
+    .unwrap();
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::sweep_treasury(
+                &stranger.pubkey(),
+                &pool.address,
+                &pool.treasury
+            )],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    let manager_lamports = test.lamports(&manager).await;
+    test.send(&[sweep], &[&pool.manager]).await.unwrap();
+    assert_eq!(test.lamports(&manager).await, manager_lamports + 5_000);
+
+    // the refunds stay in the treasury for the members
+    let first_lamports = test.lamports(&owned.first.pubkey()).await;
+    test.send(
+        &[instruction::claim_refund(
+            &owned.first.pubkey(),
+            &pool.address,
+            &pool.treasury,
+            None,
+        )],
+        &[&owned.first],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        test.lamports(&owned.first.pubkey()).await,
+        first_lamports + 240_000
+    );
+}
@@ -0,0 +1,476 @@
+//! Shared setup of the program tests: funded wallets, token pools, auctions and buyouts in a bank
+//! running the processor natively. native programs can neither allocate accounts nor call the token
+//! program through a cross program invocation, so accounts are stored as the instructions doing that
+//! leave them
+#![allow(dead_code)]
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::{Clock, UnixTimestamp},
+        instruction::{Instruction, InstructionError},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        rent::Rent,
+        system_program,
+    },
+    solana_program_test::{processor, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext},
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token::state::{Account as TokenAccount, AccountState, Mint},
+    token_pool::{
+        error::TokenPoolError,
+        processor::process_instruction,
+        state::{
+            max_members_for, AdmissionPolicy, Buyout, BuyoutStage, Escrow, EscrowStage,
+            ListingKind, MaximumKind, PoolMemberList, PoolTargetKind, TokenPool, TokenPoolStage,
+            TOKEN_POOL_VERSION,
+        },
+    },
+    token_pool_client::{
+        instruction,
+        pda::{find_buyout_address, find_pool_vault_address, find_program_data_address},
+    },
+};
+
+/// Lamports each wallet of a test starts with
+pub const WALLET_LAMPORTS: u64 = 10_000_000_000;
+
+pub struct Test {
+    pub context: ProgramTestContext,
+}
+
+/// A token pool with the keys needed to act on it
+pub struct Pool {
+    pub address: Pubkey,
+    pub treasury: Pubkey,
+    pub manager: Keypair,
+}
+
+/// An english auction of an nft
+pub struct Auction {
+    pub address: Pubkey,
+    pub nft_mint: Pubkey,
+}
+
+/// Error the program returns as the custom error of the only instruction of a transaction
+pub fn custom(error: TokenPoolError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+impl Test {
+    pub async fn start() -> Self {
+        let program_test = ProgramTest::new(
+            "token_pool",
+            token_pool::id(),
+            processor!(process_instruction),
+        );
+        Self {
+            context: program_test.start_with_context().await,
+        }
+    }
+
+    /// send the instructions paid by the payer of the bank, every transaction gets a new blockhash
+    /// so that sending the same instructions twice is not taken for a duplicate
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        self.context.last_blockhash = self
+            .context
+            .banks_client
+            .get_new_latest_blockhash(&self.context.last_blockhash)
+            .await
+            .unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            self.context.last_blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|err| err.unwrap())
+    }
+
+    fn set_account(&mut self, address: &Pubkey, account: Account) {
+        self.context
+            .set_account(address, &AccountSharedData::from(account));
+    }
+
+    /// give the account the lamports, its data and owner stay as they are
+    pub async fn set_lamports(&mut self, address: &Pubkey, lamports: u64) {
+        let mut account = self.account(address).await.unwrap();
+        account.lamports = lamports;
+        self.set_account(address, account);
+    }
+
+    /// a system account holding `WALLET_LAMPORTS`
+    pub fn wallet(&mut self) -> Keypair {
+        let wallet = Keypair::new();
+        self.set_account(
+            &wallet.pubkey(),
+            Account::new(WALLET_LAMPORTS, 0, &system_program::id()),
+        );
+        wallet
+    }
+
+    /// a rent exempt account of the program with zeroed data, as clients allocate them
+    pub fn program_account(&mut self, len: usize) -> Pubkey {
+        let address = Pubkey::new_unique();
+        let lamports = Rent::default().minimum_balance(len);
+        self.set_account(&address, Account::new(lamports, len, &token_pool::id()));
+        address
+    }
+
+    /// an account of the token program standing for a mint, instructions not calling the token program
+    /// only check its owner
+    pub fn mint(&mut self) -> Pubkey {
+        let address = Pubkey::new_unique();
+        let lamports = Rent::default().minimum_balance(Mint::LEN);
+        self.set_account(
+            &address,
+            Account::new(lamports, Mint::LEN, &spl_token::id()),
+        );
+        address
+    }
+
+    /// a token account of the token program holding `amount` of the mint for its owner
+    pub fn token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let address = Pubkey::new_unique();
+        let mut account = Account::new(
+            Rent::default().minimum_balance(TokenAccount::LEN),
+            TokenAccount::LEN,
+            &spl_token::id(),
+        );
+        TokenAccount {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        }
+        .pack_into_slice(&mut account.data);
+        self.set_account(&address, account);
+        address
+    }
+
+    /// the program data account of the program as the upgradeable loader leaves it, naming its upgrade authority
+    pub fn program_data(&mut self, upgrade_authority: &Pubkey) {
+        let state = UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(*upgrade_authority),
+        };
+        let len = UpgradeableLoaderState::size_of_programdata_metadata();
+        let account = Account::new_data_with_space(
+            Rent::default().minimum_balance(len),
+            &state,
+            len,
+            &bpf_loader_upgradeable::id(),
+        )
+        .unwrap();
+        self.set_account(&find_program_data_address().0, account);
+    }
+
+    pub async fn now(&mut self) -> UnixTimestamp {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+    }
+
+    pub async fn warp_to(&mut self, unix_timestamp: UnixTimestamp) {
+        let mut clock = self
+            .context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.account(address)
+            .await
+            .map_or(0, |account| account.lamports)
+    }
+
+    pub async fn token_pool(&mut self, address: &Pubkey) -> TokenPool {
+        TokenPool::load(&self.account(address).await.unwrap().data).unwrap()
+    }
+
+    pub async fn escrow(&mut self, address: &Pubkey) -> Escrow {
+        Escrow::unpack_unchecked(&self.account(address).await.unwrap().data).unwrap()
+    }
+
+    pub async fn state<T: BorshDeserialize>(&mut self, address: &Pubkey) -> T {
+        solana_program::borsh::try_from_slice_unchecked(&self.account(address).await.unwrap().data)
+            .unwrap()
+    }
+
+    /// store the state in a rent exempt account of the program sized for it
+    pub fn set_state<T: BorshSerialize>(&mut self, address: &Pubkey, state: &T) {
+        let len = state.try_to_vec().unwrap().len();
+        self.set_state_with_len(address, state, len, 0);
+    }
+
+    /// store the state at the start of a program account of `len` bytes, holding `lamports` over its rent
+    pub fn set_state_with_len<T: BorshSerialize>(
+        &mut self,
+        address: &Pubkey,
+        state: &T,
+        len: usize,
+        lamports: u64,
+    ) {
+        let mut account = Account::new(
+            Rent::default().minimum_balance(len) + lamports,
+            len,
+            &token_pool::id(),
+        );
+        state.serialize(&mut &mut account.data[..]).unwrap();
+        self.set_account(address, account);
+    }
+
+    /// change the stored token pool, e.g. to the state an instruction calling the token program leaves it in
+    pub async fn update_pool(&mut self, pool: &Pool, update: impl FnOnce(&mut TokenPool)) {
+        let mut token_pool = self.token_pool(&pool.address).await;
+        update(&mut token_pool);
+        self.set_state(&pool.address, &token_pool);
+    }
+
+    /// a token pool buying the nft mint as `InitializePool` leaves it
+    pub async fn create_pool(
+        &mut self,
+        target_token: &Pubkey,
+        target_amount: u64,
+        minimum_amount: u64,
+    ) -> Pool {
+        let manager = self.wallet();
+        let address = Pubkey::new_unique();
+        let treasury = self.program_account(0);
+        let max_members = max_members_for(target_amount, minimum_amount).unwrap();
+        let token_pool = TokenPool {
+            version: TOKEN_POOL_VERSION,
+            stage: TokenPoolStage::Initialized,
+            target_amount,
+            minimum_exemption_amount: 0,
+            minimum_amount,
+            current_balance: 0,
+            target_token: *target_token,
+            description: String::new(),
+            vault: find_pool_vault_address(&address).0,
+            manager: manager.pubkey(),
+            treasurey: treasury,
+            target_kind: PoolTargetKind::SingleMint,
+            inventory: Pubkey::default(),
+            target_token_amount: 0,
+            reserve_price: 0,
+            objection_period: 0,
+            gate_mint: Pubkey::default(),
+            maximum_amount: 0,
+            maximum_kind: MaximumKind::Amount,
+            manager_share: 0,
+            admission_policy: AdmissionPolicy::LastMember,
+            subscription_end: 0,
+            pending_manager: Pubkey::default(),
+            paused: false,
+            rent_payer: manager.pubkey(),
+            committed_bid: 0,
+            bid_escrow: Pubkey::default(),
+            buyout_end: 0,
+            pool_member_list: PoolMemberList::new(max_members),
+        };
+        self.set_state(&address, &token_pool);
+        Pool {
+            address,
+            treasury,
+            manager,
+        }
+    }
+
+    pub async fn join(&mut self, pool: &Pool, member: &Keypair, amount: u64) {
+        self.send(
+            &[instruction::add_member(
+                &member.pubkey(),
+                &pool.address,
+                &pool.treasury,
+                None,
+                amount,
+            )],
+            &[member],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// an english auction of a new nft as `ListNftAuction` leaves it, ending after `duration` seconds
+    pub async fn auction(&mut self, reserve_price: u64, duration: UnixTimestamp) -> Auction {
+        let seller = self.wallet();
+        let auction = Auction {
+            address: Pubkey::new_unique(),
+            nft_mint: Pubkey::new_unique(),
+        };
+        let escrow = Escrow {
+            stage: EscrowStage::Initialized,
+            seller: seller.pubkey(),
+            nft: Pubkey::new_unique(),
+            nft_mint: auction.nft_mint,
+            amount: reserve_price,
+            listing: ListingKind::EnglishAuction,
+            end_time: self.now().await + duration,
+            min_bid_increment: 10_000,
+            rent_payer: seller.pubkey(),
+            ..Escrow::default()
+        };
+        self.set_state_with_len(&auction.address, &escrow, Escrow::LEN, 0);
+        auction
+    }
+
+    /// the wallet bids for the nft of the auction with its own lamports
+    pub async fn bid(
+        &mut self,
+        auction: &Auction,
+        bidder: &Keypair,
+        amount: u64,
+    ) -> Result<(), TransactionError> {
+        let escrow = self.escrow(&auction.address).await;
+        let bid = instruction::place_bid(&bidder.pubkey(), &auction.address, &escrow, None, amount);
+        self.send(&[bid_without_duplicates(bid, &escrow)], &[bidder])
+            .await
+    }
+
+    /// the member bids for the nft of the auction with the treasury of the token pool
+    pub async fn bid_with_pool(
+        &mut self,
+        auction: &Auction,
+        pool: &Pool,
+        member: &Keypair,
+        amount: u64,
+    ) -> Result<(), TransactionError> {
+        let escrow = self.escrow(&auction.address).await;
+        let token_pool = self.token_pool(&pool.address).await;
+        let bid = instruction::place_bid(
+            &member.pubkey(),
+            &auction.address,
+            &escrow,
+            Some((&pool.address, &token_pool)),
+            amount,
+        );
+        self.send(&[bid_without_duplicates(bid, &escrow)], &[member])
+            .await
+    }
+}
+
+/// A bid names its bidder as the previous bidder when it is the first one, and its token pool when
+/// the pool raises its own bid, the program never reads that place then. the native bank keeps a copy
+/// of an account per place it has in an instruction and writes the copies back in any order, so the
+/// seller takes that place
+fn bid_without_duplicates(mut bid: Instruction, escrow: &Escrow) -> Instruction {
+    let previous_bidder = bid.accounts[2].pubkey;
+    if bid
+        .accounts
+        .iter()
+        .filter(|account| account.pubkey == previous_bidder)
+        .count()
+        > 1
+    {
+        bid.accounts[2].pubkey = escrow.seller;
+    }
+    bid
+}
+
+/// A token pool of two members owning its nft, with the treasury left as the purchase leaves it
+pub struct OwnedPool {
+    pub pool: Pool,
+    /// holds 60% of the pool
+    pub first: Keypair,
+    /// holds 40% of the pool
+    pub second: Keypair,
+}
+
+/// A token pool reaching its target of 1_000_000 lamports, the first member holds 60% of it and the
+/// second 40%
+pub async fn pool_of_two(test: &mut Test) -> (Pool, Keypair, Keypair) {
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let first = test.wallet();
+    let second = test.wallet();
+    test.join(&pool, &first, 600_000).await;
+    test.join(&pool, &second, 400_000).await;
+    (pool, first, second)
+}
+
+/// Members deposit 600_000 and 400_000 lamports and the pool buys its nft for 600_000, the 400_000
+/// left is due back to them as 240_000 and 160_000
+pub async fn pool_owning_nft(test: &mut Test) -> OwnedPool {
+    let (pool, first, second) = pool_of_two(test).await;
+    test.update_pool(&pool, |token_pool| {
+        token_pool.settle_shares().unwrap();
+        token_pool.stage = TokenPoolStage::NFTOwned;
+        token_pool.current_balance -= 600_000;
+        token_pool.refund_leftover();
+    })
+    .await;
+    let treasury_lamports = test.lamports(&pool.treasury).await;
+    test.set_lamports(&pool.treasury, treasury_lamports - 600_000)
+        .await;
+    OwnedPool {
+        pool,
+        first,
+        second,
+    }
+}
+
+/// Buyer offers `price` for the whole pool as `StartBuyout` leaves it, members may object till `end_time`
+pub async fn start_buyout(
+    test: &mut Test,
+    pool: &Pool,
+    buyer: &Keypair,
+    price: u64,
+    end_time: UnixTimestamp,
+) -> Pubkey {
+    let token_pool = test.token_pool(&pool.address).await;
+    let buyer_share = token_pool.pool_member_list.share_of(buyer.pubkey());
+    let buyout = Buyout {
+        stage: BuyoutStage::Active,
+        token_pool: pool.address,
+        buyer: buyer.pubkey(),
+        price,
+        deposit: Buyout::deposit_for(price, buyer_share),
+        end_time,
+        objection_share: 0.0,
+        objectors: Vec::new(),
+    };
+    let address = find_buyout_address(&pool.address).0;
+    let max_members = token_pool.pool_member_list.header.max_members;
+    test.set_state_with_len(
+        &address,
+        &buyout,
+        Buyout::account_size(max_members),
+        buyout.deposit,
+    );
+    test.update_pool(pool, |token_pool| token_pool.buyout_end = end_time)
+        .await;
+    address
+}
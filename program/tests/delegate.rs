@@ -0,0 +1,159 @@
+mod common;
+
+use {
+    common::{custom, pool_of_two, pool_owning_nft, start_buyout, Test},
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::signature::Signer,
+    token_pool::{
+        error::TokenPoolError,
+        state::{Buyout, Escrow, DELEGATE_CLAIM, DELEGATE_SELL, DELEGATE_VOTE},
+    },
+    token_pool_client::{instruction, pda::find_buyout_address},
+};
+
+#[tokio::test]
+async fn members_approve_a_delegate_with_known_permissions() {
+    let mut test = Test::start().await;
+    let (pool, first, _) = pool_of_two(&mut test).await;
+    let custodian = test.wallet();
+    let approve = |member: &Pubkey, delegate: &Pubkey, permissions| {
+        instruction::approve_delegate(member, &pool.address, delegate, permissions)
+    };
+
+    for (delegate, permissions) in [
+        (custodian.pubkey(), 0),
+        (custodian.pubkey(), DELEGATE_CLAIM << 1),
+        (first.pubkey(), DELEGATE_SELL),
+        (Pubkey::default(), DELEGATE_SELL),
+    ] {
+        assert_eq!(
+            test.send(
+                &[approve(&first.pubkey(), &delegate, permissions)],
+                &[&first]
+            )
+            .await,
+            Err(custom(TokenPoolError::InvalidData))
+        );
+    }
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[approve(
+                &stranger.pubkey(),
+                &custodian.pubkey(),
+                DELEGATE_SELL
+            )],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::MemberNotInPool))
+    );
+
+    test.send(
+        &[approve(
+            &first.pubkey(),
+            &custodian.pubkey(),
+            DELEGATE_VOTE | DELEGATE_CLAIM,
+        )],
+        &[&first],
+    )
+    .await
+    .unwrap();
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    let index = members.get_member_index(first.pubkey()).unwrap();
+    assert_eq!(members.members[index].delegate, custodian.pubkey());
+    assert_eq!(
+        members.members[index].delegate_permissions,
+        DELEGATE_VOTE | DELEGATE_CLAIM
+    );
+    // voting and claiming do not let the delegate sell the share
+    let escrow_address = test.program_account(Escrow::LEN);
+    assert_eq!(
+        test.send(
+            &[instruction::sell_share(
+                &first.pubkey(),
+                &pool.address,
+                &escrow_address,
+                None,
+                Some(&custodian.pubkey()),
+                300_000,
+                0,
+            )],
+            &[&custodian]
+        )
+        .await,
+        Err(custom(TokenPoolError::DelegateNotAllowed))
+    );
+
+    test.send(
+        &[instruction::revoke_delegate(&first.pubkey(), &pool.address)],
+        &[&first],
+    )
+    .await
+    .unwrap();
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.members[index].delegate, Pubkey::default());
+    assert_eq!(members.members[index].delegate_permissions, 0);
+}
+
+#[tokio::test]
+async fn delegate_acts_within_its_permissions_till_revoked() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let end_time = test.now().await + 100;
+    start_buyout(&mut test, &owned.pool, &owned.first, 2_000_000, end_time).await;
+    let member = owned.second.pubkey();
+    let custodian = test.wallet();
+    let approve_vote = instruction::approve_delegate(
+        &member,
+        &owned.pool.address,
+        &custodian.pubkey(),
+        DELEGATE_VOTE,
+    );
+    test.send(&[approve_vote.clone()], &[&owned.second])
+        .await
+        .unwrap();
+
+    // voting does not let the delegate claim for the member
+    assert_eq!(
+        test.send(
+            &[instruction::claim_refund(
+                &member,
+                &owned.pool.address,
+                &owned.pool.treasury,
+                Some(&custodian.pubkey()),
+            )],
+            &[&custodian]
+        )
+        .await,
+        Err(custom(TokenPoolError::DelegateNotAllowed))
+    );
+
+    let object = |delegate: &Pubkey| {
+        instruction::object_buyout(&member, &owned.pool.address, Some(delegate))
+    };
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(&[object(&stranger.pubkey())], &[&stranger]).await,
+        Err(custom(TokenPoolError::DelegateNotAllowed))
+    );
+    test.send(
+        &[instruction::revoke_delegate(&member, &owned.pool.address)],
+        &[&owned.second],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        test.send(&[object(&custodian.pubkey())], &[&custodian])
+            .await,
+        Err(custom(TokenPoolError::DelegateNotAllowed))
+    );
+
+    test.send(&[approve_vote], &[&owned.second]).await.unwrap();
+    test.send(&[object(&custodian.pubkey())], &[&custodian])
+        .await
+        .unwrap();
+    let (buyout, _) = find_buyout_address(&owned.pool.address);
+    assert_eq!(test.state::<Buyout>(&buyout).await.objectors, vec![member]);
+}
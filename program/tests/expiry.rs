@@ -0,0 +1,189 @@
+mod common;
+
+use {
+    common::{custom, Test},
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::signature::Signer,
+    std::slice,
+    token_pool::{
+        error::TokenPoolError,
+        state::{Escrow, EscrowStage, ListingKind, DELEGATE_SELL},
+    },
+    token_pool_client::{instruction, pda::find_share_escrow_vault_address},
+};
+
+#[tokio::test]
+async fn expired_share_sale_goes_back_to_its_seller() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let first = test.wallet();
+    let second = test.wallet();
+    test.join(&pool, &first, 600_000).await;
+    test.join(&pool, &second, 400_000).await;
+
+    // the custodian of the share puts it on sale for 100 seconds and pays for the escrow
+    let custodian = test.wallet();
+    test.send(
+        &[instruction::approve_delegate(
+            &first.pubkey(),
+            &pool.address,
+            &custodian.pubkey(),
+            DELEGATE_SELL,
+        )],
+        &[&first],
+    )
+    .await
+    .unwrap();
+    let escrow_address = test.program_account(Escrow::LEN);
+    test.send(
+        &[instruction::sell_share(
+            &first.pubkey(),
+            &pool.address,
+            &escrow_address,
+            None,
+            Some(&custodian.pubkey()),
+            300_000,
+            100,
+        )],
+        &[&custodian],
+    )
+    .await
+    .unwrap();
+    let escrow = test.escrow(&escrow_address).await;
+    let expires_at = test.now().await + 100;
+    assert_eq!(escrow.expires_at, expires_at);
+
+    let expire = instruction::expire_escrow(&escrow_address, &escrow, Some(&pool.address));
+    assert_eq!(
+        test.send(slice::from_ref(&expire), &[]).await,
+        Err(custom(TokenPoolError::EscrowNotExpired))
+    );
+
+    test.warp_to(expires_at).await;
+    let buyer = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::buy_share(
+                &buyer.pubkey(),
+                &pool.address,
+                &escrow_address,
+                &escrow,
+                None,
+                300_000,
+            )],
+            &[&buyer],
+        )
+        .await,
+        Err(custom(TokenPoolError::EscrowExpired))
+    );
+
+    // the sale only gives the share back in the pool it was started in
+    let other_pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    assert_eq!(
+        test.send(
+            &[instruction::expire_escrow(
+                &escrow_address,
+                &escrow,
+                Some(&other_pool.address),
+            )],
+            &[],
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    let escrow_lamports = test.lamports(&escrow_address).await;
+    let custodian_lamports = test.lamports(&custodian.pubkey()).await;
+    test.send(&[expire], &[]).await.unwrap();
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.share_of(first.pubkey()), 60.0);
+    let (escrow_vault, _) = find_share_escrow_vault_address(&first.pubkey(), &pool.address);
+    assert!(!members.find_member(escrow_vault));
+    assert!(test.account(&escrow_address).await.is_none());
+    assert_eq!(
+        test.lamports(&custodian.pubkey()).await,
+        custodian_lamports + escrow_lamports
+    );
+}
+
+#[tokio::test]
+async fn only_seller_sets_when_a_listing_expires() {
+    let mut test = Test::start().await;
+    let seller = test.wallet();
+    let escrow_address = Pubkey::new_unique();
+    let escrow = Escrow {
+        stage: EscrowStage::Initialized,
+        seller: seller.pubkey(),
+        nft: Pubkey::new_unique(),
+        nft_mint: Pubkey::new_unique(),
+        amount: 1_000_000,
+        listing: ListingKind::FixedPrice,
+        rent_payer: seller.pubkey(),
+        ..Escrow::default()
+    };
+    test.set_state_with_len(&escrow_address, &escrow, Escrow::LEN, 0);
+    let now = test.now().await;
+
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::set_escrow_expiry(
+                &stranger.pubkey(),
+                &escrow_address,
+                now + 100
+            )],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    assert_eq!(
+        test.send(
+            &[instruction::set_escrow_expiry(
+                &seller.pubkey(),
+                &escrow_address,
+                now
+            )],
+            &[&seller]
+        )
+        .await,
+        Err(custom(TokenPoolError::EscrowExpired))
+    );
+    test.send(
+        &[instruction::set_escrow_expiry(
+            &seller.pubkey(),
+            &escrow_address,
+            now + 100,
+        )],
+        &[&seller],
+    )
+    .await
+    .unwrap();
+    assert_eq!(test.escrow(&escrow_address).await.expires_at, now + 100);
+
+    // an english auction ends with its bids instead
+    let auction_address = Pubkey::new_unique();
+    let auction = Escrow {
+        listing: ListingKind::EnglishAuction,
+        end_time: now + 100,
+        ..escrow
+    };
+    test.set_state_with_len(&auction_address, &auction, Escrow::LEN, 0);
+    assert_eq!(
+        test.send(
+            &[instruction::set_escrow_expiry(
+                &seller.pubkey(),
+                &auction_address,
+                now + 100
+            )],
+            &[&seller]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidListingKind))
+    );
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use {
+    common::{custom, Test},
+    solana_program::{instruction::InstructionError, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::{signature::Signer, transaction::TransactionError},
+    token_pool::error::TokenPoolError,
+    token_pool_client::instruction,
+};
+
+#[tokio::test]
+async fn gate_mint_is_set_by_the_manager() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let gate_mint = test.mint();
+    let set_gate = |manager: &Pubkey, gate_mint: Option<&Pubkey>| {
+        instruction::set_gate_mint(manager, &pool.address, gate_mint)
+    };
+
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[set_gate(&stranger.pubkey(), Some(&gate_mint))],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    assert_eq!(
+        test.send(
+            &[set_gate(
+                &pool.manager.pubkey(),
+                Some(&Pubkey::new_unique())
+            )],
+            &[&pool.manager]
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IllegalOwner
+        ))
+    );
+
+    test.send(
+        &[set_gate(&pool.manager.pubkey(), Some(&gate_mint))],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    assert_eq!(test.token_pool(&pool.address).await.gate_mint, gate_mint);
+    test.send(&[set_gate(&pool.manager.pubkey(), None)], &[&pool.manager])
+        .await
+        .unwrap();
+    assert_eq!(
+        test.token_pool(&pool.address).await.gate_mint,
+        Pubkey::default()
+    );
+}
+
+#[tokio::test]
+async fn only_holders_of_the_gate_mint_join() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let gate_mint = test.mint();
+    test.send(
+        &[instruction::set_gate_mint(
+            &pool.manager.pubkey(),
+            &pool.address,
+            Some(&gate_mint),
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    let member = test.wallet();
+    let join = |gate_token_account: Option<&Pubkey>| {
+        instruction::add_member(
+            &member.pubkey(),
+            &pool.address,
+            &pool.treasury,
+            gate_token_account,
+            100_000,
+        )
+    };
+
+    let other_mint = test.mint();
+    let not_allowed = [
+        None,
+        Some(test.token_account(&other_mint, &member.pubkey(), 1)),
+        Some(test.token_account(&gate_mint, &Pubkey::new_unique(), 1)),
+        Some(test.token_account(&gate_mint, &member.pubkey(), 0)),
+    ];
+    for gate_token_account in not_allowed {
+        assert_eq!(
+            test.send(&[join(gate_token_account.as_ref())], &[&member])
+                .await,
+            Err(custom(TokenPoolError::MemberNotAllowed))
+        );
+    }
+    assert_eq!(
+        test.send(&[join(Some(&Pubkey::new_unique()))], &[&member])
+            .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IllegalOwner
+        ))
+    );
+
+    let gate_token_account = test.token_account(&gate_mint, &member.pubkey(), 1);
+    test.send(&[join(Some(&gate_token_account))], &[&member])
+        .await
+        .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert!(token_pool.pool_member_list.find_member(member.pubkey()));
+    assert_eq!(token_pool.current_balance, 100_000);
+}
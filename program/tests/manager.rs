@@ -0,0 +1,164 @@
+mod common;
+
+use {
+    common::{custom, Test},
+    solana_program::{instruction::InstructionError, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::{signature::Signer, transaction::TransactionError},
+    token_pool::error::TokenPoolError,
+    token_pool_client::instruction,
+};
+
+#[tokio::test]
+async fn manager_proposes_and_cancels_a_new_manager() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let new_manager = test.wallet();
+    let propose = |manager: &Pubkey, new_manager: &Pubkey| {
+        instruction::propose_manager(manager, &pool.address, new_manager)
+    };
+
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[propose(&stranger.pubkey(), &stranger.pubkey())],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    assert_eq!(
+        test.send(
+            &[propose(&pool.manager.pubkey(), &Pubkey::default())],
+            &[&pool.manager]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    let cancel = |manager: &Pubkey| instruction::cancel_manager_transfer(manager, &pool.address);
+    assert_eq!(
+        test.send(&[cancel(&pool.manager.pubkey())], &[&pool.manager])
+            .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    test.send(
+        &[propose(&pool.manager.pubkey(), &new_manager.pubkey())],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.manager, pool.manager.pubkey());
+    assert_eq!(token_pool.pending_manager, new_manager.pubkey());
+
+    assert_eq!(
+        test.send(&[cancel(&new_manager.pubkey())], &[&new_manager])
+            .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    test.send(&[cancel(&pool.manager.pubkey())], &[&pool.manager])
+        .await
+        .unwrap();
+    assert_eq!(
+        test.token_pool(&pool.address).await.pending_manager,
+        Pubkey::default()
+    );
+    assert_eq!(
+        test.send(
+            &[instruction::accept_manager(
+                &new_manager.pubkey(),
+                &pool.address
+            )],
+            &[&new_manager]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+}
+
+#[tokio::test]
+async fn proposed_manager_accepts_and_takes_the_place_of_the_manager() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    test.send(
+        &[instruction::set_manager_share(
+            &pool.manager.pubkey(),
+            &pool.address,
+            500,
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    let new_manager = test.wallet();
+    test.send(
+        &[instruction::propose_manager(
+            &pool.manager.pubkey(),
+            &pool.address,
+            &new_manager.pubkey(),
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::accept_manager(
+                &stranger.pubkey(),
+                &pool.address
+            )],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    let mut unsigned = instruction::accept_manager(&new_manager.pubkey(), &pool.address);
+    unsigned.accounts[0].is_signer = false;
+    assert_eq!(
+        test.send(&[unsigned], &[]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+
+    test.send(
+        &[instruction::accept_manager(
+            &new_manager.pubkey(),
+            &pool.address,
+        )],
+        &[&new_manager],
+    )
+    .await
+    .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.manager, new_manager.pubkey());
+    assert_eq!(token_pool.pending_manager, Pubkey::default());
+    assert!(token_pool
+        .pool_member_list
+        .find_member(new_manager.pubkey()));
+    assert!(!token_pool
+        .pool_member_list
+        .find_member(pool.manager.pubkey()));
+
+    // the old manager has handed over
+    assert_eq!(
+        test.send(
+            &[instruction::set_pool_paused(
+                &pool.manager.pubkey(),
+                &pool.address,
+                true
+            )],
+            &[&pool.manager]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use {
+    common::{custom, Pool, Test},
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    token_pool::{
+        error::TokenPoolError,
+        state::{MaximumKind, ShareBid, ShareBidStage},
+    },
+    token_pool_client::{instruction, pda::find_share_bid_address},
+};
+
+/// a token pool of 10 members at most, each contributing up to 40% of its target of 1_000_000
+async fn capped_pool(test: &mut Test) -> Pool {
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    test.send(
+        &[instruction::set_maximum_amount(
+            &pool.manager.pubkey(),
+            &pool.address,
+            40,
+            MaximumKind::Percent,
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    pool
+}
+
+async fn contribute(
+    test: &mut Test,
+    pool: &Pool,
+    member: &Keypair,
+    amount: u64,
+) -> Result<(), TransactionError> {
+    let token_pool = test.token_pool(&pool.address).await;
+    let instruction = if token_pool.pool_member_list.find_member(member.pubkey()) {
+        instruction::upgrade_share(&member.pubkey(), &pool.address, &pool.treasury, amount)
+    } else {
+        instruction::add_member(
+            &member.pubkey(),
+            &pool.address,
+            &pool.treasury,
+            None,
+            amount,
+        )
+    };
+    test.send(&[instruction], &[member]).await
+}
+
+#[tokio::test]
+async fn maximum_amount_is_set_by_the_manager_within_the_target() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let set_maximum = |manager: &Pubkey, maximum_amount| {
+        instruction::set_maximum_amount(
+            manager,
+            &pool.address,
+            maximum_amount,
+            MaximumKind::Percent,
+        )
+    };
+
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(&[set_maximum(&stranger.pubkey(), 40)], &[&stranger])
+            .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    assert_eq!(
+        test.send(
+            &[set_maximum(&pool.manager.pubkey(), 101)],
+            &[&pool.manager]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongAmountData))
+    );
+    // 10 members of 5% each would never reach the target
+    assert_eq!(
+        test.send(&[set_maximum(&pool.manager.pubkey(), 5)], &[&pool.manager])
+            .await,
+        Err(custom(TokenPoolError::WrongAmountData))
+    );
+
+    test.send(&[set_maximum(&pool.manager.pubkey(), 40)], &[&pool.manager])
+        .await
+        .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.maximum_kind, MaximumKind::Percent);
+    assert_eq!(token_pool.maximum_contribution(), Some(400_000));
+}
+
+#[tokio::test]
+async fn members_contribute_up_to_the_maximum() {
+    let mut test = Test::start().await;
+    let pool = capped_pool(&mut test).await;
+    let first = test.wallet();
+    let second = test.wallet();
+
+    assert_eq!(
+        contribute(&mut test, &pool, &first, 500_000).await,
+        Err(custom(TokenPoolError::AboveMaximumAmount))
+    );
+    contribute(&mut test, &pool, &first, 400_000).await.unwrap();
+    contribute(&mut test, &pool, &second, 300_000)
+        .await
+        .unwrap();
+    assert_eq!(
+        contribute(&mut test, &pool, &first, 100_000).await,
+        Err(custom(TokenPoolError::AboveMaximumAmount))
+    );
+    contribute(&mut test, &pool, &second, 100_000)
+        .await
+        .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(
+        token_pool
+            .pool_member_list
+            .amount_deposited_by(second.pubkey()),
+        400_000
+    );
+}
+
+#[tokio::test]
+async fn shares_do_not_move_to_members_above_the_maximum() {
+    let mut test = Test::start().await;
+    let pool = capped_pool(&mut test).await;
+    let first = test.wallet();
+    let second = test.wallet();
+    contribute(&mut test, &pool, &first, 400_000).await.unwrap();
+    contribute(&mut test, &pool, &second, 400_000)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test.send(
+            &[instruction::transfer_share(
+                &first.pubkey(),
+                &pool.address,
+                &second.pubkey(),
+                None,
+                100,
+            )],
+            &[&first]
+        )
+        .await,
+        Err(custom(TokenPoolError::AboveMaximumAmount))
+    );
+
+    // the second member bids for more of the pool as `PlaceShareBid` leaves it
+    let (share_bid, _) = find_share_bid_address(&pool.address, &second.pubkey());
+    let bid = ShareBid {
+        stage: ShareBidStage::Open,
+        token_pool: pool.address,
+        bidder: second.pubkey(),
+        price_per_unit: 100,
+        quantity: 1_000,
+    };
+    test.set_state_with_len(&share_bid, &bid, ShareBid::LEN, 100_000);
+    assert_eq!(
+        test.send(
+            &[instruction::fill_bid(
+                &first.pubkey(),
+                &pool.address,
+                &second.pubkey(),
+                100,
+                100,
+            )],
+            &[&first]
+        )
+        .await,
+        Err(custom(TokenPoolError::AboveMaximumAmount))
+    );
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.amount_deposited_by(first.pubkey()), 400_000);
+    assert_eq!(members.amount_deposited_by(second.pubkey()), 400_000);
+}
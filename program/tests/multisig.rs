@@ -0,0 +1,236 @@
+mod common;
+
+use {
+    common::{custom, Test},
+    solana_program::{
+        instruction::InstructionError, program_pack::Pack, pubkey::Pubkey, rent::Rent,
+    },
+    solana_program_test::tokio,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    token_pool::{
+        error::TokenPoolError,
+        state::{Multisig, MAX_SIGNERS},
+    },
+    token_pool_client::instruction,
+};
+
+fn error(error: InstructionError) -> TransactionError {
+    TransactionError::InstructionError(0, error)
+}
+
+/// an allocated multisig account of the program holding `lamports` over its rent, not initialized yet
+fn multisig_account(test: &mut Test, lamports: u64) -> Keypair {
+    let multisig = Keypair::new();
+    test.set_state_with_len(
+        &multisig.pubkey(),
+        &Multisig::default(),
+        Multisig::LEN,
+        lamports,
+    );
+    multisig
+}
+
+/// a 2 of 3 multisig
+async fn multisig(test: &mut Test, lamports: u64) -> (Pubkey, [Keypair; 3]) {
+    let multisig = multisig_account(test, lamports);
+    let signers = [test.wallet(), test.wallet(), test.wallet()];
+    let keys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    test.send(
+        &[instruction::initialize_multisig(
+            &multisig.pubkey(),
+            &keys,
+            2,
+        )],
+        &[&multisig],
+    )
+    .await
+    .unwrap();
+    (multisig.pubkey(), signers)
+}
+
+#[tokio::test]
+async fn multisig_is_initialized_once_with_m_of_its_signers() {
+    let mut test = Test::start().await;
+    let multisig = multisig_account(&mut test, 0);
+    let signers: Vec<Pubkey> = (0..=MAX_SIGNERS).map(|_| Pubkey::new_unique()).collect();
+    let initialize =
+        |signers: &[Pubkey], m| instruction::initialize_multisig(&multisig.pubkey(), signers, m);
+
+    for (signers, m) in [
+        (&signers[..0], 0),
+        (&signers[..2], 0),
+        (&signers[..2], 3),
+        (&signers[..], 2),
+    ] {
+        assert_eq!(
+            test.send(&[initialize(signers, m)], &[&multisig]).await,
+            Err(custom(TokenPoolError::InvalidData))
+        );
+    }
+    let mut unsigned = initialize(&signers[..3], 2);
+    unsigned.accounts[0].is_signer = false;
+    assert_eq!(
+        test.send(&[unsigned], &[]).await,
+        Err(error(InstructionError::MissingRequiredSignature))
+    );
+
+    test.send(&[initialize(&signers[..3], 2)], &[&multisig])
+        .await
+        .unwrap();
+    let state = test.state::<Multisig>(&multisig.pubkey()).await;
+    assert!(state.is_initialized);
+    assert_eq!((state.m, state.n), (2, 3));
+    assert_eq!(state.signers[..3], signers[..3]);
+    assert_eq!(state.signers[3], Pubkey::default());
+
+    assert_eq!(
+        test.send(&[initialize(&signers[3..5], 1)], &[&multisig])
+            .await,
+        Err(error(InstructionError::AccountAlreadyInitialized))
+    );
+}
+
+#[tokio::test]
+async fn multisig_manager_acts_with_m_of_its_signers() {
+    let mut test = Test::start().await;
+    let (multisig, signers) = multisig(&mut test, 0).await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    test.send(
+        &[instruction::propose_manager(
+            &pool.manager.pubkey(),
+            &pool.address,
+            &multisig,
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    let signed = |instruction, signers: &[&Keypair]| {
+        let keys: Vec<Pubkey> = signers.iter().map(|signer| signer.pubkey()).collect();
+        instruction::multisig_signed(instruction, &multisig, &keys)
+    };
+
+    // a stranger signing along does not count towards the multisig
+    let stranger = test.wallet();
+    let accept = instruction::accept_manager(&multisig, &pool.address);
+    assert_eq!(
+        test.send(
+            &[signed(accept.clone(), &[&signers[0], &stranger])],
+            &[&signers[0], &stranger]
+        )
+        .await,
+        Err(error(InstructionError::MissingRequiredSignature))
+    );
+    test.send(
+        &[signed(accept, &[&signers[0], &signers[2]])],
+        &[&signers[0], &signers[2]],
+    )
+    .await
+    .unwrap();
+    assert_eq!(test.token_pool(&pool.address).await.manager, multisig);
+
+    let pause = instruction::set_pool_paused(&multisig, &pool.address, true);
+    assert_eq!(
+        test.send(&[signed(pause.clone(), &[&signers[1]])], &[&signers[1]])
+            .await,
+        Err(error(InstructionError::MissingRequiredSignature))
+    );
+    // the signers of the multisig do not act alone as manager
+    assert_eq!(
+        test.send(
+            &[instruction::set_pool_paused(
+                &signers[1].pubkey(),
+                &pool.address,
+                true
+            )],
+            &[&signers[1]]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    test.send(
+        &[signed(pause, &[&signers[1], &signers[2]])],
+        &[&signers[1], &signers[2]],
+    )
+    .await
+    .unwrap();
+    assert!(test.token_pool(&pool.address).await.paused);
+}
+
+#[tokio::test]
+async fn multisig_collects_its_fees_with_m_of_its_signers() {
+    let mut test = Test::start().await;
+    let (multisig, signers) = multisig(&mut test, 500_000).await;
+    let destination = test.wallet().pubkey();
+    let signer_keys = |signers: &[&Keypair]| -> Vec<Pubkey> {
+        signers.iter().map(|signer| signer.pubkey()).collect()
+    };
+
+    assert_eq!(
+        test.send(
+            &[instruction::collect_fees(
+                &multisig,
+                &destination,
+                &signer_keys(&[&signers[0]]),
+                0
+            )],
+            &[&signers[0]]
+        )
+        .await,
+        Err(error(InstructionError::MissingRequiredSignature))
+    );
+    assert_eq!(
+        test.send(
+            &[instruction::collect_fees(
+                &multisig,
+                &destination,
+                &signer_keys(&[&signers[0], &signers[1]]),
+                500_001
+            )],
+            &[&signers[0], &signers[1]]
+        )
+        .await,
+        Err(error(InstructionError::InsufficientFunds))
+    );
+    // an account which is not a multisig has no fees to collect
+    let wallet = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::collect_fees(
+                &wallet.pubkey(),
+                &destination,
+                &[],
+                0
+            )],
+            &[]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    let destination_lamports = test.lamports(&destination).await;
+    test.send(
+        &[instruction::collect_fees(
+            &multisig,
+            &destination,
+            &signer_keys(&[&signers[0], &signers[1]]),
+            0,
+        )],
+        &[&signers[0], &signers[1]],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        test.lamports(&destination).await,
+        destination_lamports + 500_000
+    );
+    assert_eq!(
+        test.lamports(&multisig).await,
+        Rent::default().minimum_balance(Multisig::LEN)
+    );
+}
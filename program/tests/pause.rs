@@ -0,0 +1,256 @@
+mod common;
+
+use {
+    common::{custom, pool_owning_nft, start_buyout, OwnedPool, Test},
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::signature::Signer,
+    std::slice,
+    token_pool::{
+        error::TokenPoolError,
+        state::{Buyout, BuyoutStage, Escrow, PlatformConfig, TokenPoolStage},
+    },
+    token_pool_client::{instruction, pda::find_platform_config_address},
+};
+
+async fn pause_pool(test: &mut Test, owned: &OwnedPool) {
+    test.send(
+        &[instruction::set_pool_paused(
+            &owned.pool.manager.pubkey(),
+            &owned.pool.address,
+            true,
+        )],
+        &[&owned.pool.manager],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn paused_platform_stops_listings_and_auctions() {
+    let mut test = Test::start().await;
+    let auction = test.auction(100_000, 100).await;
+    let platform_config = PlatformConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        max_members: 100,
+        paused: true,
+    };
+    test.set_state_with_len(
+        &find_platform_config_address().0,
+        &platform_config,
+        PlatformConfig::LEN,
+        0,
+    );
+
+    let seller = test.wallet();
+    let escrow_address = test.program_account(Escrow::LEN);
+    let (mint, token_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let now = test.now().await;
+    for list in [
+        instruction::list_nft(
+            &seller.pubkey(),
+            &escrow_address,
+            &mint,
+            &token_account,
+            1_000_000,
+            0,
+        ),
+        instruction::list_nft_auction(
+            &seller.pubkey(),
+            &escrow_address,
+            &mint,
+            &token_account,
+            1_000_000,
+            10_000,
+            now + 100,
+        ),
+        instruction::list_tokens(
+            &seller.pubkey(),
+            &escrow_address,
+            &mint,
+            &token_account,
+            1_000,
+        ),
+    ] {
+        assert_eq!(
+            test.send(&[list], &[&seller]).await,
+            Err(custom(TokenPoolError::Paused))
+        );
+    }
+
+    let bidder = test.wallet();
+    assert_eq!(
+        test.bid(&auction, &bidder, 100_000).await,
+        Err(custom(TokenPoolError::Paused))
+    );
+    // an ended auction keeps its nft till the platform resumes
+    test.warp_to(now + 100).await;
+    let escrow = test.escrow(&auction.address).await;
+    assert_eq!(
+        test.send(
+            &[instruction::settle_auction(&auction.address, &escrow, None)],
+            &[]
+        )
+        .await,
+        Err(custom(TokenPoolError::Paused))
+    );
+}
+
+#[tokio::test]
+async fn paused_pool_refunds_a_rejected_buyout_but_does_not_complete_one() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let end_time = test.now().await + 100;
+    let buyout = start_buyout(&mut test, &owned.pool, &owned.first, 2_000_000, end_time).await;
+    pause_pool(&mut test, &owned).await;
+
+    test.warp_to(end_time).await;
+    let token_pool = test.token_pool(&owned.pool.address).await;
+    let buyout_state = test.state::<Buyout>(&buyout).await;
+    let complete = instruction::complete_buyout(
+        &owned.pool.address,
+        &token_pool,
+        &buyout_state,
+        &Pubkey::new_unique(),
+    );
+    assert_eq!(
+        test.send(slice::from_ref(&complete), &[]).await,
+        Err(custom(TokenPoolError::Paused))
+    );
+
+    // the second member objected in the window, the deposit goes back to the buyer
+    let rejected = Buyout {
+        objection_share: 40.0,
+        objectors: vec![owned.second.pubkey()],
+        ..buyout_state
+    };
+    let buyout_lamports = test.lamports(&buyout).await;
+    let buyout_len = test.account(&buyout).await.unwrap().data.len();
+    test.set_state_with_len(&buyout, &rejected, buyout_len, 0);
+    test.set_lamports(&buyout, buyout_lamports).await;
+    let buyer_lamports = test.lamports(&owned.first.pubkey()).await;
+    test.send(&[complete], &[]).await.unwrap();
+    assert!(test.account(&buyout).await.is_none());
+    assert_eq!(
+        test.lamports(&owned.first.pubkey()).await,
+        buyer_lamports + buyout_lamports
+    );
+    assert_eq!(
+        test.token_pool(&owned.pool.address).await.stage,
+        TokenPoolStage::NFTOwned
+    );
+}
+
+#[tokio::test]
+async fn paused_pool_holds_buyout_payouts() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let end_time = test.now().await + 100;
+    let buyout = start_buyout(&mut test, &owned.pool, &owned.first, 2_000_000, end_time).await;
+    let mut buyout_state = test.state::<Buyout>(&buyout).await;
+    buyout_state.stage = BuyoutStage::Succeeded;
+    let buyout_len = test.account(&buyout).await.unwrap().data.len();
+    test.set_state_with_len(&buyout, &buyout_state, buyout_len, 0);
+    test.update_pool(&owned.pool, |token_pool| {
+        token_pool.stage = TokenPoolStage::BoughtOut;
+    })
+    .await;
+    pause_pool(&mut test, &owned).await;
+
+    assert_eq!(
+        test.send(
+            &[instruction::claim_buyout_payout(
+                &owned.second.pubkey(),
+                &owned.pool.address,
+                &buyout_state,
+                None,
+            )],
+            &[&owned.second]
+        )
+        .await,
+        Err(custom(TokenPoolError::Paused))
+    );
+}
+
+#[tokio::test]
+async fn platform_is_paused_by_its_authority_only() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let authority = test.wallet();
+    let stranger = test.wallet();
+    let pause = |authority: &Pubkey, paused| instruction::set_platform_paused(authority, paused);
+
+    // nothing is paused before the platform config is set up
+    assert_eq!(
+        test.send(&[pause(&authority.pubkey(), true)], &[&authority])
+            .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    // the upgrade authority of the program sets it up
+    test.program_data(&authority.pubkey());
+    assert_eq!(
+        test.send(
+            &[instruction::set_platform_config(&stranger.pubkey(), 50)],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+    assert_eq!(
+        test.send(
+            &[instruction::set_platform_config(&authority.pubkey(), 1)],
+            &[&authority]
+        )
+        .await,
+        Err(custom(TokenPoolError::MaxMembersExceeded))
+    );
+
+    // the platform config as `SetPlatformConfig` creates it
+    let (platform_config, _) = find_platform_config_address();
+    let config = PlatformConfig {
+        is_initialized: true,
+        authority: authority.pubkey(),
+        max_members: 50,
+        paused: false,
+    };
+    test.set_state_with_len(&platform_config, &config, PlatformConfig::LEN, 0);
+    for instruction in [
+        pause(&stranger.pubkey(), true),
+        instruction::set_platform_config(&stranger.pubkey(), 20),
+    ] {
+        assert_eq!(
+            test.send(&[instruction], &[&stranger]).await,
+            Err(custom(TokenPoolError::WrongManager))
+        );
+    }
+
+    test.send(&[pause(&authority.pubkey(), true)], &[&authority])
+        .await
+        .unwrap();
+    assert!(test.state::<PlatformConfig>(&platform_config).await.paused);
+    let member = test.wallet();
+    let join = instruction::add_member(
+        &member.pubkey(),
+        &pool.address,
+        &pool.treasury,
+        None,
+        100_000,
+    );
+    assert_eq!(
+        test.send(slice::from_ref(&join), &[&member]).await,
+        Err(custom(TokenPoolError::Paused))
+    );
+
+    test.send(&[pause(&authority.pubkey(), false)], &[&authority])
+        .await
+        .unwrap();
+    test.send(&[join], &[&member]).await.unwrap();
+    assert!(test
+        .token_pool(&pool.address)
+        .await
+        .pool_member_list
+        .find_member(member.pubkey()));
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use {
+    common::{custom, pool_owning_nft, OwnedPool, Test},
+    solana_program::pubkey::Pubkey,
+    solana_program_test::tokio,
+    solana_sdk::signature::Signer,
+    token_pool::{error::TokenPoolError, state::PoolTargetKind},
+    token_pool_client::instruction,
+};
+
+/// the pool bought 500 tokens of `mint` instead of an nft, they are held by its vault
+async fn pool_owning_tokens(test: &mut Test, mint: &Pubkey) -> (OwnedPool, Pubkey) {
+    let owned = pool_owning_nft(test).await;
+    test.update_pool(&owned.pool, |token_pool| {
+        token_pool.target_kind = PoolTargetKind::Fungible;
+        token_pool.target_token = *mint;
+        token_pool.target_token_amount = 500;
+    })
+    .await;
+    let vault = test.token_pool(&owned.pool.address).await.vault;
+    let pool_tokens = test.token_account(mint, &vault, 500);
+    (owned, pool_tokens)
+}
+
+#[tokio::test]
+async fn only_members_of_a_pool_owning_tokens_redeem() {
+    let mut test = Test::start().await;
+    let owned = pool_owning_nft(&mut test).await;
+    let mint = test.mint();
+    let vault = test.token_pool(&owned.pool.address).await.vault;
+    let nft_account = test.token_account(&mint, &vault, 1);
+    let member_tokens = test.token_account(&mint, &owned.first.pubkey(), 0);
+    assert_eq!(
+        test.send(
+            &[instruction::redeem(
+                &owned.first.pubkey(),
+                &owned.pool.address,
+                &nft_account,
+                &member_tokens,
+            )],
+            &[&owned.first]
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongTargetKind))
+    );
+
+    let (owned, pool_tokens) = pool_owning_tokens(&mut test, &mint).await;
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::redeem(
+                &stranger.pubkey(),
+                &owned.pool.address,
+                &pool_tokens,
+                &member_tokens,
+            )],
+            &[&stranger]
+        )
+        .await,
+        Err(custom(TokenPoolError::MemberNotInPool))
+    );
+}
+
+#[tokio::test]
+async fn members_redeem_from_the_vault_after_their_refund() {
+    let mut test = Test::start().await;
+    let mint = test.mint();
+    let (owned, pool_tokens) = pool_owning_tokens(&mut test, &mint).await;
+    let member = owned.second.pubkey();
+    let member_tokens = test.token_account(&mint, &member, 0);
+    let redeem = |pool_tokens: &Pubkey| {
+        instruction::redeem(&member, &owned.pool.address, pool_tokens, &member_tokens)
+    };
+
+    assert_eq!(
+        test.send(&[redeem(&pool_tokens)], &[&owned.second]).await,
+        Err(custom(TokenPoolError::RefundNotClaimed))
+    );
+    test.send(
+        &[instruction::claim_refund(
+            &member,
+            &owned.pool.address,
+            &owned.pool.treasury,
+            None,
+        )],
+        &[&owned.second],
+    )
+    .await
+    .unwrap();
+
+    // the tokens come out of an account of the vault holding the mint of the pool
+    let vault = test.token_pool(&owned.pool.address).await.vault;
+    let other_mint = test.mint();
+    let other_tokens = test.token_account(&other_mint, &vault, 500);
+    let foreign_tokens = test.token_account(&mint, &Pubkey::new_unique(), 500);
+    for pool_tokens in [other_tokens, foreign_tokens] {
+        assert_eq!(
+            test.send(&[redeem(&pool_tokens)], &[&owned.second]).await,
+            Err(custom(TokenPoolError::InvalidData))
+        );
+    }
+    assert_eq!(
+        test.token_pool(&owned.pool.address)
+            .await
+            .pool_member_list
+            .share_of(member),
+        40.0
+    );
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use {
+    common::{custom, Pool, Test},
+    solana_program::{clock::UnixTimestamp, instruction::InstructionError, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    },
+    std::slice,
+    token_pool::error::TokenPoolError,
+    token_pool_client::instruction,
+};
+
+/// a token pool taking contributions over its target of 1_000_000 lamports till the returned end
+async fn oversubscribed_pool(test: &mut Test) -> (Pool, UnixTimestamp) {
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let subscription_end = test.now().await + 100;
+    test.send(
+        &[instruction::set_subscription_end(
+            &pool.manager.pubkey(),
+            &pool.address,
+            subscription_end,
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    (pool, subscription_end)
+}
+
+#[tokio::test]
+async fn oversubscribed_pool_refunds_the_excess_pro_rata() {
+    let mut test = Test::start().await;
+    let (pool, subscription_end) = oversubscribed_pool(&mut test).await;
+    let first = test.wallet();
+    let second = test.wallet();
+    test.join(&pool, &first, 900_000).await;
+    test.join(&pool, &second, 300_000).await;
+    assert_eq!(
+        test.token_pool(&pool.address).await.current_balance,
+        1_200_000
+    );
+
+    let close = instruction::close_subscription(&pool.address);
+    assert_eq!(
+        test.send(slice::from_ref(&close), &[]).await,
+        Err(custom(TokenPoolError::SubscriptionOpen))
+    );
+    test.warp_to(subscription_end).await;
+    test.send(&[close], &[]).await.unwrap();
+
+    // the 200_000 over the target is due back in proportion to what each member deposited
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.current_balance, 1_000_000);
+    assert_eq!(token_pool.subscription_end, 0);
+    let members = &token_pool.pool_member_list;
+    let first_member = members.members[members.get_member_index(first.pubkey()).unwrap()];
+    assert_eq!(first_member.amount_deposited, 750_000);
+    assert_eq!(first_member.share, 75.0);
+    assert_eq!(first_member.refund_due, 150_000);
+    assert_eq!(members.share_of(second.pubkey()), 25.0);
+
+    let claim_refund = |member: &Keypair| {
+        instruction::claim_refund(&member.pubkey(), &pool.address, &pool.treasury, None)
+    };
+    let first_lamports = test.lamports(&first.pubkey()).await;
+    let treasury_lamports = test.lamports(&pool.treasury).await;
+    test.send(&[claim_refund(&first)], &[&first]).await.unwrap();
+    assert_eq!(
+        test.lamports(&first.pubkey()).await,
+        first_lamports + 150_000
+    );
+    assert_eq!(
+        test.lamports(&pool.treasury).await,
+        treasury_lamports - 150_000
+    );
+    assert_eq!(
+        test.send(&[claim_refund(&first)], &[&first]).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    test.send(&[claim_refund(&second)], &[&second])
+        .await
+        .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.pool_member_list.total_refund_due(), Some(0));
+}
+
+#[tokio::test]
+async fn shares_move_only_once_their_refund_is_claimed() {
+    let mut test = Test::start().await;
+    let (pool, subscription_end) = oversubscribed_pool(&mut test).await;
+    let first = test.wallet();
+    let second = test.wallet();
+    test.join(&pool, &first, 900_000).await;
+    test.join(&pool, &second, 300_000).await;
+    test.warp_to(subscription_end).await;
+    test.send(&[instruction::close_subscription(&pool.address)], &[])
+        .await
+        .unwrap();
+
+    let transfer =
+        instruction::transfer_share(&second.pubkey(), &pool.address, &first.pubkey(), None, 0);
+    assert_eq!(
+        test.send(slice::from_ref(&transfer), &[&second]).await,
+        Err(custom(TokenPoolError::RefundNotClaimed))
+    );
+    test.send(
+        &[instruction::claim_refund(
+            &second.pubkey(),
+            &pool.address,
+            &pool.treasury,
+            None,
+        )],
+        &[&second],
+    )
+    .await
+    .unwrap();
+    test.send(&[transfer], &[&second]).await.unwrap();
+}
+
+#[tokio::test]
+async fn members_join_only_till_the_subscription_ends() {
+    let mut test = Test::start().await;
+    let (pool, subscription_end) = oversubscribed_pool(&mut test).await;
+    let first = test.wallet();
+    test.join(&pool, &first, 1_000_000).await;
+
+    // an oversubscribing pool still asks for the minimum amount once its target is reached
+    let second = test.wallet();
+    let join = |amount| {
+        instruction::add_member(
+            &second.pubkey(),
+            &pool.address,
+            &pool.treasury,
+            None,
+            amount,
+        )
+    };
+    assert_eq!(
+        test.send(&[join(50_000)], &[&second]).await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InsufficientFunds
+        ))
+    );
+    test.warp_to(subscription_end).await;
+    assert_eq!(
+        test.send(&[join(100_000)], &[&second]).await,
+        Err(custom(TokenPoolError::SubscriptionEnded))
+    );
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use {
+    common::{custom, pool_of_two, Pool, Test},
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::signature::{Keypair, Signer},
+    token_pool::{
+        error::TokenPoolError,
+        state::{ShareBid, ShareBidStage, TOTAL_SHARE_UNITS},
+    },
+    token_pool_client::{instruction, pda::find_share_bid_address},
+};
+
+/// the bidder escrows 2_000 share units at 100 lamports each as `PlaceShareBid` leaves it
+fn place_bid(test: &mut Test, pool: &Pool, bidder: &Keypair) -> Pubkey {
+    let (address, _) = find_share_bid_address(&pool.address, &bidder.pubkey());
+    let share_bid = ShareBid {
+        stage: ShareBidStage::Open,
+        token_pool: pool.address,
+        bidder: bidder.pubkey(),
+        price_per_unit: 100,
+        quantity: 2_000,
+    };
+    test.set_state_with_len(&address, &share_bid, ShareBid::LEN, 200_000);
+    address
+}
+
+#[tokio::test]
+async fn share_bid_is_filled_by_members_at_its_price() {
+    let mut test = Test::start().await;
+    let (pool, first, _) = pool_of_two(&mut test).await;
+    let bidder = test.wallet();
+    let share_bid = place_bid(&mut test, &pool, &bidder);
+
+    let place = |price_per_unit, quantity| {
+        instruction::place_share_bid(
+            &bidder.pubkey(),
+            &pool.address,
+            None,
+            price_per_unit,
+            quantity,
+        )
+    };
+    assert_eq!(
+        test.send(&[place(0, 1_000)], &[&bidder]).await,
+        Err(custom(TokenPoolError::WrongAmountData))
+    );
+    assert_eq!(
+        test.send(&[place(100, TOTAL_SHARE_UNITS + 1)], &[&bidder])
+            .await,
+        Err(custom(TokenPoolError::WrongAmountData))
+    );
+    // a bidder has one open bid per pool
+    assert_eq!(
+        test.send(&[place(100, 1_000)], &[&bidder]).await,
+        Err(custom(TokenPoolError::InvalidShareBid))
+    );
+
+    let fill = |member: &Pubkey, units, min_price_per_unit| {
+        instruction::fill_bid(
+            member,
+            &pool.address,
+            &bidder.pubkey(),
+            units,
+            min_price_per_unit,
+        )
+    };
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(&[fill(&stranger.pubkey(), 1_000, 100)], &[&stranger])
+            .await,
+        Err(custom(TokenPoolError::MemberNotInPool))
+    );
+    assert_eq!(
+        test.send(&[fill(&first.pubkey(), 1_000, 101)], &[&first])
+            .await,
+        Err(custom(TokenPoolError::BidPriceBelowMinimum))
+    );
+    assert_eq!(
+        test.send(&[fill(&first.pubkey(), 2_001, 100)], &[&first])
+            .await,
+        Err(custom(TokenPoolError::InsufficientShare))
+    );
+
+    let first_lamports = test.lamports(&first.pubkey()).await;
+    test.send(&[fill(&first.pubkey(), 1_000, 100)], &[&first])
+        .await
+        .unwrap();
+    assert_eq!(
+        test.lamports(&first.pubkey()).await,
+        first_lamports + 100_000
+    );
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.share_of(first.pubkey()), 50.0);
+    assert_eq!(members.share_of(bidder.pubkey()), 10.0);
+    assert_eq!(test.state::<ShareBid>(&share_bid).await.quantity, 1_000);
+
+    // filling what is left of the bid closes it to the bidder
+    let bid_lamports = test.lamports(&share_bid).await;
+    let bidder_lamports = test.lamports(&bidder.pubkey()).await;
+    test.send(&[fill(&first.pubkey(), 1_000, 100)], &[&first])
+        .await
+        .unwrap();
+    assert!(test.account(&share_bid).await.is_none());
+    assert_eq!(
+        test.lamports(&bidder.pubkey()).await,
+        bidder_lamports + bid_lamports - 100_000
+    );
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.share_of(first.pubkey()), 40.0);
+    assert_eq!(members.share_of(bidder.pubkey()), 20.0);
+}
+
+#[tokio::test]
+async fn share_bid_is_cancelled_back_to_its_bidder_only() {
+    let mut test = Test::start().await;
+    let (pool, _, _) = pool_of_two(&mut test).await;
+    let bidder = test.wallet();
+    let share_bid = place_bid(&mut test, &pool, &bidder);
+
+    let stranger = test.wallet();
+    let mut cancel = instruction::cancel_share_bid(&bidder.pubkey(), &pool.address);
+    cancel.accounts[0].pubkey = stranger.pubkey();
+    assert_eq!(
+        test.send(&[cancel], &[&stranger]).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    let bid_lamports = test.lamports(&share_bid).await;
+    let bidder_lamports = test.lamports(&bidder.pubkey()).await;
+    test.send(
+        &[instruction::cancel_share_bid(
+            &bidder.pubkey(),
+            &pool.address,
+        )],
+        &[&bidder],
+    )
+    .await
+    .unwrap();
+    assert!(test.account(&share_bid).await.is_none());
+    assert_eq!(
+        test.lamports(&bidder.pubkey()).await,
+        bidder_lamports + bid_lamports
+    );
+}
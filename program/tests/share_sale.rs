@@ -0,0 +1,223 @@
+mod common;
+
+use {
+    common::{custom, pool_of_two, Test},
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::signature::Signer,
+    token_pool::{
+        error::TokenPoolError,
+        state::{Escrow, EscrowStage, DELEGATE_SELL},
+    },
+    token_pool_client::{instruction, pda::find_share_escrow_vault_address},
+};
+
+#[tokio::test]
+async fn share_sale_is_cancelled_back_to_its_seller() {
+    let mut test = Test::start().await;
+    let (pool, first, _) = pool_of_two(&mut test).await;
+    // the custodian of the share puts it on sale and pays for the escrow
+    let custodian = test.wallet();
+    test.send(
+        &[instruction::approve_delegate(
+            &first.pubkey(),
+            &pool.address,
+            &custodian.pubkey(),
+            DELEGATE_SELL,
+        )],
+        &[&first],
+    )
+    .await
+    .unwrap();
+    let escrow_address = test.program_account(Escrow::LEN);
+    test.send(
+        &[instruction::sell_share(
+            &first.pubkey(),
+            &pool.address,
+            &escrow_address,
+            None,
+            Some(&custodian.pubkey()),
+            300_000,
+            0,
+        )],
+        &[&custodian],
+    )
+    .await
+    .unwrap();
+    let (escrow_vault, _) = find_share_escrow_vault_address(&first.pubkey(), &pool.address);
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.share_of(escrow_vault), 60.0);
+    assert!(!members.find_member(first.pubkey()));
+
+    let escrow = test.escrow(&escrow_address).await;
+    let stranger = test.wallet();
+    let mut cancel = instruction::cancel_share_sale(&pool.address, &escrow_address, &escrow, None);
+    cancel.accounts[0].pubkey = stranger.pubkey();
+    assert_eq!(
+        test.send(&[cancel], &[&stranger]).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    let escrow_lamports = test.lamports(&escrow_address).await;
+    let custodian_lamports = test.lamports(&custodian.pubkey()).await;
+    test.send(
+        &[instruction::cancel_share_sale(
+            &pool.address,
+            &escrow_address,
+            &escrow,
+            None,
+        )],
+        &[&first],
+    )
+    .await
+    .unwrap();
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.share_of(first.pubkey()), 60.0);
+    assert!(!members.find_member(escrow_vault));
+    assert!(test.account(&escrow_address).await.is_none());
+    assert_eq!(
+        test.lamports(&custodian.pubkey()).await,
+        custodian_lamports + escrow_lamports
+    );
+}
+
+#[tokio::test]
+async fn share_sale_is_held_by_the_escrow_vault_of_its_seller_and_pool() {
+    let mut test = Test::start().await;
+    let (pool, first, _) = pool_of_two(&mut test).await;
+    let other_pool = Pubkey::new_unique();
+
+    let escrow_address = test.program_account(Escrow::LEN);
+    let mut sell = instruction::sell_share(
+        &first.pubkey(),
+        &pool.address,
+        &escrow_address,
+        None,
+        None,
+        300_000,
+        0,
+    );
+    sell.accounts[3].pubkey = find_share_escrow_vault_address(&first.pubkey(), &other_pool).0;
+    assert_eq!(
+        test.send(&[sell], &[&first]).await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+
+    // a sale whose share is held by any other vault is neither cancelled nor bought
+    let foreign_vault = Pubkey::new_unique();
+    test.update_pool(&pool, |token_pool| {
+        token_pool
+            .pool_member_list
+            .init_escrow(first.pubkey(), escrow_address, foreign_vault)
+    })
+    .await;
+    let escrow = Escrow {
+        stage: EscrowStage::Initialized,
+        seller: first.pubkey(),
+        escrow_vault: foreign_vault,
+        share: 60.0,
+        amount: 300_000,
+        rent_payer: first.pubkey(),
+        ..Escrow::default()
+    };
+    test.set_state_with_len(&escrow_address, &escrow, Escrow::LEN, 0);
+    assert_eq!(
+        test.send(
+            &[instruction::cancel_share_sale(
+                &pool.address,
+                &escrow_address,
+                &escrow,
+                None
+            )],
+            &[&first]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    let buyer = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::buy_share(
+                &buyer.pubkey(),
+                &pool.address,
+                &escrow_address,
+                &escrow,
+                None,
+                300_000
+            )],
+            &[&buyer]
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    assert_eq!(
+        test.token_pool(&pool.address)
+            .await
+            .pool_member_list
+            .share_of(foreign_vault),
+        60.0
+    );
+}
+
+#[tokio::test]
+async fn private_share_sale_is_bought_by_its_buyer_only() {
+    let mut test = Test::start().await;
+    let (pool, first, _) = pool_of_two(&mut test).await;
+    let custodian = test.wallet();
+    test.send(
+        &[instruction::approve_delegate(
+            &first.pubkey(),
+            &pool.address,
+            &custodian.pubkey(),
+            DELEGATE_SELL,
+        )],
+        &[&first],
+    )
+    .await
+    .unwrap();
+    let buyer = test.wallet();
+    let escrow_address = test.program_account(Escrow::LEN);
+    test.send(
+        &[instruction::sell_share(
+            &first.pubkey(),
+            &pool.address,
+            &escrow_address,
+            Some(&buyer.pubkey()),
+            Some(&custodian.pubkey()),
+            300_000,
+            0,
+        )],
+        &[&custodian],
+    )
+    .await
+    .unwrap();
+    let escrow = test.escrow(&escrow_address).await;
+    assert_eq!(escrow.allowed_buyer, buyer.pubkey());
+
+    let buy = |buyer: &Pubkey| {
+        instruction::buy_share(
+            buyer,
+            &pool.address,
+            &escrow_address,
+            &escrow,
+            None,
+            300_000,
+        )
+    };
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(&[buy(&stranger.pubkey())], &[&stranger]).await,
+        Err(custom(TokenPoolError::BuyerNotAllowed))
+    );
+
+    let first_lamports = test.lamports(&first.pubkey()).await;
+    test.send(&[buy(&buyer.pubkey())], &[&buyer]).await.unwrap();
+    let members = test.token_pool(&pool.address).await.pool_member_list;
+    assert_eq!(members.share_of(buyer.pubkey()), 60.0);
+    assert!(!members.find_member(stranger.pubkey()));
+    assert!(test.account(&escrow_address).await.is_none());
+    assert_eq!(
+        test.lamports(&first.pubkey()).await,
+        first_lamports + 300_000
+    );
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use {
+    common::{custom, Test},
+    solana_program::{instruction::InstructionError, pubkey::Pubkey},
+    solana_program_test::tokio,
+    solana_sdk::{signature::Signer, transaction::TransactionError},
+    token_pool::{
+        error::TokenPoolError,
+        state::{AllowedMints, PoolTargetKind},
+    },
+    token_pool_client::instruction,
+};
+
+#[tokio::test]
+async fn allowed_mints_turn_the_pool_into_a_collection_keeping_its_mint() {
+    let mut test = Test::start().await;
+    let target_mint = Pubkey::new_unique();
+    let pool = test.create_pool(&target_mint, 1_000_000, 100_000).await;
+    let allowed_mints = test.program_account(AllowedMints::account_size(2));
+
+    let mint = Pubkey::new_unique();
+    test.send(
+        &[instruction::add_allowed_mints(
+            &pool.manager.pubkey(),
+            &pool.address,
+            &allowed_mints,
+            &[mint],
+        )],
+        &[&pool.manager],
+    )
+    .await
+    .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.target_kind, PoolTargetKind::Collection);
+    assert_eq!(token_pool.target_token, allowed_mints);
+    let list: AllowedMints = test.state(&allowed_mints).await;
+    assert_eq!(list.token_pool, pool.address);
+    assert_eq!(list.mints, vec![target_mint, mint]);
+
+    assert_eq!(
+        test.send(
+            &[instruction::add_allowed_mints(
+                &pool.manager.pubkey(),
+                &pool.address,
+                &allowed_mints,
+                &[Pubkey::new_unique()],
+            )],
+            &[&pool.manager],
+        )
+        .await,
+        Err(custom(TokenPoolError::AllowedMintsFull))
+    );
+}
+
+#[tokio::test]
+async fn allowed_mints_are_set_by_the_manager_before_members_contribute() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let allowed_mints = test.program_account(AllowedMints::account_size(10));
+    let mints = [Pubkey::new_unique()];
+
+    let stranger = test.wallet();
+    assert_eq!(
+        test.send(
+            &[instruction::add_allowed_mints(
+                &stranger.pubkey(),
+                &pool.address,
+                &allowed_mints,
+                &mints,
+            )],
+            &[&stranger],
+        )
+        .await,
+        Err(custom(TokenPoolError::WrongManager))
+    );
+
+    let member = test.wallet();
+    test.join(&pool, &member, 100_000).await;
+    assert_eq!(
+        test.send(
+            &[instruction::add_allowed_mints(
+                &pool.manager.pubkey(),
+                &pool.address,
+                &allowed_mints,
+                &mints,
+            )],
+            &[&pool.manager],
+        )
+        .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    assert_eq!(
+        test.token_pool(&pool.address).await.target_kind,
+        PoolTargetKind::SingleMint
+    );
+}
+
+#[tokio::test]
+async fn fungible_target_is_set_by_the_manager_before_members_contribute() {
+    let mut test = Test::start().await;
+    let pool = test
+        .create_pool(&Pubkey::new_unique(), 1_000_000, 100_000)
+        .await;
+    let mint = test.mint();
+    let set_target = |mint: &Pubkey, token_amount| {
+        instruction::set_fungible_target(&pool.manager.pubkey(), &pool.address, mint, token_amount)
+    };
+
+    assert_eq!(
+        test.send(&[set_target(&mint, 0)], &[&pool.manager]).await,
+        Err(custom(TokenPoolError::WrongAmountData))
+    );
+    assert_eq!(
+        test.send(&[set_target(&Pubkey::new_unique(), 500)], &[&pool.manager])
+            .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IllegalOwner
+        ))
+    );
+    test.send(&[set_target(&mint, 500)], &[&pool.manager])
+        .await
+        .unwrap();
+    let token_pool = test.token_pool(&pool.address).await;
+    assert_eq!(token_pool.target_kind, PoolTargetKind::Fungible);
+    assert_eq!(token_pool.target_token, mint);
+    assert_eq!(token_pool.target_token_amount, 500);
+
+    let member = test.wallet();
+    test.join(&pool, &member, 100_000).await;
+    let other_mint = test.mint();
+    assert_eq!(
+        test.send(&[set_target(&other_mint, 500)], &[&pool.manager])
+            .await,
+        Err(custom(TokenPoolError::InvalidData))
+    );
+    assert_eq!(test.token_pool(&pool.address).await.target_token, mint);
+}
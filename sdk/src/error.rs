@@ -0,0 +1,25 @@
+use {solana_program::pubkey::Pubkey, thiserror::Error};
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Request to the rpc node failed
+    #[error("rpc request failed: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    /// Account does not exist on chain
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// Account is not owned by the token pool program
+    #[error("account {0} is not owned by the token pool program")]
+    InvalidOwner(Pubkey),
+    /// Account data could not be decoded into the expected state
+    #[error("account {0} has invalid data")]
+    InvalidAccountData(Pubkey),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(e))
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
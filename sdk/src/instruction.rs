@@ -0,0 +1,1364 @@
+use {
+    crate::pda::{
+        find_buyout_address, find_listing_vault_address, find_platform_config_address,
+        find_pool_vault_address, find_program_data_address, find_share_bid_address,
+        find_share_escrow_vault_address,
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        clock::UnixTimestamp,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program, sysvar,
+    },
+    token_pool::{
+        instructions::Payload,
+        state::{
+            AdmissionPolicy, AllowedMints, Buyout, Escrow, Inventory, MaximumKind, PoolTargetKind,
+            TokenPool,
+        },
+    },
+};
+
+/// Variants of the instruction payload understood by the processor
+pub mod variant {
+    pub const INITIALIZE_POOL: u8 = 0;
+    pub const ADD_MEMBER: u8 = 1;
+    pub const SELL_SHARE: u8 = 2;
+    pub const BUY_SHARE: u8 = 3;
+    pub const UPGRADE_SHARE: u8 = 4;
+    pub const LIST_NFT: u8 = 5;
+    pub const EXECUTE_NFT_BUY: u8 = 6;
+    pub const PROPOSE_MANAGER: u8 = 7;
+    pub const GET_NFT_AUTHORITY: u8 = 8;
+    pub const CANCEL_SHARE_SALE: u8 = 9;
+    pub const CANCEL_NFT_LISTING: u8 = 10;
+    pub const LIST_NFT_AUCTION: u8 = 11;
+    pub const PLACE_BID: u8 = 12;
+    pub const SETTLE_AUCTION: u8 = 13;
+    pub const PLACE_SHARE_BID: u8 = 14;
+    pub const CANCEL_SHARE_BID: u8 = 15;
+    pub const FILL_BID: u8 = 16;
+    pub const ADD_ALLOWED_MINTS: u8 = 17;
+    pub const INITIALIZE_BASKET: u8 = 18;
+    pub const SET_FUNGIBLE_TARGET: u8 = 19;
+    pub const LIST_TOKENS: u8 = 20;
+    pub const EXECUTE_TOKEN_BUY: u8 = 21;
+    pub const REDEEM: u8 = 22;
+    pub const SET_BUYOUT_TERMS: u8 = 23;
+    pub const START_BUYOUT: u8 = 24;
+    pub const OBJECT_BUYOUT: u8 = 25;
+    pub const COMPLETE_BUYOUT: u8 = 26;
+    pub const CLAIM_BUYOUT_PAYOUT: u8 = 27;
+    pub const SET_GATE_MINT: u8 = 28;
+    pub const SET_MAXIMUM_AMOUNT: u8 = 29;
+    pub const SET_MANAGER_SHARE: u8 = 30;
+    pub const SET_PLATFORM_CONFIG: u8 = 31;
+    pub const SET_ADMISSION_POLICY: u8 = 32;
+    pub const SET_SUBSCRIPTION_END: u8 = 33;
+    pub const CLOSE_SUBSCRIPTION: u8 = 34;
+    pub const CLAIM_REFUND: u8 = 35;
+    pub const SET_ESCROW_EXPIRY: u8 = 36;
+    pub const EXPIRE_ESCROW: u8 = 37;
+    pub const TRANSFER_SHARE: u8 = 38;
+    pub const APPROVE_DELEGATE: u8 = 39;
+    pub const REVOKE_DELEGATE: u8 = 40;
+    pub const INITIALIZE_MULTISIG: u8 = 41;
+    pub const COLLECT_FEES: u8 = 42;
+    pub const ACCEPT_MANAGER: u8 = 43;
+    pub const CANCEL_MANAGER_TRANSFER: u8 = 44;
+    pub const SET_POOL_PAUSED: u8 = 45;
+    pub const SET_PLATFORM_PAUSED: u8 = 46;
+    pub const CLOSE_POOL: u8 = 47;
+    pub const CLOSE_ESCROW: u8 = 48;
+    pub const CLOSE_TREASURY: u8 = 49;
+    pub const SWEEP_TREASURY: u8 = 50;
+    pub const CLOSE_ALLOWED_MINTS: u8 = 51;
+    pub const MIGRATE_TOKEN_POOL: u8 = 52;
+    pub const MIGRATE_ESCROW: u8 = 53;
+}
+
+/// serialize the payload, arguments not used by an instruction are left empty
+fn payload(variant: u8, arg1: u64, arg2: u64, arg3: String, arg4: u32, arg5: u64) -> Vec<u8> {
+    Payload {
+        variant,
+        arg1,
+        arg2,
+        arg3,
+        arg4,
+        arg5,
+    }
+    .try_to_vec()
+    .unwrap()
+}
+
+fn amount_payload(variant: u8, amount: u64) -> Vec<u8> {
+    payload(variant, amount, 0, String::new(), 0, 0)
+}
+
+/// Creates an `InitializePool` instruction, the program allocates the token pool account which has
+/// to sign, the treasury account should already be allocated and owned by the program
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_pool(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    treasury: &Pubkey,
+    target_token: &Pubkey,
+    target_amount: u64,
+    minimum_amount: u64,
+    description: String,
+    minimum_exemption_amount: u64,
+) -> Instruction {
+    let (vault, _) = find_pool_vault_address(token_pool);
+    let (platform_config, _) = find_platform_config_address();
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*manager, true),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(*target_token, false),
+            AccountMeta::new(*token_pool, true),
+            AccountMeta::new_readonly(*treasury, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(platform_config, false),
+        ],
+        data: payload(
+            variant::INITIALIZE_POOL,
+            target_amount,
+            minimum_amount,
+            description,
+            0,
+            minimum_exemption_amount,
+        ),
+    }
+}
+
+/// Creates an `AddMember` instruction
+pub fn add_member(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    treasury: &Pubkey,
+    gate_token_account: Option<&Pubkey>,
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*member, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(*treasury, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        platform_config_account(),
+    ];
+    push_gate_token_account(&mut accounts, gate_token_account);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::ADD_MEMBER, amount),
+    }
+}
+
+/// the platform config tells the program whether the platform is paused
+fn platform_config_account() -> AccountMeta {
+    let (platform_config, _) = find_platform_config_address();
+    AccountMeta::new_readonly(platform_config, false)
+}
+
+/// a gated token pool needs the token account of the gate mint held by whoever joins it
+fn push_gate_token_account(accounts: &mut Vec<AccountMeta>, gate_token_account: Option<&Pubkey>) {
+    if let Some(gate_token_account) = gate_token_account {
+        accounts.push(AccountMeta::new_readonly(*gate_token_account, false));
+    }
+}
+
+/// The delegate signs for the member, which is the first account
+fn push_delegate(accounts: &mut Vec<AccountMeta>, delegate: Option<&Pubkey>) {
+    if let Some(delegate) = delegate {
+        accounts[0].is_signer = false;
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
+}
+
+/// Creates a `SellShare` instruction, the escrow state account should already
+/// be allocated and owned by the program, a private sale only lets the allowed buyer buy the share.
+/// a non zero `expires_in` expires the sale that many seconds after it starts
+pub fn sell_share(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    escrow_state: &Pubkey,
+    allowed_buyer: Option<&Pubkey>,
+    delegate: Option<&Pubkey>,
+    amount: u64,
+    expires_in: u32,
+) -> Instruction {
+    let (escrow_vault, _) = find_share_escrow_vault_address(member, token_pool);
+    let mut accounts = vec![
+        AccountMeta::new(*member, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(*escrow_state, false),
+        AccountMeta::new_readonly(escrow_vault, false),
+        platform_config_account(),
+    ];
+    if allowed_buyer.is_some() || delegate.is_some() {
+        // default pubkey in place of the allowed buyer lets anyone buy
+        let allowed_buyer = allowed_buyer.copied().unwrap_or_default();
+        accounts.push(AccountMeta::new_readonly(allowed_buyer, false));
+    }
+    push_delegate(&mut accounts, delegate);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: payload(variant::SELL_SHARE, amount, 0, String::new(), expires_in, 0),
+    }
+}
+
+/// Creates a `BuyShare` instruction from the decoded escrow of the share sale
+pub fn buy_share(
+    buyer: &Pubkey,
+    token_pool: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    gate_token_account: Option<&Pubkey>,
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*buyer, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new_readonly(escrow.escrow_vault, false),
+        AccountMeta::new(escrow.seller, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        platform_config_account(),
+        AccountMeta::new(escrow.rent_payer, false),
+    ];
+    push_gate_token_account(&mut accounts, gate_token_account);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::BUY_SHARE, amount),
+    }
+}
+
+/// Creates an `UpgradeShare` instruction
+pub fn upgrade_share(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    treasury: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*member, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new(*treasury, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            platform_config_account(),
+        ],
+        data: amount_payload(variant::UPGRADE_SHARE, amount),
+    }
+}
+
+/// Creates a `ListNFT` instruction, the escrow state account should already
+/// be allocated and owned by the program. a non zero `expires_in` expires the listing
+/// that many seconds after it starts
+pub fn list_nft(
+    seller: &Pubkey,
+    escrow_state: &Pubkey,
+    nft_mint: &Pubkey,
+    nft_account: &Pubkey,
+    amount: u64,
+    expires_in: u32,
+) -> Instruction {
+    list_nft_with_payload(
+        seller,
+        escrow_state,
+        nft_mint,
+        nft_account,
+        payload(variant::LIST_NFT, amount, 0, String::new(), expires_in, 0),
+    )
+}
+
+/// Creates a `ListNFT` instruction for a dutch auction, whose price decays from
+/// start price to floor price till end time
+#[allow(clippy::too_many_arguments)]
+pub fn list_nft_dutch_auction(
+    seller: &Pubkey,
+    escrow_state: &Pubkey,
+    nft_mint: &Pubkey,
+    nft_account: &Pubkey,
+    start_price: u64,
+    floor_price: u64,
+    end_time: UnixTimestamp,
+    expires_in: u32,
+) -> Instruction {
+    list_nft_with_payload(
+        seller,
+        escrow_state,
+        nft_mint,
+        nft_account,
+        payload(
+            variant::LIST_NFT,
+            start_price,
+            floor_price,
+            String::new(),
+            expires_in,
+            end_time as u64,
+        ),
+    )
+}
+
+fn list_nft_with_payload(
+    seller: &Pubkey,
+    escrow_state: &Pubkey,
+    nft_mint: &Pubkey,
+    nft_account: &Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    let (vault, _) = find_listing_vault_address(nft_account);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*seller, true),
+            AccountMeta::new(*escrow_state, false),
+            AccountMeta::new(*nft_mint, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(*nft_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            platform_config_account(),
+        ],
+        data,
+    }
+}
+
+/// Creates an `ExecuteNFTBuy` instruction from the decoded token pool and escrow
+pub fn execute_nft_buy(
+    buyer: &Pubkey,
+    token_pool_address: &Pubkey,
+    token_pool: &TokenPool,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    amount: u64,
+) -> Instruction {
+    let mut instruction = Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*buyer, true),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new(token_pool.vault, false),
+            AccountMeta::new(escrow.nft, false),
+            AccountMeta::new(*token_pool_address, false),
+            AccountMeta::new(token_pool.treasurey, false),
+            AccountMeta::new(escrow.seller, false),
+            AccountMeta::new(escrow.nft_mint, false),
+            AccountMeta::new(escrow.escrow_vault, false),
+            AccountMeta::new(token_pool.manager, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            platform_config_account(),
+        ],
+        data: amount_payload(variant::EXECUTE_NFT_BUY, amount),
+    };
+    push_allowed_mints(&mut instruction, token_pool);
+    if token_pool.target_kind == PoolTargetKind::Basket {
+        instruction
+            .accounts
+            .push(AccountMeta::new(token_pool.inventory, false));
+    }
+    instruction
+}
+
+/// a collection or basket token pool needs its allowed mints account to check the nft it is buying
+fn push_allowed_mints(instruction: &mut Instruction, token_pool: &TokenPool) {
+    if token_pool.target_kind != PoolTargetKind::SingleMint {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(token_pool.target_token, false));
+    }
+}
+
+/// Creates a `ProposeManager` instruction, the new manager takes over with `AcceptManager`
+pub fn propose_manager(manager: &Pubkey, token_pool: &Pubkey, new_manager: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new_readonly(*new_manager, false),
+        ],
+        data: amount_payload(variant::PROPOSE_MANAGER, 0),
+    }
+}
+
+/// Creates a `GetNFTAuthority` instruction, a basket token pool needs its decoded inventory
+pub fn get_nft_authority(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    nft_mint: &Pubkey,
+    nft_account: &Pubkey,
+    inventory: Option<(&Pubkey, &Inventory)>,
+) -> Instruction {
+    let (vault, _) = find_pool_vault_address(token_pool);
+    let mut accounts = vec![
+        AccountMeta::new(*member, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(*nft_mint, false),
+        AccountMeta::new(*nft_account, false),
+        AccountMeta::new(vault, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    if let Some((inventory_address, inventory)) = inventory {
+        accounts.push(AccountMeta::new(*inventory_address, false));
+        // claiming the last nft closes the inventory
+        if inventory.items.len() <= 1 {
+            accounts.push(AccountMeta::new(inventory.rent_payer, false));
+        }
+    }
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::GET_NFT_AUTHORITY, 0),
+    }
+}
+
+/// Creates a `CancelShareSale` instruction from the decoded escrow of the share sale
+pub fn cancel_share_sale(
+    token_pool: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    delegate: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(escrow.seller, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new_readonly(escrow.escrow_vault, false),
+        AccountMeta::new(escrow.rent_payer, false),
+    ];
+    push_delegate(&mut accounts, delegate);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::CANCEL_SHARE_SALE, 0),
+    }
+}
+
+/// Creates a `CancelNFTListing` instruction from the decoded escrow
+pub fn cancel_nft_listing(escrow_address: &Pubkey, escrow: &Escrow) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(escrow.seller, true),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new(escrow.nft_mint, false),
+            AccountMeta::new_readonly(escrow.escrow_vault, false),
+            AccountMeta::new(escrow.nft, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: amount_payload(variant::CANCEL_NFT_LISTING, 0),
+    }
+}
+
+/// Creates a `ListNFTAuction` instruction, the escrow state account should already
+/// be allocated and owned by the program
+pub fn list_nft_auction(
+    seller: &Pubkey,
+    escrow_state: &Pubkey,
+    nft_mint: &Pubkey,
+    nft_account: &Pubkey,
+    reserve_price: u64,
+    min_bid_increment: u64,
+    end_time: UnixTimestamp,
+) -> Instruction {
+    let (vault, _) = find_listing_vault_address(nft_account);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*seller, true),
+            AccountMeta::new(*escrow_state, false),
+            AccountMeta::new(*nft_mint, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(*nft_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            platform_config_account(),
+        ],
+        data: payload(
+            variant::LIST_NFT_AUCTION,
+            reserve_price,
+            min_bid_increment,
+            String::new(),
+            0,
+            end_time as u64,
+        ),
+    }
+}
+
+/// Creates a `PlaceBid` instruction, pass the token pool to bid with its treasury
+pub fn place_bid(
+    bidder: &Pubkey,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    token_pool: Option<(&Pubkey, &TokenPool)>,
+    amount: u64,
+) -> Instruction {
+    // there is nobody to refund before the first bid, an outbid token pool gets its committed bid back
+    let previous_bidder = if escrow.highest_bid == 0 {
+        *bidder
+    } else if escrow.bidder_pool != Pubkey::default() {
+        escrow.bidder_pool
+    } else {
+        escrow.highest_bidder
+    };
+    let accounts = vec![
+        AccountMeta::new(*bidder, true),
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new(previous_bidder, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        platform_config_account(),
+    ];
+    let mut instruction = Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::PLACE_BID, amount),
+    };
+    if let Some((token_pool_address, token_pool)) = token_pool {
+        instruction
+            .accounts
+            .push(AccountMeta::new(*token_pool_address, false));
+        push_allowed_mints(&mut instruction, token_pool);
+    }
+    instruction
+}
+
+/// Creates a `SettleAuction` instruction from the decoded escrow, the winning
+/// token pool is needed when a token pool has the highest bid
+pub fn settle_auction(
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    winning_pool: Option<&TokenPool>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new(escrow.seller, false),
+        AccountMeta::new(escrow.nft_mint, false),
+        AccountMeta::new_readonly(escrow.escrow_vault, false),
+        AccountMeta::new(escrow.nft, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        platform_config_account(),
+    ];
+    if let Some(token_pool) = winning_pool {
+        accounts.extend([
+            AccountMeta::new(escrow.bidder_pool, false),
+            AccountMeta::new(token_pool.treasurey, false),
+            AccountMeta::new(token_pool.manager, false),
+        ]);
+        if token_pool.target_kind == PoolTargetKind::Basket {
+            accounts.push(AccountMeta::new(token_pool.inventory, false));
+        }
+    }
+    let mut instruction = Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::SETTLE_AUCTION, 0),
+    };
+    if let Some(token_pool) = winning_pool {
+        push_allowed_mints(&mut instruction, token_pool);
+    }
+    instruction
+}
+
+/// Creates a `PlaceShareBid` instruction, `price_per_unit * quantity` lamports are
+/// kept in the share bid account till it is filled or cancelled
+pub fn place_share_bid(
+    bidder: &Pubkey,
+    token_pool: &Pubkey,
+    gate_token_account: Option<&Pubkey>,
+    price_per_unit: u64,
+    quantity: u64,
+) -> Instruction {
+    let (share_bid, _) = find_share_bid_address(token_pool, bidder);
+    let mut accounts = vec![
+        AccountMeta::new(*bidder, true),
+        AccountMeta::new_readonly(*token_pool, false),
+        AccountMeta::new(share_bid, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        platform_config_account(),
+    ];
+    push_gate_token_account(&mut accounts, gate_token_account);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: payload(
+            variant::PLACE_SHARE_BID,
+            price_per_unit,
+            quantity,
+            String::new(),
+            0,
+            0,
+        ),
+    }
+}
+
+/// Creates a `CancelShareBid` instruction
+pub fn cancel_share_bid(bidder: &Pubkey, token_pool: &Pubkey) -> Instruction {
+    let (share_bid, _) = find_share_bid_address(token_pool, bidder);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*bidder, true),
+            AccountMeta::new(share_bid, false),
+        ],
+        data: amount_payload(variant::CANCEL_SHARE_BID, 0),
+    }
+}
+
+/// Creates a `FillBid` instruction selling `units` of the share of a member into
+/// the bid of `bidder`, the bid should pay at least `min_price_per_unit`
+pub fn fill_bid(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    bidder: &Pubkey,
+    units: u64,
+    min_price_per_unit: u64,
+) -> Instruction {
+    let (share_bid, _) = find_share_bid_address(token_pool, bidder);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*member, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new(share_bid, false),
+            AccountMeta::new(*bidder, false),
+            platform_config_account(),
+        ],
+        data: payload(
+            variant::FILL_BID,
+            units,
+            min_price_per_unit,
+            String::new(),
+            0,
+            0,
+        ),
+    }
+}
+
+/// Creates an `AddAllowedMints` instruction, the allowed mints account should already be
+/// allocated and owned by the program, see `state::allowed_mints_account_size`
+pub fn add_allowed_mints(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    allowed_mints: &Pubkey,
+    mints: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(*allowed_mints, false),
+    ];
+    accounts.extend(
+        mints
+            .iter()
+            .map(|mint| AccountMeta::new_readonly(*mint, false)),
+    );
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::ADD_ALLOWED_MINTS, 0),
+    }
+}
+
+/// Creates an `InitializeBasket` instruction, the inventory account should already be
+/// allocated and owned by the program, see `state::inventory_account_size`
+pub fn initialize_basket(manager: &Pubkey, token_pool: &Pubkey, inventory: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new(*inventory, false),
+        ],
+        data: amount_payload(variant::INITIALIZE_BASKET, 0),
+    }
+}
+
+/// Creates a `SetFungibleTarget` instruction, the token pool buys `token_amount` of `mint`
+pub fn set_fungible_target(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    mint: &Pubkey,
+    token_amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+        data: amount_payload(variant::SET_FUNGIBLE_TARGET, token_amount),
+    }
+}
+
+/// Creates a `ListTokens` instruction selling all the tokens of `token_account` for `amount`
+/// lamports, the escrow account should already be allocated and owned by the program
+pub fn list_tokens(
+    seller: &Pubkey,
+    escrow_state: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (vault, _) = find_listing_vault_address(token_account);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*seller, true),
+            AccountMeta::new(*escrow_state, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(*token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            platform_config_account(),
+        ],
+        data: amount_payload(variant::LIST_TOKENS, amount),
+    }
+}
+
+/// Creates an `ExecuteTokenBuy` instruction from the decoded token pool and escrow,
+/// `amount` should be the price asked in the escrow
+pub fn execute_token_buy(
+    buyer: &Pubkey,
+    token_pool_address: &Pubkey,
+    token_pool: &TokenPool,
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*buyer, true),
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new_readonly(token_pool.vault, false),
+            AccountMeta::new(escrow.nft, false),
+            AccountMeta::new(*token_pool_address, false),
+            AccountMeta::new(token_pool.treasurey, false),
+            AccountMeta::new(escrow.seller, false),
+            AccountMeta::new_readonly(escrow.nft_mint, false),
+            AccountMeta::new_readonly(escrow.escrow_vault, false),
+            AccountMeta::new(token_pool.manager, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            platform_config_account(),
+        ],
+        data: amount_payload(variant::EXECUTE_TOKEN_BUY, amount),
+    }
+}
+
+/// Creates a `Redeem` instruction paying a member of a fungible token pool their
+/// pro-rata amount of the tokens held by `pool_token_account`
+pub fn redeem(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    pool_token_account: &Pubkey,
+    member_token_account: &Pubkey,
+) -> Instruction {
+    let (vault, _) = find_pool_vault_address(token_pool);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*member, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new(*pool_token_account, false),
+            AccountMeta::new(*member_token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: amount_payload(variant::REDEEM, 0),
+    }
+}
+
+/// Creates a `SetBuyoutTerms` instruction, a zero reserve price disables buyouts
+pub fn set_buyout_terms(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    reserve_price: u64,
+    objection_period: UnixTimestamp,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: payload(
+            variant::SET_BUYOUT_TERMS,
+            reserve_price,
+            0,
+            String::new(),
+            0,
+            objection_period as u64,
+        ),
+    }
+}
+
+/// Creates a `StartBuyout` instruction, the current buyout is needed to refund its
+/// buyer when the offer replaces it
+pub fn start_buyout(
+    buyer: &Pubkey,
+    token_pool: &Pubkey,
+    current_buyout: Option<&Buyout>,
+    price: u64,
+) -> Instruction {
+    let (buyout, _) = find_buyout_address(token_pool);
+    let current_buyer = current_buyout.map_or(*buyer, |buyout| buyout.buyer);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*buyer, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new(buyout, false),
+            AccountMeta::new(current_buyer, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            platform_config_account(),
+        ],
+        data: amount_payload(variant::START_BUYOUT, price),
+    }
+}
+
+/// Creates an `ObjectBuyout` instruction
+pub fn object_buyout(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    delegate: Option<&Pubkey>,
+) -> Instruction {
+    let (buyout, _) = find_buyout_address(token_pool);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*member, true),
+        AccountMeta::new_readonly(*token_pool, false),
+        AccountMeta::new(buyout, false),
+    ];
+    push_delegate(&mut accounts, delegate);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::OBJECT_BUYOUT, 0),
+    }
+}
+
+/// Creates a `CompleteBuyout` instruction from the decoded token pool and buyout
+pub fn complete_buyout(
+    token_pool_address: &Pubkey,
+    token_pool: &TokenPool,
+    buyout: &Buyout,
+    nft_account: &Pubkey,
+) -> Instruction {
+    let (buyout_address, _) = find_buyout_address(token_pool_address);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(buyout_address, false),
+            AccountMeta::new(*token_pool_address, false),
+            AccountMeta::new(buyout.buyer, false),
+            AccountMeta::new(token_pool.target_token, false),
+            AccountMeta::new(*nft_account, false),
+            AccountMeta::new_readonly(token_pool.vault, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            platform_config_account(),
+        ],
+        data: amount_payload(variant::COMPLETE_BUYOUT, 0),
+    }
+}
+
+/// Creates a `ClaimBuyoutPayout` instruction
+pub fn claim_buyout_payout(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    buyout: &Buyout,
+    delegate: Option<&Pubkey>,
+) -> Instruction {
+    let (buyout_address, _) = find_buyout_address(token_pool);
+    let mut accounts = vec![
+        AccountMeta::new(*member, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(buyout_address, false),
+        AccountMeta::new(buyout.buyer, false),
+        platform_config_account(),
+    ];
+    push_delegate(&mut accounts, delegate);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::CLAIM_BUYOUT_PAYOUT, 0),
+    }
+}
+
+/// Creates a `SetGateMint` instruction, `None` lets anyone join the token pool again
+pub fn set_gate_mint(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    gate_mint: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new(*token_pool, false),
+    ];
+    if let Some(gate_mint) = gate_mint {
+        accounts.push(AccountMeta::new_readonly(*gate_mint, false));
+    }
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::SET_GATE_MINT, 0),
+    }
+}
+
+/// Creates a `SetMaximumAmount` instruction, zero removes the cap on what a member contributes
+pub fn set_maximum_amount(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    maximum_amount: u64,
+    maximum_kind: MaximumKind,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: payload(
+            variant::SET_MAXIMUM_AMOUNT,
+            maximum_amount,
+            maximum_kind as u64,
+            String::new(),
+            0,
+            0,
+        ),
+    }
+}
+
+/// Creates a `SetManagerShare` instruction, the manager share is in share units and granted when the pool buys
+pub fn set_manager_share(manager: &Pubkey, token_pool: &Pubkey, manager_share: u64) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: amount_payload(variant::SET_MANAGER_SHARE, manager_share),
+    }
+}
+
+/// Creates a `SetPlatformConfig` instruction, only the upgrade authority of the program can create
+/// the platform config
+pub fn set_platform_config(authority: &Pubkey, max_members: u32) -> Instruction {
+    let (platform_config, _) = find_platform_config_address();
+    let (program_data, _) = find_program_data_address();
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(platform_config, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_data, false),
+        ],
+        data: payload(
+            variant::SET_PLATFORM_CONFIG,
+            0,
+            0,
+            String::new(),
+            max_members,
+            0,
+        ),
+    }
+}
+
+/// Creates a `SetAdmissionPolicy` instruction
+pub fn set_admission_policy(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    admission_policy: AdmissionPolicy,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: amount_payload(variant::SET_ADMISSION_POLICY, admission_policy as u64),
+    }
+}
+
+/// Creates a `SetSubscriptionEnd` instruction, zero stops oversubscribing the token pool
+pub fn set_subscription_end(
+    manager: &Pubkey,
+    token_pool: &Pubkey,
+    subscription_end: UnixTimestamp,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: payload(
+            variant::SET_SUBSCRIPTION_END,
+            0,
+            0,
+            String::new(),
+            0,
+            subscription_end as u64,
+        ),
+    }
+}
+
+/// Creates a `CloseSubscription` instruction, anyone can send it once the subscription has ended
+pub fn close_subscription(token_pool: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![AccountMeta::new(*token_pool, false)],
+        data: amount_payload(variant::CLOSE_SUBSCRIPTION, 0),
+    }
+}
+
+/// Creates a `ClaimRefund` instruction
+pub fn claim_refund(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    treasury: &Pubkey,
+    delegate: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*member, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new(*treasury, false),
+    ];
+    push_delegate(&mut accounts, delegate);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::CLAIM_REFUND, 0),
+    }
+}
+
+/// Creates a `SetEscrowExpiry` instruction, zero never expires the escrow
+pub fn set_escrow_expiry(
+    seller: &Pubkey,
+    escrow_state: &Pubkey,
+    expires_at: UnixTimestamp,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*seller, true),
+            AccountMeta::new(*escrow_state, false),
+        ],
+        data: payload(
+            variant::SET_ESCROW_EXPIRY,
+            0,
+            0,
+            String::new(),
+            0,
+            expires_at as u64,
+        ),
+    }
+}
+
+/// Creates an `ExpireEscrow` instruction from the decoded escrow, a share sale needs its token pool
+pub fn expire_escrow(
+    escrow_address: &Pubkey,
+    escrow: &Escrow,
+    token_pool: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*escrow_address, false),
+        AccountMeta::new(escrow.seller, false),
+        AccountMeta::new_readonly(escrow.escrow_vault, false),
+        AccountMeta::new(escrow.rent_payer, false),
+    ];
+    match token_pool {
+        Some(token_pool) => accounts.push(AccountMeta::new(*token_pool, false)),
+        None => accounts.extend([
+            AccountMeta::new(escrow.nft_mint, false),
+            AccountMeta::new(escrow.nft, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ]),
+    }
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::EXPIRE_ESCROW, 0),
+    }
+}
+
+/// Creates a `TransferShare` instruction, zero units transfers the whole share of member
+pub fn transfer_share(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    recipient: &Pubkey,
+    gate_token_account: Option<&Pubkey>,
+    units: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*member, true),
+        AccountMeta::new(*token_pool, false),
+        AccountMeta::new_readonly(*recipient, false),
+        platform_config_account(),
+    ];
+    push_gate_token_account(&mut accounts, gate_token_account);
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::TRANSFER_SHARE, units),
+    }
+}
+
+/// Creates an `ApproveDelegate` instruction, permissions are the bits of `DELEGATE_VOTE`,
+/// `DELEGATE_SELL` and `DELEGATE_CLAIM`
+pub fn approve_delegate(
+    member: &Pubkey,
+    token_pool: &Pubkey,
+    delegate: &Pubkey,
+    permissions: u8,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*member, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new_readonly(*delegate, false),
+        ],
+        data: amount_payload(variant::APPROVE_DELEGATE, permissions as u64),
+    }
+}
+
+/// Creates a `RevokeDelegate` instruction
+pub fn revoke_delegate(member: &Pubkey, token_pool: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*member, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: amount_payload(variant::REVOKE_DELEGATE, 0),
+    }
+}
+
+/// Signs an instruction of a token pool managed by a multisig with the signers of the multisig
+/// in place of the multisig
+pub fn multisig_signed(
+    mut instruction: Instruction,
+    multisig: &Pubkey,
+    signers: &[Pubkey],
+) -> Instruction {
+    for account in instruction.accounts.iter_mut() {
+        if account.pubkey == *multisig {
+            account.is_signer = false;
+        }
+    }
+    instruction.accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    instruction
+}
+
+/// Creates an `InitializeMultisig` instruction, the multisig account signs and should be allocated
+/// rent exempt with `Multisig::LEN` and owned by the program earlier in the same transaction
+pub fn initialize_multisig(multisig: &Pubkey, signers: &[Pubkey], m: u8) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*multisig, true)];
+    accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, false)),
+    );
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::INITIALIZE_MULTISIG, m as u64),
+    }
+}
+
+/// Creates a `CollectFees` instruction, zero collects every lamport over the rent exemption
+pub fn collect_fees(
+    multisig: &Pubkey,
+    destination: &Pubkey,
+    signers: &[Pubkey],
+    amount: u64,
+) -> Instruction {
+    multisig_signed(
+        Instruction {
+            program_id: token_pool::id(),
+            accounts: vec![
+                AccountMeta::new(*multisig, false),
+                AccountMeta::new(*destination, false),
+            ],
+            data: amount_payload(variant::COLLECT_FEES, amount),
+        },
+        multisig,
+        signers,
+    )
+}
+
+/// Creates an `AcceptManager` instruction
+pub fn accept_manager(new_manager: &Pubkey, token_pool: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*new_manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: amount_payload(variant::ACCEPT_MANAGER, 0),
+    }
+}
+
+/// Creates a `CancelManagerTransfer` instruction
+pub fn cancel_manager_transfer(manager: &Pubkey, token_pool: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: amount_payload(variant::CANCEL_MANAGER_TRANSFER, 0),
+    }
+}
+
+/// Creates a `SetPoolPaused` instruction, a paused token pool takes no contributions, trades or purchases
+pub fn set_pool_paused(manager: &Pubkey, token_pool: &Pubkey, paused: bool) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*manager, true),
+            AccountMeta::new(*token_pool, false),
+        ],
+        data: amount_payload(variant::SET_POOL_PAUSED, paused as u64),
+    }
+}
+
+/// Creates a `SetPlatformPaused` instruction, which pauses or resumes every token pool
+pub fn set_platform_paused(authority: &Pubkey, paused: bool) -> Instruction {
+    let (platform_config, _) = find_platform_config_address();
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(platform_config, false),
+        ],
+        data: amount_payload(variant::SET_PLATFORM_PAUSED, paused as u64),
+    }
+}
+
+/// Creates a `ClosePool` instruction refunding the rent of a finished token pool to its rent payer,
+/// the manager signs only to close a pool nobody has joined. the treasury has to be closed first
+pub fn close_pool(
+    token_pool_address: &Pubkey,
+    token_pool: &TokenPool,
+    manager: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*token_pool_address, false),
+        AccountMeta::new_readonly(token_pool.treasurey, false),
+        AccountMeta::new(token_pool.rent_payer, false),
+    ];
+    if let Some(manager) = manager {
+        accounts.push(AccountMeta::new_readonly(*manager, true));
+    }
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::CLOSE_POOL, 0),
+    }
+}
+
+/// Creates a `CloseEscrow` instruction refunding the rent of the escrow of a sold nft to its rent payer
+pub fn close_escrow(escrow_address: &Pubkey, escrow: &Escrow) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*escrow_address, false),
+            AccountMeta::new(escrow.rent_payer, false),
+        ],
+        data: amount_payload(variant::CLOSE_ESCROW, 0),
+    }
+}
+
+/// Creates a `CloseTreasury` instruction refunding the treasury of a finished token pool to its rent
+/// payer, the manager signs only to close a pool nobody has joined
+pub fn close_treasury(
+    token_pool_address: &Pubkey,
+    token_pool: &TokenPool,
+    manager: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_pool_address, false),
+        AccountMeta::new(token_pool.treasurey, false),
+        AccountMeta::new(token_pool.rent_payer, false),
+    ];
+    if let Some(manager) = manager {
+        accounts.push(AccountMeta::new_readonly(*manager, true));
+    }
+    Instruction {
+        program_id: token_pool::id(),
+        accounts,
+        data: amount_payload(variant::CLOSE_TREASURY, 0),
+    }
+}
+
+/// Creates a `SweepTreasury` instruction moving what the treasury holds over its current balance,
+/// refunds due and rent to the manager
+pub fn sweep_treasury(manager: &Pubkey, token_pool: &Pubkey, treasury: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*manager, true),
+            AccountMeta::new_readonly(*token_pool, false),
+            AccountMeta::new(*treasury, false),
+        ],
+        data: amount_payload(variant::SWEEP_TREASURY, 0),
+    }
+}
+
+/// Creates a `CloseAllowedMints` instruction refunding the allowed mints of a finished or closed
+/// token pool to the manager who set them up
+pub fn close_allowed_mints(
+    allowed_mints_address: &Pubkey,
+    allowed_mints: &AllowedMints,
+) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*allowed_mints_address, false),
+            AccountMeta::new_readonly(allowed_mints.token_pool, false),
+            AccountMeta::new(allowed_mints.rent_payer, false),
+        ],
+        data: amount_payload(variant::CLOSE_ALLOWED_MINTS, 0),
+    }
+}
+
+/// Creates a `MigrateTokenPool` instruction moving a token pool created before the layout was
+/// versioned to the current layout, the payer funds the rent of the bigger account
+pub fn migrate_token_pool(payer: &Pubkey, token_pool: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*token_pool, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: amount_payload(variant::MIGRATE_TOKEN_POOL, 0),
+    }
+}
+
+/// Creates a `MigrateEscrow` instruction moving an escrow created before listings had kinds to the
+/// current layout, the payer funds the rent of the bigger account
+pub fn migrate_escrow(payer: &Pubkey, escrow: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: token_pool::id(),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*escrow, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: amount_payload(variant::MIGRATE_ESCROW, 0),
+    }
+}
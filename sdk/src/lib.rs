@@ -0,0 +1,19 @@
+//! Rust client library for the token pool program: decodes pool, escrow and
+//! share bid accounts, derives program addresses and builds instructions.
+pub mod error;
+pub mod instruction;
+pub mod pda;
+pub mod rpc;
+pub mod state;
+
+pub use token_pool::{
+    id,
+    state::{
+        max_members_for, share_to_units, units_to_share, AccountType, AdmissionPolicy,
+        AllowedMints, Buyout, BuyoutStage, Escrow, EscrowStage, Inventory, InventoryItem,
+        ListingKind, MaximumKind, Multisig, PlatformConfig, PoolMemberList, PoolMemberShareInfo,
+        PoolTargetKind, ShareBid, ShareBidStage, ShareStage, TokenPool, TokenPoolHeader,
+        TokenPoolStage, DELEGATE_CLAIM, DELEGATE_SELL, DELEGATE_VOTE, MAX_DESCRIPTION_LEN,
+        MAX_POOL_MEMBERS, MAX_SIGNERS, TOTAL_SHARE_UNITS,
+    },
+};
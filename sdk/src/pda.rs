@@ -0,0 +1,62 @@
+use solana_program::{bpf_loader_upgradeable, pubkey::Pubkey};
+
+/// Seed of the vault which owns the nft bought by a token pool
+pub const POOL_VAULT_SEED: &[u8] = b"pool";
+/// Seed of the vault which holds the authority of a listed nft
+pub const LISTING_VAULT_SEED: &[u8] = b"listnft";
+/// Seed of the vault which holds a member's share while it is on sale
+pub const SHARE_ESCROW_VAULT_SEED: &[u8] = b"escrow";
+/// Seed of the account which holds a standing bid for the share units of a token pool
+pub const SHARE_BID_SEED: &[u8] = b"bid";
+/// Seed of the account which holds the buyout offer for a token pool
+pub const BUYOUT_SEED: &[u8] = b"buyout";
+/// Seed of the platform config account
+pub const PLATFORM_SEED: &[u8] = b"platform";
+
+/// find the vault of a token pool, which will own the nft bought using pool money
+pub fn find_pool_vault_address(token_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_VAULT_SEED, token_pool.as_ref()], &token_pool::id())
+}
+
+/// find the vault which holds the authority of a listed nft account
+pub fn find_listing_vault_address(nft_account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LISTING_VAULT_SEED, nft_account.as_ref()],
+        &token_pool::id(),
+    )
+}
+
+/// find the escrow vault which holds the share of a seller in a token pool
+pub fn find_share_escrow_vault_address(seller: &Pubkey, token_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SHARE_ESCROW_VAULT_SEED,
+            seller.as_ref(),
+            token_pool.as_ref(),
+        ],
+        &token_pool::id(),
+    )
+}
+
+/// find the account which holds the standing bid of a bidder for share units of a token pool
+pub fn find_share_bid_address(token_pool: &Pubkey, bidder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SHARE_BID_SEED, token_pool.as_ref(), bidder.as_ref()],
+        &token_pool::id(),
+    )
+}
+
+/// find the account which holds the buyout offer for a token pool
+pub fn find_buyout_address(token_pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BUYOUT_SEED, token_pool.as_ref()], &token_pool::id())
+}
+
+/// find the platform config account, which limits the token pools created on the platform
+pub fn find_platform_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLATFORM_SEED], &token_pool::id())
+}
+
+/// find the program data account of the token pool program, which holds its upgrade authority
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[token_pool::id().as_ref()], &bpf_loader_upgradeable::id())
+}
@@ -0,0 +1,183 @@
+use {
+    crate::{
+        error::{ClientError, ClientResult},
+        pda::{find_buyout_address, find_platform_config_address},
+        state::{
+            decode_allowed_mints, decode_buyout, decode_escrow, decode_inventory, decode_multisig,
+            decode_platform_config, decode_share_bid, decode_token_pool, pool_members,
+            share_bid_account_size, SHARE_BID_TOKEN_POOL_OFFSET, TARGET_TOKEN_OFFSET,
+        },
+    },
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_program::pubkey::Pubkey,
+    token_pool::state::{
+        AllowedMints, Buyout, Escrow, Inventory, Multisig, PlatformConfig, PoolMemberShareInfo,
+        ShareBid, TokenPool,
+    },
+};
+
+/// fetch the data of an account and check that it is owned by the token pool program
+async fn get_program_account_data(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> ClientResult<Vec<u8>> {
+    let account = rpc_client
+        .get_account_with_commitment(address, rpc_client.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    if account.owner != token_pool::id() {
+        return Err(ClientError::InvalidOwner(*address));
+    }
+    Ok(account.data)
+}
+
+/// fetch and decode a token pool account
+pub async fn get_token_pool(rpc_client: &RpcClient, address: &Pubkey) -> ClientResult<TokenPool> {
+    let data = get_program_account_data(rpc_client, address).await?;
+    decode_token_pool(address, &data)
+}
+
+/// fetch and decode an escrow account
+pub async fn get_escrow(rpc_client: &RpcClient, address: &Pubkey) -> ClientResult<Escrow> {
+    let data = get_program_account_data(rpc_client, address).await?;
+    decode_escrow(address, &data)
+}
+
+/// fetch and decode the allowed mints account of a collection token pool
+pub async fn get_allowed_mints(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> ClientResult<AllowedMints> {
+    let data = get_program_account_data(rpc_client, address).await?;
+    decode_allowed_mints(address, &data)
+}
+
+/// fetch and decode the inventory account of a basket token pool
+pub async fn get_inventory(rpc_client: &RpcClient, address: &Pubkey) -> ClientResult<Inventory> {
+    let data = get_program_account_data(rpc_client, address).await?;
+    decode_inventory(address, &data)
+}
+
+/// fetch and decode the buyout offer for a token pool
+pub async fn get_buyout(rpc_client: &RpcClient, token_pool: &Pubkey) -> ClientResult<Buyout> {
+    let (address, _) = find_buyout_address(token_pool);
+    let data = get_program_account_data(rpc_client, &address).await?;
+    decode_buyout(&address, &data)
+}
+
+/// fetch and decode the platform config account
+pub async fn get_platform_config(rpc_client: &RpcClient) -> ClientResult<PlatformConfig> {
+    let (address, _) = find_platform_config_address();
+    let data = get_program_account_data(rpc_client, &address).await?;
+    decode_platform_config(&address, &data)
+}
+
+/// fetch and decode a multisig account
+pub async fn get_multisig(rpc_client: &RpcClient, address: &Pubkey) -> ClientResult<Multisig> {
+    let data = get_program_account_data(rpc_client, address).await?;
+    decode_multisig(address, &data)
+}
+
+/// fetch and decode a share bid account
+pub async fn get_share_bid(rpc_client: &RpcClient, address: &Pubkey) -> ClientResult<ShareBid> {
+    let data = get_program_account_data(rpc_client, address).await?;
+    decode_share_bid(address, &data)
+}
+
+/// fetch the open share bids of a token pool, best priced bid first
+pub async fn get_share_bids(
+    rpc_client: &RpcClient,
+    token_pool: &Pubkey,
+) -> ClientResult<Vec<(Pubkey, ShareBid)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize(share_bid_account_size() as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                SHARE_BID_TOKEN_POOL_OFFSET,
+                token_pool.as_ref(),
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc_client.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&token_pool::id(), config)
+        .await?;
+    let mut share_bids: Vec<(Pubkey, ShareBid)> = accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            decode_share_bid(&address, &account.data)
+                .ok()
+                .map(|share_bid| (address, share_bid))
+        })
+        .collect();
+    share_bids.sort_by_key(|(_, share_bid)| std::cmp::Reverse(share_bid.price_per_unit));
+    Ok(share_bids)
+}
+
+/// fetch the members of a token pool along with their shares
+pub async fn get_pool_members(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> ClientResult<Vec<PoolMemberShareInfo>> {
+    let token_pool = get_token_pool(rpc_client, address).await?;
+    Ok(pool_members(&token_pool))
+}
+
+/// find all the token pools which are buying the given target mint, collection
+/// pools are only found by the mint once they have bought it
+pub async fn find_pools_by_target_mint(
+    rpc_client: &RpcClient,
+    target_mint: &Pubkey,
+) -> ClientResult<Vec<(Pubkey, TokenPool)>> {
+    get_token_pools_with_filters(
+        rpc_client,
+        vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            TARGET_TOKEN_OFFSET,
+            target_mint.as_ref(),
+        ))],
+    )
+    .await
+}
+
+/// fetch all the token pools of the program
+pub async fn get_token_pools(rpc_client: &RpcClient) -> ClientResult<Vec<(Pubkey, TokenPool)>> {
+    get_token_pools_with_filters(rpc_client, vec![]).await
+}
+
+async fn get_token_pools_with_filters(
+    rpc_client: &RpcClient,
+    filters: Vec<RpcFilterType>,
+) -> ClientResult<Vec<(Pubkey, TokenPool)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: (!filters.is_empty()).then_some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc_client.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&token_pool::id(), config)
+        .await?;
+    // escrow accounts are owned by the program as well, so only keep what decodes as a token pool
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(address, account)| {
+            decode_token_pool(&address, &account.data)
+                .ok()
+                .map(|token_pool| (address, token_pool))
+        })
+        .collect())
+}
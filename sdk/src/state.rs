@@ -0,0 +1,242 @@
+use {
+    crate::error::{ClientError, ClientResult},
+    solana_program::{
+        borsh::{get_instance_packed_len, try_from_slice_unchecked},
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+    token_pool::state::{
+        AccountType, AdmissionPolicy, AllowedMints, Buyout, BuyoutStage, Escrow, Inventory,
+        MaximumKind, Multisig, PlatformConfig, PoolMemberList, PoolMemberShareInfo, PoolTargetKind,
+        ShareBid, ShareBidStage, TokenPool, TokenPoolStage, TOKEN_POOL_VERSION,
+    },
+};
+
+/// Offset of `TokenPool::target_token` in the token pool account data,
+/// version (1) + stage (1) + target, minimum exemption, minimum and current amounts (8 * 4)
+pub const TARGET_TOKEN_OFFSET: usize = 1 + 1 + 8 + 8 + 8 + 8;
+
+/// decode the data of a token pool account
+pub fn decode_token_pool(address: &Pubkey, data: &[u8]) -> ClientResult<TokenPool> {
+    let token_pool =
+        TokenPool::load(data).map_err(|_| ClientError::InvalidAccountData(*address))?;
+    if token_pool.stage == TokenPoolStage::Uninitialized {
+        return Err(ClientError::InvalidAccountData(*address));
+    }
+    Ok(token_pool)
+}
+
+/// decode the data of an escrow account created by `SellShare` or `ListNFT`
+pub fn decode_escrow(address: &Pubkey, data: &[u8]) -> ClientResult<Escrow> {
+    Escrow::unpack_unchecked(data).map_err(|_| ClientError::InvalidAccountData(*address))
+}
+
+/// Offset of `ShareBid::token_pool` in the share bid account data, after the stage (1)
+pub const SHARE_BID_TOKEN_POOL_OFFSET: usize = 1;
+
+/// decode the data of a share bid account created by `PlaceShareBid`
+pub fn decode_share_bid(address: &Pubkey, data: &[u8]) -> ClientResult<ShareBid> {
+    let share_bid =
+        ShareBid::unpack_unchecked(data).map_err(|_| ClientError::InvalidAccountData(*address))?;
+    if share_bid.stage != ShareBidStage::Open {
+        return Err(ClientError::InvalidAccountData(*address));
+    }
+    Ok(share_bid)
+}
+
+/// decode the data of an allowed mints account of a collection token pool
+pub fn decode_allowed_mints(address: &Pubkey, data: &[u8]) -> ClientResult<AllowedMints> {
+    let allowed_mints = try_from_slice_unchecked::<AllowedMints>(data)
+        .map_err(|_| ClientError::InvalidAccountData(*address))?;
+    if allowed_mints.token_pool == Pubkey::default() {
+        return Err(ClientError::InvalidAccountData(*address));
+    }
+    Ok(allowed_mints)
+}
+
+/// decode the data of an inventory account of a basket token pool
+pub fn decode_inventory(address: &Pubkey, data: &[u8]) -> ClientResult<Inventory> {
+    let inventory = try_from_slice_unchecked::<Inventory>(data)
+        .map_err(|_| ClientError::InvalidAccountData(*address))?;
+    if inventory.token_pool == Pubkey::default() {
+        return Err(ClientError::InvalidAccountData(*address));
+    }
+    Ok(inventory)
+}
+
+/// decode the data of the platform config account
+pub fn decode_platform_config(address: &Pubkey, data: &[u8]) -> ClientResult<PlatformConfig> {
+    let platform_config = PlatformConfig::unpack_unchecked(data)
+        .map_err(|_| ClientError::InvalidAccountData(*address))?;
+    if !platform_config.is_initialized {
+        return Err(ClientError::InvalidAccountData(*address));
+    }
+    Ok(platform_config)
+}
+
+/// decode the data of a multisig account created by `InitializeMultisig`
+pub fn decode_multisig(address: &Pubkey, data: &[u8]) -> ClientResult<Multisig> {
+    let multisig =
+        Multisig::unpack_unchecked(data).map_err(|_| ClientError::InvalidAccountData(*address))?;
+    if !multisig.is_initialized {
+        return Err(ClientError::InvalidAccountData(*address));
+    }
+    Ok(multisig)
+}
+
+/// decode the data of a buyout account created by `StartBuyout`
+pub fn decode_buyout(address: &Pubkey, data: &[u8]) -> ClientResult<Buyout> {
+    let buyout = try_from_slice_unchecked::<Buyout>(data)
+        .map_err(|_| ClientError::InvalidAccountData(*address))?;
+    if buyout.stage == BuyoutStage::Uninitialized {
+        return Err(ClientError::InvalidAccountData(*address));
+    }
+    Ok(buyout)
+}
+
+/// get the members of a token pool, skipping the empty member slots
+pub fn pool_members(token_pool: &TokenPool) -> Vec<PoolMemberShareInfo> {
+    token_pool
+        .pool_member_list
+        .members
+        .iter()
+        .filter(|member| member.account_type == AccountType::TokenPoolMember)
+        .copied()
+        .collect()
+}
+
+/// size of the token pool account needed for `max_members` and the given description
+pub fn token_pool_account_size(max_members: u32, description: &str) -> usize {
+    get_instance_packed_len(&blank_token_pool(max_members, description)).unwrap()
+}
+
+/// an uninitialized token pool with room for `max_members` and the given description
+fn blank_token_pool(max_members: u32, description: &str) -> TokenPool {
+    TokenPool {
+        version: TOKEN_POOL_VERSION,
+        stage: TokenPoolStage::Uninitialized,
+        target_amount: 0,
+        minimum_exemption_amount: 0,
+        minimum_amount: 0,
+        current_balance: 0,
+        target_token: Pubkey::default(),
+        description: description.to_string(),
+        vault: Pubkey::default(),
+        manager: Pubkey::default(),
+        treasurey: Pubkey::default(),
+        committed_bid: 0,
+        bid_escrow: Pubkey::default(),
+        target_kind: PoolTargetKind::SingleMint,
+        inventory: Pubkey::default(),
+        target_token_amount: 0,
+        reserve_price: 0,
+        objection_period: 0,
+        buyout_end: 0,
+        gate_mint: Pubkey::default(),
+        maximum_amount: 0,
+        maximum_kind: MaximumKind::Amount,
+        manager_share: 0,
+        admission_policy: AdmissionPolicy::LastMember,
+        subscription_end: 0,
+        pending_manager: Pubkey::default(),
+        paused: false,
+        rent_payer: Pubkey::default(),
+        pool_member_list: PoolMemberList::new(max_members),
+    }
+}
+
+/// size of an escrow account
+pub fn escrow_account_size() -> usize {
+    Escrow::LEN
+}
+
+/// size of a share bid account
+pub fn share_bid_account_size() -> usize {
+    ShareBid::LEN
+}
+
+/// size of a multisig account
+pub fn multisig_account_size() -> usize {
+    Multisig::LEN
+}
+
+/// size of an allowed mints account which can hold `max_mints` mints
+pub fn allowed_mints_account_size(max_mints: usize) -> usize {
+    AllowedMints::account_size(max_mints)
+}
+
+/// size of an inventory account which can hold `max_nfts` nfts
+pub fn inventory_account_size(max_nfts: usize) -> usize {
+    Inventory::account_size(max_nfts)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        borsh::BorshSerialize,
+        solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE,
+        token_pool::state::{MAX_DESCRIPTION_LEN, MAX_POOL_MEMBERS},
+    };
+
+    #[test]
+    fn largest_token_pool_account_can_be_created() {
+        let description = "x".repeat(MAX_DESCRIPTION_LEN);
+        assert!(
+            token_pool_account_size(MAX_POOL_MEMBERS, &description) <= MAX_PERMITTED_DATA_INCREASE
+        );
+    }
+
+    #[test]
+    fn token_pool_is_decoded_with_its_target_token_at_the_offset() {
+        let address = Pubkey::new_unique();
+        let mut token_pool = blank_token_pool(2, "pool");
+        token_pool.stage = TokenPoolStage::Initialized;
+        token_pool.target_token = Pubkey::new_unique();
+        let data = token_pool.try_to_vec().unwrap();
+
+        assert_eq!(decode_token_pool(&address, &data).unwrap(), token_pool);
+        assert_eq!(
+            data[TARGET_TOKEN_OFFSET..TARGET_TOKEN_OFFSET + 32],
+            token_pool.target_token.to_bytes()
+        );
+    }
+
+    #[test]
+    fn token_pool_of_another_layout_or_not_initialized_is_not_decoded() {
+        let address = Pubkey::new_unique();
+        let mut token_pool = blank_token_pool(2, "pool");
+        assert!(matches!(
+            decode_token_pool(&address, &token_pool.try_to_vec().unwrap()),
+            Err(ClientError::InvalidAccountData(key)) if key == address
+        ));
+
+        token_pool.stage = TokenPoolStage::Initialized;
+        let mut data = token_pool.try_to_vec().unwrap();
+        data[0] = TOKEN_POOL_VERSION - 1;
+        assert!(matches!(
+            decode_token_pool(&address, &data),
+            Err(ClientError::InvalidAccountData(key)) if key == address
+        ));
+    }
+
+    #[test]
+    fn accounts_not_initialized_are_not_decoded() {
+        let address = Pubkey::new_unique();
+        let invalid = |result: ClientResult<()>| matches!(result, Err(ClientError::InvalidAccountData(key)) if key == address);
+
+        assert!(invalid(decode_escrow(&address, &[0; 8]).map(|_| ())));
+        let share_bid = ShareBid::default().try_to_vec().unwrap();
+        assert!(invalid(decode_share_bid(&address, &share_bid).map(|_| ())));
+        let platform_config = PlatformConfig::default().try_to_vec().unwrap();
+        assert!(invalid(
+            decode_platform_config(&address, &platform_config).map(|_| ())
+        ));
+        let buyout = Buyout::default().try_to_vec().unwrap();
+        assert!(invalid(decode_buyout(&address, &buyout).map(|_| ())));
+        let allowed_mints = AllowedMints::default().try_to_vec().unwrap();
+        assert!(invalid(
+            decode_allowed_mints(&address, &allowed_mints).map(|_| ())
+        ));
+    }
+}